@@ -0,0 +1,8 @@
+Feature: Deployer chosen by the environment
+  Runs on the deployer named by LOGOS_BLOCKCHAIN_CUCUMBER_DEPLOYER (local when
+  unset).
+
+  Scenario: Consensus makes progress
+    Given a star topology of 2 nodes
+    When the scenario runs for 60 seconds
+    Then consensus is live
@@ -0,0 +1,12 @@
+@compose
+Feature: Compose deployer smoke tests
+
+  Scenario: Random restarts under load
+    Given the compose deployer
+    And a ring topology of 4 nodes
+    And 200 funded wallets
+    And no node may fall more than 10 blocks behind
+    When transactions are submitted at 5 per block for 300 seconds
+    And random nodes are restarted every 60 to 120 seconds
+    Then consensus is live
+    And all nodes converge on the same tip
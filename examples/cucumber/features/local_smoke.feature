@@ -0,0 +1,18 @@
+@local
+Feature: Local deployer smoke tests
+
+  Scenario: Transactions keep consensus live
+    Given the local deployer
+    And a star topology of 3 nodes
+    When transactions are submitted at 5 per block for 60 seconds
+    Then the scenario succeeds
+    And consensus is live
+
+  Scenario: A node restart does not stall consensus
+    Given the local deployer
+    And a star topology of 4 nodes
+    And chain safety is checked
+    When transactions are submitted at 5 per block for 90 seconds
+    And node-1 is restarted
+    Then consensus is live
+    And all nodes converge on the same tip
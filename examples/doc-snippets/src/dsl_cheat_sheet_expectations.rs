@@ -6,5 +6,6 @@ use crate::SnippetResult;
 pub fn expectations_plan() -> SnippetResult<Scenario<()>> {
     ScenarioBuilder::topology_with(|t| t.network_star().nodes(1))
         .expect_consensus_liveness() // Assert blocks are produced continuously
+        .expect_chain_safety() // Assert no two nodes finalize conflicting blocks
         .build()
 }
@@ -0,0 +1,14 @@
+use testing_framework_core::{
+    scenario::{Builder, ScenarioBuilder},
+    topology::configs::network::Libp2pNetworkLayout,
+};
+
+pub fn datacenters() -> Builder<()> {
+    ScenarioBuilder::topology_with(|t| {
+        t.nodes(9).network_layout(Libp2pNetworkLayout::Clustered {
+            clusters: 3,            // nodes 0,3,6 / 1,4,7 / 2,5,8
+            inter_cluster_links: 1, // one link between neighbouring clusters
+            seed: 7,
+        })
+    })
+}
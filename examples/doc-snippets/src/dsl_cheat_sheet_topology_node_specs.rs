@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use testing_framework_core::{
+    scenario::{Builder, ScenarioBuilder},
+    topology::configs::node_spec::{NodeRole, NodeSpec},
+};
+
+pub fn mixed_roles() -> Builder<()> {
+    ScenarioBuilder::topology_with(|t| {
+        t.network_star()
+            .nodes(4)
+            .node_spec(0, NodeSpec::default().with_stake(500_000)) // heavier producer
+            .node_spec(
+                2,
+                NodeSpec::new(NodeRole::BlendCore).with_log_level("debug"),
+            )
+            .node_spec(
+                3,
+                NodeSpec::new(NodeRole::Edge)
+                    .with_initial_peers([1])
+                    .with_start_delay(Duration::from_secs(20)), // joins late
+            )
+    })
+}
@@ -0,0 +1,12 @@
+use testing_framework_core::{
+    scenario::{Builder, ScenarioBuilder},
+    topology::configs::stake::StakeDistribution,
+};
+
+pub fn skewed_stake() -> Builder<()> {
+    ScenarioBuilder::topology_with(|t| {
+        t.network_star()
+            .nodes(5)
+            .stake_distribution(StakeDistribution::Whale { share: 0.6 }) // node-0 holds 60%
+    })
+}
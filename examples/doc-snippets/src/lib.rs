@@ -13,6 +13,9 @@ mod dsl_cheat_sheet_expectations;
 mod dsl_cheat_sheet_imports;
 mod dsl_cheat_sheet_run_duration;
 mod dsl_cheat_sheet_topology;
+mod dsl_cheat_sheet_topology_layouts;
+mod dsl_cheat_sheet_topology_node_specs;
+mod dsl_cheat_sheet_topology_stake;
 mod dsl_cheat_sheet_transactions_workload;
 mod dsl_cheat_sheet_wallets;
 mod dsl_cheat_sheet_workload_chaos;
@@ -0,0 +1,174 @@
+use std::{env, process, str::FromStr};
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use runner_examples::DeployerKind;
+use testing_framework_core::scenario::{
+    Deployer as _, NodeControlCapability, ObservabilityCapability, Runner, Scenario,
+};
+use testing_framework_runner_compose::{ComposeDeployer, ComposeRunnerError};
+use testing_framework_runner_k8s::{K8sDeployer, K8sRunnerError};
+use testing_framework_runner_local::LocalDeployer;
+use testing_framework_workflows::{ObservabilityBuilderExt as _, ScenarioFile, ScenarioRegistry};
+use tracing::{info, warn};
+
+const USAGE: &str = "usage: scenario_runner <scenario.yaml|scenario.toml> [local|compose|k8s]";
+
+#[derive(Clone, Copy, Debug)]
+enum Target {
+    Local,
+    Compose,
+    K8s,
+}
+
+impl FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "compose" => Ok(Self::Compose),
+            "k8s" => Ok(Self::K8s),
+            other => Err(anyhow!("unknown deployer '{other}'; {USAGE}")),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (path, target) = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err:#}");
+            process::exit(2);
+        }
+    };
+
+    let log_dir_kind = match target {
+        Target::Local => Some(DeployerKind::Local),
+        Target::Compose => Some(DeployerKind::Compose),
+        Target::K8s => None,
+    };
+    if let Some(kind) = log_dir_kind {
+        runner_examples::defaults::init_node_log_dir_defaults(kind);
+    }
+
+    tracing_subscriber::fmt::init();
+
+    if let Err(err) = run_scenario_file(&path, target).await {
+        warn!("scenario {path} failed: {err:#}");
+        process::exit(1);
+    }
+}
+
+/// The deployer comes from the second argument, falling back to
+/// `LOGOS_BLOCKCHAIN_SCENARIO_DEPLOYER` and then to `local`.
+fn parse_args() -> Result<(String, Target)> {
+    let mut args = env::args().skip(1);
+    let Some(path) = args.next() else {
+        bail!(USAGE);
+    };
+    let target = match args.next() {
+        Some(raw) => raw.parse()?,
+        None => env::var("LOGOS_BLOCKCHAIN_SCENARIO_DEPLOYER")
+            .ok()
+            .map(|raw| raw.parse())
+            .transpose()?
+            .unwrap_or(Target::Local),
+    };
+    Ok((path, target))
+}
+
+async fn run_scenario_file(path: &str, target: Target) -> Result<()> {
+    let file = ScenarioFile::load(path)?;
+    info!(
+        path,
+        ?target,
+        name = file.name.as_deref().unwrap_or("unnamed"),
+        nodes = file.topology.nodes,
+        duration_secs = file.duration_secs,
+        "loaded scenario file"
+    );
+
+    match target {
+        Target::Local => run_local(&file).await,
+        Target::Compose => run_compose(&file).await,
+        Target::K8s => run_k8s(&file).await,
+    }
+}
+
+async fn run_local(file: &ScenarioFile) -> Result<()> {
+    let deployer = LocalDeployer::default();
+
+    if file.requires_node_control() {
+        let mut plan = file
+            .to_builder(&ScenarioRegistry::<NodeControlCapability>::builtin_with_chaos())?
+            .build()?;
+        let runner = deployer
+            .deploy(&plan)
+            .await
+            .context("deploying local nodes failed")?;
+        run_plan(runner, &mut plan).await
+    } else {
+        let mut plan = file
+            .to_builder(&ScenarioRegistry::<()>::builtin())?
+            .build()?;
+        let runner = deployer
+            .deploy(&plan)
+            .await
+            .context("deploying local nodes failed")?;
+        run_plan(runner, &mut plan).await
+    }
+}
+
+async fn run_compose(file: &ScenarioFile) -> Result<()> {
+    // Compose always deploys with node control so chaos entries resolve.
+    let mut plan = file
+        .to_builder(&ScenarioRegistry::<NodeControlCapability>::builtin_with_chaos())?
+        .build()?;
+
+    // Unlike the demo runners, a missing deployer fails the run so it cannot
+    // pass for a successful scenario.
+    let runner = match ComposeDeployer::new().deploy(&plan).await {
+        Ok(runner) => runner,
+        Err(ComposeRunnerError::DockerUnavailable) => {
+            bail!("Docker is unavailable; cannot run compose scenario")
+        }
+        Err(err) => return Err(anyhow::Error::new(err)).context("deploying compose stack failed"),
+    };
+    run_plan(runner, &mut plan).await
+}
+
+async fn run_k8s(file: &ScenarioFile) -> Result<()> {
+    if file.requires_node_control() {
+        bail!("the k8s deployer has no node control; remove the `chaos` section or use compose");
+    }
+
+    let mut scenario = file.to_builder(&ScenarioRegistry::<ObservabilityCapability>::builtin())?;
+    if let Ok(url) = env::var("LOGOS_BLOCKCHAIN_METRICS_QUERY_URL") {
+        if !url.trim().is_empty() {
+            scenario = scenario.with_metrics_query_url_str(url.trim());
+        }
+    }
+    if let Ok(url) = env::var("LOGOS_BLOCKCHAIN_METRICS_OTLP_INGEST_URL") {
+        if !url.trim().is_empty() {
+            scenario = scenario.with_metrics_otlp_ingest_url_str(url.trim());
+        }
+    }
+    let mut plan = scenario.build()?;
+
+    let runner = match K8sDeployer::new().deploy(&plan).await {
+        Ok(runner) => runner,
+        Err(K8sRunnerError::ClientInit { source }) => {
+            bail!("Kubernetes cluster unavailable ({source}); cannot run k8s scenario")
+        }
+        Err(err) => return Err(anyhow::Error::new(err)).context("deploying k8s stack failed"),
+    };
+    run_plan(runner, &mut plan).await
+}
+
+async fn run_plan<Caps: Send + Sync>(runner: Runner, plan: &mut Scenario<Caps>) -> Result<()> {
+    info!("running scenario");
+    runner.run(plan).await.context("running scenario failed")?;
+    info!("scenario complete");
+    Ok(())
+}
@@ -26,7 +26,10 @@ use lb_node::{SignedMantleTx, Transaction as _};
 use lb_utils::math::NonNegativeF64;
 use num_bigint::BigUint;
 
-use super::wallet::{WalletAccount, WalletConfig};
+use super::{
+    stake::{StakeDistribution, StakeDistributionError},
+    wallet::{WalletAccount, WalletConfig},
+};
 use crate::nodes::blend::ACTIVITY_THRESHOLD_SENSITIVITY;
 
 #[derive(Debug, thiserror::Error)]
@@ -41,6 +44,8 @@ pub enum ConsensusConfigError {
     DeclarationSignature { message: String },
     #[error("genesis ledger is missing expected utxo note: {note}")]
     MissingGenesisUtxo { note: String },
+    #[error(transparent)]
+    Stake(#[from] StakeDistributionError),
 }
 
 #[derive(Clone)]
@@ -48,6 +53,7 @@ pub struct ConsensusParams {
     pub n_participants: usize,
     pub security_param: NonZero<u32>,
     pub active_slot_coeff: f64,
+    pub stake_distribution: StakeDistribution,
 }
 
 impl ConsensusParams {
@@ -71,6 +77,7 @@ impl ConsensusParams {
             security_param: unsafe { NonZero::new_unchecked(10) },
             // a block should be produced (on average) every slot
             active_slot_coeff,
+            stake_distribution: StakeDistribution::Uniform,
         }
     }
 }
@@ -102,6 +109,9 @@ impl ProviderInfo {
 pub struct GeneralConsensusConfig {
     pub leader_pk: ZkPublicKey,
     pub leader_sk: UnsecuredZkKey,
+    /// Value of this node's leader note in genesis; zero when the node holds
+    /// no leader note.
+    pub leader_stake: u64,
     pub ledger_config: lb_ledger::Config,
     pub genesis_tx: GenesisTx,
     pub utxos: Vec<Utxo>,
@@ -219,13 +229,15 @@ pub fn create_consensus_configs(
     let mut blend_notes = Vec::new();
     let mut sdp_notes = Vec::new();
 
-    let leader_stake = leader_stake_amount(wallet, ids.len());
+    let leader_stakes = consensus_params
+        .stake_distribution
+        .leader_stakes(leader_stake_amount(wallet, ids.len()), ids.len())?;
     let utxos = create_utxos_for_leader_and_services(
         ids,
         &mut leader_keys,
         &mut blend_notes,
         &mut sdp_notes,
-        leader_stake,
+        &leader_stakes,
     );
     let mut utxos = append_wallet_utxos(utxos, wallet);
     let genesis_tx = create_genesis_tx(&mut utxos)?;
@@ -237,6 +249,7 @@ pub fn create_consensus_configs(
         .map(|(i, (pk, sk))| GeneralConsensusConfig {
             leader_pk: pk,
             leader_sk: sk,
+            leader_stake: leader_stakes[i],
             ledger_config: ledger_config.clone(),
             genesis_tx: genesis_tx.clone(),
             utxos: utxos.clone(),
@@ -247,7 +260,7 @@ pub fn create_consensus_configs(
         .collect())
 }
 
-fn leader_stake_amount(wallet: &WalletConfig, n_participants: usize) -> u64 {
+pub(crate) fn leader_stake_amount(wallet: &WalletConfig, n_participants: usize) -> u64 {
     // Minimum leader stake (legacy baseline) so small test wallets still
     // have a viable leader in low-fund scenarios.
     const MIN_LEADER_STAKE: u64 = 100_000;
@@ -281,13 +294,13 @@ fn create_utxos_for_leader_and_services(
     leader_keys: &mut Vec<(ZkPublicKey, UnsecuredZkKey)>,
     blend_notes: &mut Vec<ServiceNote>,
     sdp_notes: &mut Vec<ServiceNote>,
-    leader_stake: u64,
+    leader_stakes: &[u64],
 ) -> Vec<Utxo> {
     let mut utxos = Vec::new();
 
     // Create notes for leader, Blend and DA declarations.
     let mut output_index = 0;
-    for &id in ids {
+    for (&id, &leader_stake) in ids.iter().zip(leader_stakes) {
         output_index = push_leader_utxo(id, leader_keys, &mut utxos, output_index, leader_stake);
         output_index = push_service_note(b"bn", id, blend_notes, &mut utxos, output_index);
         output_index = push_service_note(b"sdp", id, sdp_notes, &mut utxos, output_index);
@@ -318,6 +331,9 @@ fn push_leader_utxo(
     let sk = UnsecuredZkKey::from(BigUint::from_bytes_le(&sk_data));
     let pk = sk.to_public_key();
     leader_keys.push((pk, sk));
+    if leader_stake == 0 {
+        return output_index;
+    }
     utxos.push(Utxo {
         note: Note::new(leader_stake, pk),
         tx_hash: BigUint::from(0u8).into(),
@@ -4,7 +4,9 @@ pub mod blend;
 pub mod bootstrap;
 pub mod consensus;
 pub mod network;
+pub mod node_spec;
 pub mod runtime;
+pub mod stake;
 pub mod time;
 pub mod tracing;
 pub mod wallet;
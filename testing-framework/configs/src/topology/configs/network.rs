@@ -1,23 +1,64 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    time::Duration,
+};
 
 use lb_libp2p::{
     IdentifySettings, KademliaSettings, Multiaddr, NatSettings, Protocol, ed25519, gossipsub,
 };
 use lb_node::config::network::serde::{BackendSettings, Config, SwarmConfig};
 use lb_utils::net::get_available_udp_port;
+use rand::{Rng as _, SeedableRng as _, rngs::StdRng, seq::SliceRandom as _};
+use serde::{Deserialize, Serialize};
 use testing_framework_env as tf_env;
 use thiserror::Error;
 
 use crate::{node_address_from_port, secret_key_to_peer_id};
 
 const PEER_RESPONSE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Random graphs that come out disconnected are redrawn this many times
+/// before the layout is rejected.
+const MAX_LAYOUT_ATTEMPTS: usize = 32;
 
-#[derive(Default, Clone)]
+/// Shape of the libp2p graph formed by the nodes' initial peers.
+///
+/// Every link is dialed by the higher-indexed node, so readiness can derive
+/// the expected peer counts from the initial peer lists alone.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Libp2pNetworkLayout {
     #[default]
     Star,
     Chain,
     Full,
+    /// Chain whose last node also links back to the first.
+    Ring,
+    /// Random connected graph where every node has exactly `degree` peers.
+    RandomRegular {
+        degree: usize,
+        seed: u64,
+    },
+    /// Watts-Strogatz graph: a ring lattice where each node links to its
+    /// `neighbours` nearest nodes, with each link rewired to a random node
+    /// with probability `rewire_probability`. Rewirings that disconnect the
+    /// graph are redrawn.
+    SmallWorld {
+        neighbours: usize,
+        rewire_probability: f64,
+        seed: u64,
+    },
+    /// Nodes are assigned to `clusters` round-robin and fully meshed within a
+    /// cluster; neighbouring clusters are joined by `inter_cluster_links`
+    /// random links (at most one per node pair). With more than one cluster,
+    /// `inter_cluster_links` must be non-zero.
+    Clustered {
+        clusters: usize,
+        inter_cluster_links: usize,
+        seed: u64,
+    },
+    /// Peers of every node by index. Links are undirected, so listing a link
+    /// on one side is enough.
+    Explicit(Vec<Vec<usize>>),
 }
 
 #[derive(Default, Clone)]
@@ -35,6 +76,27 @@ pub enum NetworkConfigError {
     NodeKeyFromBytes { message: String },
     #[error("failed to build loopback multiaddr for NAT settings: {message}")]
     LoopbackMultiaddr { message: String },
+    #[error(
+        "random regular layout needs degree below the node count and an even degree sum (degree {degree}, nodes {nodes})"
+    )]
+    InvalidRegularDegree { degree: usize, nodes: usize },
+    #[error(
+        "small-world layout needs an even, non-zero neighbour count below the node count (neighbours {neighbours}, nodes {nodes})"
+    )]
+    InvalidSmallWorldNeighbours { neighbours: usize, nodes: usize },
+    #[error("clustered layout needs between 1 and {nodes} clusters, got {clusters}")]
+    InvalidClusterCount { clusters: usize, nodes: usize },
+    #[error("clustered layout with {clusters} clusters needs at least one inter-cluster link")]
+    NoInterClusterLinks { clusters: usize },
+    #[error("{layout} layout stayed disconnected after {attempts} attempts")]
+    DisconnectedLayout {
+        layout: &'static str,
+        attempts: usize,
+    },
+    #[error("explicit layout lists {actual} nodes but the topology has {expected}")]
+    AdjacencyLenMismatch { actual: usize, expected: usize },
+    #[error("explicit layout links node {index} to invalid peer {peer}")]
+    InvalidAdjacencyPeer { index: usize, peer: usize },
 }
 
 fn default_swarm_config() -> SwarmConfig {
@@ -94,7 +156,7 @@ pub fn create_network_configs(
         })
         .collect::<Result<_, _>>()?;
 
-    let all_initial_peers = initial_peers_by_network_layout(&swarm_configs, network_params);
+    let all_initial_peers = initial_peers_by_network_layout(&swarm_configs, network_params)?;
 
     Ok(swarm_configs
         .iter()
@@ -141,52 +203,394 @@ pub fn build_network_config_for_node(
 fn initial_peers_by_network_layout(
     swarm_configs: &[SwarmConfig],
     network_params: &NetworkParams,
-) -> Vec<Vec<Multiaddr>> {
-    let Some(first_swarm) = swarm_configs.first() else {
-        return Vec::new();
+) -> Result<Vec<Vec<Multiaddr>>, NetworkConfigError> {
+    let mut all_initial_peers = vec![Vec::new(); swarm_configs.len()];
+    for (dialer, target) in
+        layout_links(&network_params.libp2p_network_layout, swarm_configs.len())?
+    {
+        all_initial_peers[dialer].push(node_address_with_peer(&swarm_configs[target]));
+    }
+
+    Ok(all_initial_peers)
+}
+
+/// Resolves `layout` into `(dialer, target)` pairs for `n` nodes, sorted and
+/// with `dialer > target`.
+pub fn layout_links(
+    layout: &Libp2pNetworkLayout,
+    n: usize,
+) -> Result<Vec<(usize, usize)>, NetworkConfigError> {
+    let mut links = BTreeSet::new();
+    let mut link = |a: usize, b: usize| {
+        if a != b {
+            links.insert((a.max(b), a.min(b)));
+        }
     };
 
-    let mut all_initial_peers = vec![];
+    match layout {
+        // First node is the hub; all other nodes connect to it.
+        Libp2pNetworkLayout::Star => (1..n).for_each(|i| link(i, 0)),
+        // Each node connects to the previous one.
+        Libp2pNetworkLayout::Chain => (1..n).for_each(|i| link(i, i - 1)),
+        Libp2pNetworkLayout::Full => {
+            for i in 0..n {
+                (0..i).for_each(|j| link(i, j));
+            }
+        }
+        Libp2pNetworkLayout::Ring => {
+            (1..n).for_each(|i| link(i, i - 1));
+            if n > 2 {
+                link(n - 1, 0);
+            }
+        }
+        Libp2pNetworkLayout::RandomRegular { degree, seed } => {
+            random_regular_links(*degree, *seed, n)?
+                .into_iter()
+                .for_each(|(a, b)| link(a, b));
+        }
+        Libp2pNetworkLayout::SmallWorld {
+            neighbours,
+            rewire_probability,
+            seed,
+        } => {
+            small_world_links(*neighbours, *rewire_probability, *seed, n)?
+                .into_iter()
+                .for_each(|(a, b)| link(a, b));
+        }
+        Libp2pNetworkLayout::Clustered {
+            clusters,
+            inter_cluster_links,
+            seed,
+        } => {
+            clustered_links(*clusters, *inter_cluster_links, *seed, n)?
+                .into_iter()
+                .for_each(|(a, b)| link(a, b));
+        }
+        Libp2pNetworkLayout::Explicit(adjacency) => {
+            if adjacency.len() != n {
+                return Err(NetworkConfigError::AdjacencyLenMismatch {
+                    actual: adjacency.len(),
+                    expected: n,
+                });
+            }
+            for (index, peers) in adjacency.iter().enumerate() {
+                for &peer in peers {
+                    if peer >= n || peer == index {
+                        return Err(NetworkConfigError::InvalidAdjacencyPeer { index, peer });
+                    }
+                    link(index, peer);
+                }
+            }
+        }
+    }
+
+    Ok(links.into_iter().collect())
+}
 
-    match network_params.libp2p_network_layout {
-        Libp2pNetworkLayout::Star => {
-            // First node is the hub - has no initial peers
-            all_initial_peers.push(vec![]);
-            let first_addr = node_address_with_peer(first_swarm);
+fn ordered(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
 
-            // All other nodes connect to the first node
-            for _ in 1..swarm_configs.len() {
-                all_initial_peers.push(vec![first_addr.clone()]);
+/// Whether the undirected graph over `n` nodes formed by `edges` is
+/// connected.
+fn is_connected(n: usize, edges: &[(usize, usize)]) -> bool {
+    if n == 0 {
+        return true;
+    }
+    let mut adjacency = vec![Vec::new(); n];
+    for &(a, b) in edges {
+        adjacency[a].push(b);
+        adjacency[b].push(a);
+    }
+    let mut seen = vec![false; n];
+    seen[0] = true;
+    let mut queue = VecDeque::from([0]);
+    let mut reached = 1;
+    while let Some(node) = queue.pop_front() {
+        for &peer in &adjacency[node] {
+            if !seen[peer] {
+                seen[peer] = true;
+                reached += 1;
+                queue.push_back(peer);
             }
         }
-        Libp2pNetworkLayout::Chain => {
-            // First node has no initial peers
-            all_initial_peers.push(vec![]);
+    }
+    reached == n
+}
 
-            // Each subsequent node connects to the previous one
-            for i in 1..swarm_configs.len() {
-                let prev_addr = node_address_with_peer(&swarm_configs[i - 1]);
-                all_initial_peers.push(vec![prev_addr]);
+/// Draws graphs with `draw` until one is connected.
+fn connected_draw(
+    layout: &'static str,
+    n: usize,
+    mut draw: impl FnMut() -> Vec<(usize, usize)>,
+) -> Result<Vec<(usize, usize)>, NetworkConfigError> {
+    (0..MAX_LAYOUT_ATTEMPTS)
+        .map(|_| draw())
+        .find(|edges| is_connected(n, edges))
+        .ok_or(NetworkConfigError::DisconnectedLayout {
+            layout,
+            attempts: MAX_LAYOUT_ATTEMPTS,
+        })
+}
+
+/// Starts from a circulant graph with the right degree and shuffles it with
+/// degree-preserving edge swaps, redrawing shuffles that disconnect it.
+fn random_regular_links(
+    degree: usize,
+    seed: u64,
+    n: usize,
+) -> Result<Vec<(usize, usize)>, NetworkConfigError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if degree >= n || !(degree * n).is_multiple_of(2) {
+        return Err(NetworkConfigError::InvalidRegularDegree { degree, nodes: n });
+    }
+
+    let mut edges = BTreeSet::new();
+    for i in 0..n {
+        for offset in 1..=degree / 2 {
+            edges.insert(ordered(i, (i + offset) % n));
+        }
+        if !degree.is_multiple_of(2) {
+            edges.insert(ordered(i, (i + n / 2) % n));
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let circulant = edges.into_iter().collect::<Vec<_>>();
+    connected_draw("random regular", n, || {
+        let mut edges = circulant.clone();
+        if edges.len() < 2 {
+            return edges;
+        }
+        for _ in 0..edges.len() * 10 {
+            let first = rng.gen_range(0..edges.len());
+            let second = rng.gen_range(0..edges.len());
+            let ((a, b), (c, d)) = (edges[first], edges[second]);
+            if a == c || a == d || b == c || b == d {
+                continue;
+            }
+            let (left, right) = (ordered(a, d), ordered(c, b));
+            if edges.contains(&left) || edges.contains(&right) {
+                continue;
             }
+            edges[first] = left;
+            edges[second] = right;
         }
-        Libp2pNetworkLayout::Full => {
-            // Each node connects to all previous nodes, unidirectional connections
-            for i in 0..swarm_configs.len() {
-                let mut peers = vec![];
-                for swarm_config in swarm_configs.iter().take(i) {
-                    peers.push(node_address_with_peer(swarm_config));
+        edges
+    })
+}
+
+fn small_world_links(
+    neighbours: usize,
+    rewire_probability: f64,
+    seed: u64,
+    n: usize,
+) -> Result<Vec<(usize, usize)>, NetworkConfigError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if neighbours == 0 || !neighbours.is_multiple_of(2) || neighbours >= n {
+        return Err(NetworkConfigError::InvalidSmallWorldNeighbours {
+            neighbours,
+            nodes: n,
+        });
+    }
+
+    let probability = if rewire_probability.is_nan() {
+        0.0
+    } else {
+        rewire_probability.clamp(0.0, 1.0)
+    };
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut lattice = BTreeSet::new();
+    for i in 0..n {
+        for offset in 1..=neighbours / 2 {
+            lattice.insert(ordered(i, (i + offset) % n));
+        }
+    }
+
+    connected_draw("small-world", n, || {
+        let mut edges = lattice.clone();
+        for i in 0..n {
+            for offset in 1..=neighbours / 2 {
+                let original = ordered(i, (i + offset) % n);
+                if !rng.gen_bool(probability) || !edges.contains(&original) {
+                    continue;
+                }
+                let candidates = (0..n)
+                    .filter(|target| *target != i && !edges.contains(&ordered(i, *target)))
+                    .collect::<Vec<_>>();
+                if let Some(target) = candidates.choose(&mut rng) {
+                    edges.remove(&original);
+                    edges.insert(ordered(i, *target));
                 }
-                all_initial_peers.push(peers);
+            }
+        }
+        edges.into_iter().collect()
+    })
+}
+
+fn clustered_links(
+    clusters: usize,
+    inter_cluster_links: usize,
+    seed: u64,
+    n: usize,
+) -> Result<Vec<(usize, usize)>, NetworkConfigError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if clusters == 0 || clusters > n {
+        return Err(NetworkConfigError::InvalidClusterCount { clusters, nodes: n });
+    }
+    if clusters > 1 && inter_cluster_links == 0 {
+        return Err(NetworkConfigError::NoInterClusterLinks { clusters });
+    }
+
+    let members = (0..clusters)
+        .map(|cluster| (cluster..n).step_by(clusters).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut edges = BTreeSet::new();
+    for nodes in &members {
+        for (pos, a) in nodes.iter().enumerate() {
+            for b in &nodes[pos + 1..] {
+                edges.insert(ordered(*a, *b));
             }
         }
     }
 
-    all_initial_peers
+    // Neighbouring clusters form a ring so the whole graph stays connected.
+    let mut rng = StdRng::seed_from_u64(seed);
+    let pairs = match clusters {
+        1 => 0,
+        2 => 1,
+        _ => clusters,
+    };
+    for cluster in 0..pairs {
+        let (left, right) = (&members[cluster], &members[(cluster + 1) % clusters]);
+        let wanted = inter_cluster_links.min(left.len() * right.len());
+        let mut added = 0;
+        while added < wanted {
+            let (Some(a), Some(b)) = (left.choose(&mut rng), right.choose(&mut rng)) else {
+                break;
+            };
+            if edges.insert(ordered(*a, *b)) {
+                added += 1;
+            }
+        }
+    }
+
+    Ok(edges.into_iter().collect())
 }
 
-fn node_address_with_peer(swarm_config: &SwarmConfig) -> Multiaddr {
+pub(crate) fn node_address_with_peer(swarm_config: &SwarmConfig) -> Multiaddr {
     let mut addr = node_address_from_port(swarm_config.port);
     let peer_id = secret_key_to_peer_id(swarm_config.node_key.clone());
     addr.push(Protocol::P2p(peer_id.into()));
     addr
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Libp2pNetworkLayout, NetworkConfigError, is_connected, layout_links};
+
+    #[test]
+    fn small_world_rewiring_keeps_the_graph_connected() {
+        for seed in 0..16 {
+            let layout = Libp2pNetworkLayout::SmallWorld {
+                neighbours: 2,
+                rewire_probability: 0.5,
+                seed,
+            };
+            let links = layout_links(&layout, 12).expect("small world");
+            assert_eq!(links.len(), 12);
+            assert!(is_connected(12, &links), "seed {seed}: {links:?}");
+            assert_eq!(layout_links(&layout, 12).expect("small world"), links);
+        }
+    }
+
+    #[test]
+    fn clustered_meshes_clusters_and_links_neighbours() {
+        let layout = Libp2pNetworkLayout::Clustered {
+            clusters: 3,
+            inter_cluster_links: 2,
+            seed: 7,
+        };
+        let links = layout_links(&layout, 9).expect("clustered");
+
+        let same_cluster = |(a, b): &(usize, usize)| a % 3 == b % 3;
+        assert_eq!(links.iter().filter(|link| same_cluster(link)).count(), 9);
+        assert_eq!(links.iter().filter(|link| !same_cluster(link)).count(), 6);
+        assert!(is_connected(9, &links));
+
+        let single = Libp2pNetworkLayout::Clustered {
+            clusters: 1,
+            inter_cluster_links: 0,
+            seed: 7,
+        };
+        assert_eq!(layout_links(&single, 4).expect("one cluster").len(), 6);
+    }
+
+    #[test]
+    fn random_regular_graphs_are_connected() {
+        for seed in 0..16 {
+            let layout = Libp2pNetworkLayout::RandomRegular { degree: 2, seed };
+            let links = layout_links(&layout, 10).expect("random regular");
+            assert!(is_connected(10, &links), "seed {seed}: {links:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_layouts_are_rejected() {
+        let resolve = |layout: Libp2pNetworkLayout, n| layout_links(&layout, n).unwrap_err();
+
+        assert!(matches!(
+            resolve(Libp2pNetworkLayout::RandomRegular { degree: 3, seed: 0 }, 5),
+            NetworkConfigError::InvalidRegularDegree { .. }
+        ));
+        assert!(matches!(
+            resolve(Libp2pNetworkLayout::RandomRegular { degree: 1, seed: 0 }, 4),
+            NetworkConfigError::DisconnectedLayout { .. }
+        ));
+        for neighbours in [0, 3, 6] {
+            let layout = Libp2pNetworkLayout::SmallWorld {
+                neighbours,
+                rewire_probability: 0.1,
+                seed: 0,
+            };
+            assert!(matches!(
+                resolve(layout, 6),
+                NetworkConfigError::InvalidSmallWorldNeighbours { .. }
+            ));
+        }
+        for clusters in [0, 5] {
+            let layout = Libp2pNetworkLayout::Clustered {
+                clusters,
+                inter_cluster_links: 1,
+                seed: 0,
+            };
+            assert!(matches!(
+                resolve(layout, 4),
+                NetworkConfigError::InvalidClusterCount { .. }
+            ));
+        }
+        let unlinked = Libp2pNetworkLayout::Clustered {
+            clusters: 2,
+            inter_cluster_links: 0,
+            seed: 0,
+        };
+        assert!(matches!(
+            resolve(unlinked, 4),
+            NetworkConfigError::NoInterClusterLinks { clusters: 2 }
+        ));
+        assert!(matches!(
+            resolve(Libp2pNetworkLayout::Explicit(vec![vec![1]]), 2),
+            NetworkConfigError::AdjacencyLenMismatch { .. }
+        ));
+        assert!(matches!(
+            resolve(Libp2pNetworkLayout::Explicit(vec![vec![0], vec![]]), 2),
+            NetworkConfigError::InvalidAdjacencyPeer { index: 0, peer: 0 }
+        ));
+    }
+}
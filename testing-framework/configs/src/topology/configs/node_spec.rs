@@ -0,0 +1,496 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr as _,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::Level;
+
+use super::{
+    GeneralConfig,
+    consensus::{ConsensusParams, ProviderInfo, leader_stake_amount},
+    network::{GeneralNetworkConfig, node_address_with_peer},
+    stake::{StakeDistribution, StakeDistributionError},
+    wallet::WalletConfig,
+};
+
+/// Per-node specs keyed by node index. Nodes without an entry use
+/// [`NodeSpec::default`].
+pub type NodeSpecs = HashMap<usize, NodeSpec>;
+
+#[derive(Debug, Error)]
+pub enum NodeSpecError {
+    #[error("node spec for index {index} is outside the topology ({n_nodes} nodes)")]
+    IndexOutOfRange { index: usize, n_nodes: usize },
+    #[error("node {index} lists initial peer {peer}, but the topology has {n_nodes} nodes")]
+    UnknownPeer {
+        index: usize,
+        peer: usize,
+        n_nodes: usize,
+    },
+    #[error("node {index} lists itself as an initial peer")]
+    SelfPeer { index: usize },
+    #[error("node {index} has role {role:?}, which holds no leader stake, but sets a stake")]
+    StakeWithoutProducer { index: usize, role: NodeRole },
+    #[error("stake for block producer node {index} must be positive")]
+    ZeroStake { index: usize },
+    #[error("invalid log level '{level}' for node {index}")]
+    InvalidLogLevel { index: usize, level: String },
+    #[error("topology needs at least one block producer")]
+    NoBlockProducers,
+    #[error("topology needs at least one blend core node")]
+    NoBlendCoreNodes,
+    #[error(transparent)]
+    Stake(#[from] StakeDistributionError),
+}
+
+/// What a node does in the network.
+///
+/// | role | leader stake | blend core | dialed by others |
+/// |------|--------------|------------|------------------|
+/// | `BlockProducer` | yes | yes | yes |
+/// | `NonProducer` | no | no | yes |
+/// | `BlendCore` | no | yes | yes |
+/// | `Edge` | no | no | only via explicit `initial_peers` |
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeRole {
+    #[default]
+    BlockProducer,
+    NonProducer,
+    BlendCore,
+    Edge,
+}
+
+impl NodeRole {
+    #[must_use]
+    pub const fn holds_leader_stake(self) -> bool {
+        matches!(self, Self::BlockProducer)
+    }
+
+    #[must_use]
+    pub const fn is_blend_core(self) -> bool {
+        matches!(self, Self::BlockProducer | Self::BlendCore)
+    }
+}
+
+/// Per-node overrides for the time service. The slot duration is chain-wide
+/// and stays on the topology.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeTimeSpec {
+    pub ntp_server: Option<String>,
+    pub ntp_timeout: Option<Duration>,
+    pub ntp_update_interval: Option<Duration>,
+}
+
+/// Declarative description of a single node in a topology.
+///
+/// Unlike config patches, specs are plain data: they serialise into the
+/// cfgsync template so compose and k8s deployments generate the same configs
+/// as local runs. `binary` and `start_delay` only affect how a node process is
+/// launched and are honoured by the local deployer.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeSpec {
+    pub role: NodeRole,
+    /// Leader stake in genesis; overrides the topology stake distribution.
+    pub stake: Option<u64>,
+    /// Indices of the nodes dialed at startup; overrides the network layout.
+    pub initial_peers: Option<Vec<usize>>,
+    /// Node binary to launch instead of the resolved default.
+    pub binary: Option<PathBuf>,
+    /// Tracing level such as `info` or `debug`.
+    pub log_level: Option<String>,
+    pub time: Option<NodeTimeSpec>,
+    /// How long after deployment starts the node may be launched.
+    pub start_delay: Option<Duration>,
+}
+
+impl NodeSpec {
+    #[must_use]
+    pub fn new(role: NodeRole) -> Self {
+        Self {
+            role,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub const fn with_stake(mut self, stake: u64) -> Self {
+        self.stake = Some(stake);
+        self
+    }
+
+    #[must_use]
+    pub fn with_initial_peers(mut self, peers: impl IntoIterator<Item = usize>) -> Self {
+        self.initial_peers = Some(peers.into_iter().collect());
+        self
+    }
+
+    #[must_use]
+    pub fn with_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = Some(binary.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_log_level(mut self, level: impl Into<String>) -> Self {
+        self.log_level = Some(level.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_time(mut self, time: NodeTimeSpec) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_start_delay(mut self, delay: Duration) -> Self {
+        self.start_delay = Some(delay);
+        self
+    }
+
+    /// Applies the per-node runtime settings (log level, time service) to a
+    /// generated config.
+    pub fn apply_to(&self, index: usize, config: &mut GeneralConfig) -> Result<(), NodeSpecError> {
+        if let Some(level) = self.parsed_log_level(index)? {
+            config.tracing_config.tracing_settings.level = level;
+        }
+
+        if let Some(time) = &self.time {
+            let time_config = &mut config.time_config;
+            if let Some(server) = &time.ntp_server {
+                time_config.ntp_server.clone_from(server);
+            }
+            if let Some(timeout) = time.ntp_timeout {
+                time_config.timeout = timeout;
+            }
+            if let Some(interval) = time.ntp_update_interval {
+                time_config.update_interval = interval;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parsed_log_level(&self, index: usize) -> Result<Option<Level>, NodeSpecError> {
+        self.log_level
+            .as_deref()
+            .map(|raw| {
+                Level::from_str(raw).map_err(|_| NodeSpecError::InvalidLogLevel {
+                    index,
+                    level: raw.to_owned(),
+                })
+            })
+            .transpose()
+    }
+}
+
+#[must_use]
+pub fn role_of(specs: &NodeSpecs, index: usize) -> NodeRole {
+    specs.get(&index).map(|spec| spec.role).unwrap_or_default()
+}
+
+/// Checks that `specs` are consistent with a topology of `n_nodes` nodes.
+pub fn validate_node_specs(specs: &NodeSpecs, n_nodes: usize) -> Result<(), NodeSpecError> {
+    for (&index, spec) in specs {
+        if index >= n_nodes {
+            return Err(NodeSpecError::IndexOutOfRange { index, n_nodes });
+        }
+
+        for &peer in spec.initial_peers.iter().flatten() {
+            if peer >= n_nodes {
+                return Err(NodeSpecError::UnknownPeer {
+                    index,
+                    peer,
+                    n_nodes,
+                });
+            }
+            if peer == index {
+                return Err(NodeSpecError::SelfPeer { index });
+            }
+        }
+
+        match spec.stake {
+            Some(_) if !spec.role.holds_leader_stake() => {
+                return Err(NodeSpecError::StakeWithoutProducer {
+                    index,
+                    role: spec.role,
+                });
+            }
+            Some(0) => return Err(NodeSpecError::ZeroStake { index }),
+            _ => {}
+        }
+
+        spec.parsed_log_level(index)?;
+    }
+
+    let roles = (0..n_nodes)
+        .map(|index| role_of(specs, index))
+        .collect::<Vec<_>>();
+    if !roles.iter().any(|role| role.holds_leader_stake()) {
+        return Err(NodeSpecError::NoBlockProducers);
+    }
+    if !roles.iter().any(|role| role.is_blend_core()) {
+        return Err(NodeSpecError::NoBlendCoreNodes);
+    }
+
+    Ok(())
+}
+
+/// Folds spec stakes and roles into the consensus params: when any node
+/// deviates from the topology-wide distribution, the result carries an
+/// explicit per-node stake vector.
+pub fn consensus_params_with_specs(
+    params: &ConsensusParams,
+    wallet: &WalletConfig,
+    n_nodes: usize,
+    specs: &NodeSpecs,
+) -> Result<ConsensusParams, NodeSpecError> {
+    let overrides_stake = specs
+        .values()
+        .any(|spec| spec.stake.is_some() || !spec.role.holds_leader_stake());
+    if !overrides_stake {
+        return Ok(params.clone());
+    }
+
+    let mut stakes = params
+        .stake_distribution
+        .leader_stakes(leader_stake_amount(wallet, n_nodes), n_nodes)?;
+    for (index, stake) in stakes.iter_mut().enumerate() {
+        let Some(spec) = specs.get(&index) else {
+            continue;
+        };
+        if !spec.role.holds_leader_stake() {
+            *stake = 0;
+        } else if let Some(explicit) = spec.stake {
+            *stake = explicit;
+        }
+    }
+
+    let mut params = params.clone();
+    params.stake_distribution = StakeDistribution::Explicit(stakes);
+    Ok(params)
+}
+
+/// Drops genesis blend declarations of nodes whose role is not blend core.
+/// `providers` must be in node index order.
+#[must_use]
+pub fn retain_blend_core_providers(
+    providers: Vec<ProviderInfo>,
+    specs: &NodeSpecs,
+) -> Vec<ProviderInfo> {
+    providers
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| role_of(specs, *index).is_blend_core())
+        .map(|(_, provider)| provider)
+        .collect()
+}
+
+/// Rewrites initial peers for nodes with explicit `initial_peers` and removes
+/// edge nodes from everyone else's layout-derived peers.
+pub fn apply_initial_peer_specs(network_configs: &mut [GeneralNetworkConfig], specs: &NodeSpecs) {
+    if specs.is_empty() {
+        return;
+    }
+
+    let addresses = network_configs
+        .iter()
+        .map(|config| node_address_with_peer(&config.backend.swarm))
+        .collect::<Vec<_>>();
+    let edge_addresses = specs
+        .iter()
+        .filter(|(_, spec)| spec.role == NodeRole::Edge)
+        .filter_map(|(index, _)| addresses.get(*index).cloned())
+        .collect::<HashSet<_>>();
+
+    for (index, config) in network_configs.iter_mut().enumerate() {
+        let peers = &mut config.backend.initial_peers;
+        match specs
+            .get(&index)
+            .and_then(|spec| spec.initial_peers.as_ref())
+        {
+            Some(explicit) => {
+                *peers = explicit
+                    .iter()
+                    .filter_map(|peer| addresses.get(*peer).cloned())
+                    .collect();
+            }
+            None => peers.retain(|addr| !edge_addresses.contains(addr)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_core::{
+        mantle::Note,
+        sdp::{Locator, ServiceType},
+    };
+    use lb_key_management_system_service::keys::Ed25519Key;
+
+    use super::{
+        NodeRole, NodeSpec, NodeSpecError, NodeSpecs, apply_initial_peer_specs,
+        consensus_params_with_specs, retain_blend_core_providers, validate_node_specs,
+    };
+    use crate::topology::configs::{
+        consensus::{ConsensusParams, ProviderInfo, ServiceNote, leader_stake_amount},
+        network::{build_network_config_for_node, node_address_with_peer},
+        stake::StakeDistribution,
+        wallet::{WalletAccount, WalletConfig},
+    };
+
+    fn specs(entries: impl IntoIterator<Item = (usize, NodeSpec)>) -> NodeSpecs {
+        entries.into_iter().collect()
+    }
+
+    fn provider(index: u8) -> ProviderInfo {
+        let account = WalletAccount::deterministic(u64::from(index), 1);
+        let address = format!("/ip4/127.0.0.1/udp/{}/quic-v1", 4000 + u16::from(index));
+        ProviderInfo {
+            service_type: ServiceType::BlendNetwork,
+            provider_sk: Ed25519Key::from_bytes(&[index; 32]),
+            zk_sk: account.secret_key.clone(),
+            locator: Locator(address.parse().expect("multiaddr")),
+            note: ServiceNote {
+                pk: account.public_key(),
+                note: Note::new(1, account.public_key()),
+                sk: account.secret_key,
+                output_index: usize::from(index),
+            },
+        }
+    }
+
+    #[test]
+    fn invalid_specs_are_rejected() {
+        let check = |entries: Vec<(usize, NodeSpec)>| validate_node_specs(&specs(entries), 3);
+
+        assert!(check(vec![]).is_ok());
+        assert!(matches!(
+            check(vec![(3, NodeSpec::default())]),
+            Err(NodeSpecError::IndexOutOfRange {
+                index: 3,
+                n_nodes: 3
+            })
+        ));
+        assert!(matches!(
+            check(vec![(0, NodeSpec::default().with_initial_peers([5]))]),
+            Err(NodeSpecError::UnknownPeer {
+                index: 0,
+                peer: 5,
+                ..
+            })
+        ));
+        assert!(matches!(
+            check(vec![(1, NodeSpec::default().with_initial_peers([1]))]),
+            Err(NodeSpecError::SelfPeer { index: 1 })
+        ));
+        assert!(matches!(
+            check(vec![(0, NodeSpec::new(NodeRole::Edge).with_stake(5))]),
+            Err(NodeSpecError::StakeWithoutProducer {
+                index: 0,
+                role: NodeRole::Edge
+            })
+        ));
+        assert!(matches!(
+            check(vec![(2, NodeSpec::default().with_stake(0))]),
+            Err(NodeSpecError::ZeroStake { index: 2 })
+        ));
+        assert!(matches!(
+            check(vec![(0, NodeSpec::default().with_log_level("loud"))]),
+            Err(NodeSpecError::InvalidLogLevel { index: 0, .. })
+        ));
+        assert!(matches!(
+            check(
+                (0..3)
+                    .map(|i| (i, NodeSpec::new(NodeRole::BlendCore)))
+                    .collect()
+            ),
+            Err(NodeSpecError::NoBlockProducers)
+        ));
+        assert!(matches!(
+            check(vec![
+                (0, NodeSpec::new(NodeRole::NonProducer)),
+                (1, NodeSpec::new(NodeRole::Edge)),
+                (2, NodeSpec::new(NodeRole::NonProducer)),
+            ]),
+            Err(NodeSpecError::NoBlockProducers)
+        ));
+    }
+
+    #[test]
+    fn spec_stakes_and_roles_override_the_distribution() {
+        let params = ConsensusParams::default_for_participants(3);
+        let wallet = WalletConfig::default();
+
+        let unchanged =
+            consensus_params_with_specs(&params, &wallet, 3, &specs([])).expect("no overrides");
+        assert_eq!(unchanged.stake_distribution, StakeDistribution::Uniform);
+
+        let overridden = consensus_params_with_specs(
+            &params,
+            &wallet,
+            3,
+            &specs([
+                (1, NodeSpec::default().with_stake(7)),
+                (2, NodeSpec::new(NodeRole::NonProducer)),
+            ]),
+        )
+        .expect("overrides");
+        let base = leader_stake_amount(&wallet, 3);
+        assert_eq!(
+            overridden.stake_distribution,
+            StakeDistribution::Explicit(vec![base, 7, 0])
+        );
+    }
+
+    #[test]
+    fn only_blend_core_providers_are_kept() {
+        let providers = (0..4).map(provider).collect();
+        let kept = retain_blend_core_providers(
+            providers,
+            &specs([
+                (1, NodeSpec::new(NodeRole::NonProducer)),
+                (2, NodeSpec::new(NodeRole::BlendCore)),
+                (3, NodeSpec::new(NodeRole::Edge)),
+            ]),
+        );
+
+        let indices = kept
+            .iter()
+            .map(|provider| provider.note.output_index)
+            .collect::<Vec<_>>();
+        assert_eq!(indices, vec![0, 2]);
+    }
+
+    #[test]
+    fn explicit_peers_replace_the_layout_and_edges_are_not_dialed() {
+        let mut configs = (0..3u8)
+            .map(|index| {
+                build_network_config_for_node([index + 1; 32], 5000 + u16::from(index), Vec::new())
+                    .expect("network config")
+            })
+            .collect::<Vec<_>>();
+        let addresses = configs
+            .iter()
+            .map(|config| node_address_with_peer(&config.backend.swarm))
+            .collect::<Vec<_>>();
+        configs[0].backend.initial_peers = vec![addresses[1].clone(), addresses[2].clone()];
+        configs[1].backend.initial_peers = vec![addresses[2].clone()];
+
+        apply_initial_peer_specs(
+            &mut configs,
+            &specs([(2, NodeSpec::new(NodeRole::Edge).with_initial_peers([0]))]),
+        );
+
+        assert_eq!(configs[0].backend.initial_peers, vec![addresses[1].clone()]);
+        assert!(configs[1].backend.initial_peers.is_empty());
+        assert_eq!(configs[2].backend.initial_peers, vec![addresses[0].clone()]);
+    }
+}
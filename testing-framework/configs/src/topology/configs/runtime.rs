@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use lb_key_management_system_service::{backend::preload::PreloadKMSBackendSettings, keys::Key};
 use lb_libp2p::Multiaddr;
+use rand::{SeedableRng as _, rngs::StdRng, seq::SliceRandom as _};
 
 use crate::{
     node_address_from_port,
@@ -11,6 +12,7 @@ use crate::{
         consensus::{ConsensusParams, GeneralConsensusConfig},
         network,
         network::{Libp2pNetworkLayout, NetworkParams},
+        stake::StakeDistribution,
         time, tracing,
         wallet::WalletConfig,
     },
@@ -72,10 +74,17 @@ pub fn build_consensus_config_for_node(
     wallet_config: &WalletConfig,
     base: &GeneralConsensusConfig,
 ) -> Result<GeneralConsensusConfig, GeneralConfigError> {
-    let mut config = consensus::create_consensus_configs(&[id], consensus_params, wallet_config)?
-        .into_iter()
-        .next()
-        .ok_or(GeneralConfigError::EmptyParticipants)?;
+    // Only the leader keys are kept; genesis comes from `base`, so the stake
+    // profile of the original topology does not apply here.
+    let single_node_params = ConsensusParams {
+        stake_distribution: StakeDistribution::Uniform,
+        ..consensus_params.clone()
+    };
+    let mut config =
+        consensus::create_consensus_configs(&[id], &single_node_params, wallet_config)?
+            .into_iter()
+            .next()
+            .ok_or(GeneralConfigError::EmptyParticipants)?;
 
     config.genesis_tx = base.genesis_tx.clone();
     config.utxos = base.utxos.clone();
@@ -85,21 +94,51 @@ pub fn build_consensus_config_for_node(
     Ok(config)
 }
 
+/// Initial peers for a node joining after the topology was generated, given
+/// the network ports of the nodes already running. Layouts without a natural
+/// extension (`Explicit`) fall back to the first node.
 pub fn build_initial_peers(network_params: &NetworkParams, peer_ports: &[u16]) -> Vec<Multiaddr> {
-    match network_params.libp2p_network_layout {
-        Libp2pNetworkLayout::Star => peer_ports
-            .first()
-            .map(|port| vec![node_address_from_port(*port)])
-            .unwrap_or_default(),
-        Libp2pNetworkLayout::Chain => peer_ports
-            .last()
-            .map(|port| vec![node_address_from_port(*port)])
-            .unwrap_or_default(),
-        Libp2pNetworkLayout::Full => peer_ports
-            .iter()
-            .map(|port| node_address_from_port(*port))
-            .collect(),
-    }
+    let index = peer_ports.len();
+    let selected: Vec<u16> = match &network_params.libp2p_network_layout {
+        Libp2pNetworkLayout::Star | Libp2pNetworkLayout::Explicit(_) => {
+            peer_ports.first().copied().into_iter().collect()
+        }
+        Libp2pNetworkLayout::Chain => peer_ports.last().copied().into_iter().collect(),
+        Libp2pNetworkLayout::Full => peer_ports.to_vec(),
+        Libp2pNetworkLayout::Ring => {
+            let mut ports = peer_ports.last().copied().into_iter().collect::<Vec<_>>();
+            if index > 1 {
+                ports.extend(peer_ports.first().copied());
+            }
+            ports
+        }
+        Libp2pNetworkLayout::RandomRegular { degree, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(index as u64));
+            peer_ports
+                .choose_multiple(&mut rng, (*degree).min(index))
+                .copied()
+                .collect()
+        }
+        Libp2pNetworkLayout::SmallWorld { neighbours, .. } => {
+            peer_ports[index.saturating_sub((neighbours / 2).max(1))..].to_vec()
+        }
+        Libp2pNetworkLayout::Clustered { clusters, .. } => {
+            let cluster = index % (*clusters).max(1);
+            let mates = peer_ports
+                .iter()
+                .enumerate()
+                .filter(|(peer, _)| peer % (*clusters).max(1) == cluster)
+                .map(|(_, port)| *port)
+                .collect::<Vec<_>>();
+            if mates.is_empty() {
+                peer_ports.first().copied().into_iter().collect()
+            } else {
+                mates
+            }
+        }
+    };
+
+    selected.into_iter().map(node_address_from_port).collect()
 }
 
 fn build_kms_config_for_node(
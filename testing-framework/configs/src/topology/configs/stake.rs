@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum StakeDistributionError {
+    #[error("explicit stake vector has {actual} entries but the topology has {expected} nodes")]
+    LengthMismatch { actual: usize, expected: usize },
+    #[error("explicit stake vector gives no node any leader stake")]
+    NoLeaderStake,
+    #[error("whale share must be within (0, 1), got {share}")]
+    InvalidWhaleShare { share: f64 },
+    #[error("zipf exponent must be finite and non-negative, got {exponent}")]
+    InvalidZipfExponent { exponent: f64 },
+}
+
+/// How leader stake is spread across nodes in genesis.
+///
+/// Profiles other than [`StakeDistribution::Explicit`] keep the total leader
+/// stake equal to what [`StakeDistribution::Uniform`] would allocate, so
+/// switching profiles changes only who holds the stake.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeDistribution {
+    /// Every node gets the same leader stake.
+    #[default]
+    Uniform,
+    /// Exact leader stake per node, in node index order. A zero entry leaves
+    /// that node without a leader note, so it never proposes blocks.
+    Explicit(Vec<u64>),
+    /// Node 0 holds `share` of the total; the rest is split evenly.
+    Whale { share: f64 },
+    /// Node `i` gets stake proportional to `1 / (i + 1)^exponent`.
+    Zipf { exponent: f64 },
+}
+
+impl StakeDistribution {
+    /// Resolves per-node leader stakes for `n_nodes` nodes, where
+    /// `base_stake` is the amount each node would get under `Uniform`.
+    pub fn leader_stakes(
+        &self,
+        base_stake: u64,
+        n_nodes: usize,
+    ) -> Result<Vec<u64>, StakeDistributionError> {
+        let total = base_stake.saturating_mul(n_nodes as u64);
+        match self {
+            Self::Uniform => Ok(vec![base_stake; n_nodes]),
+            Self::Explicit(stakes) => {
+                if stakes.len() != n_nodes {
+                    return Err(StakeDistributionError::LengthMismatch {
+                        actual: stakes.len(),
+                        expected: n_nodes,
+                    });
+                }
+                if stakes.iter().all(|stake| *stake == 0) {
+                    return Err(StakeDistributionError::NoLeaderStake);
+                }
+                Ok(stakes.clone())
+            }
+            Self::Whale { share } => {
+                if !(*share > 0.0 && *share < 1.0) {
+                    return Err(StakeDistributionError::InvalidWhaleShare { share: *share });
+                }
+                if n_nodes <= 1 {
+                    return Ok(vec![total; n_nodes]);
+                }
+                let whale = ((total as f64 * share) as u64).max(1);
+                let minnow = (total.saturating_sub(whale) / (n_nodes as u64 - 1)).max(1);
+                let mut stakes = vec![minnow; n_nodes];
+                stakes[0] = whale;
+                Ok(stakes)
+            }
+            Self::Zipf { exponent } => {
+                if !exponent.is_finite() || *exponent < 0.0 {
+                    return Err(StakeDistributionError::InvalidZipfExponent {
+                        exponent: *exponent,
+                    });
+                }
+                let weights = (1..=n_nodes)
+                    .map(|rank| (rank as f64).powf(-exponent))
+                    .collect::<Vec<_>>();
+                let sum: f64 = weights.iter().sum();
+                Ok(weights
+                    .into_iter()
+                    .map(|weight| ((total as f64 * weight / sum) as u64).max(1))
+                    .collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StakeDistribution, StakeDistributionError};
+
+    const BASE: u64 = 1_000;
+
+    #[test]
+    fn uniform_gives_every_node_the_base_stake() {
+        let stakes = StakeDistribution::Uniform
+            .leader_stakes(BASE, 4)
+            .expect("uniform");
+        assert_eq!(stakes, vec![BASE; 4]);
+    }
+
+    #[test]
+    fn explicit_stakes_are_used_as_given() {
+        let explicit = StakeDistribution::Explicit(vec![5, 0, 9]);
+        assert_eq!(explicit.leader_stakes(BASE, 3), Ok(vec![5, 0, 9]));
+        assert_eq!(
+            explicit.leader_stakes(BASE, 4),
+            Err(StakeDistributionError::LengthMismatch {
+                actual: 3,
+                expected: 4
+            })
+        );
+        assert_eq!(
+            StakeDistribution::Explicit(vec![0, 0]).leader_stakes(BASE, 2),
+            Err(StakeDistributionError::NoLeaderStake)
+        );
+    }
+
+    #[test]
+    fn whale_holds_its_share_of_the_uniform_total() {
+        let stakes = StakeDistribution::Whale { share: 0.4 }
+            .leader_stakes(BASE, 4)
+            .expect("whale");
+        assert_eq!(stakes, vec![1_600, 800, 800, 800]);
+        assert_eq!(stakes.iter().sum::<u64>(), BASE * 4);
+
+        for share in [0.0, 1.0, f64::NAN] {
+            assert!(matches!(
+                StakeDistribution::Whale { share }.leader_stakes(BASE, 4),
+                Err(StakeDistributionError::InvalidWhaleShare { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn zipf_stakes_decrease_with_rank_and_keep_the_total() {
+        let stakes = StakeDistribution::Zipf { exponent: 1.0 }
+            .leader_stakes(BASE, 5)
+            .expect("zipf");
+        assert!(stakes.windows(2).all(|pair| pair[0] > pair[1]));
+        let sum = stakes.iter().sum::<u64>();
+        assert!(sum <= BASE * 5 && sum > BASE * 5 - 5, "sum {sum}");
+
+        let flat = StakeDistribution::Zipf { exponent: 0.0 }
+            .leader_stakes(BASE, 3)
+            .expect("flat zipf");
+        assert_eq!(flat, vec![BASE; 3]);
+
+        assert!(matches!(
+            StakeDistribution::Zipf { exponent: -1.0 }.leader_stakes(BASE, 3),
+            Err(StakeDistributionError::InvalidZipfExponent { .. })
+        ));
+    }
+}
@@ -9,6 +9,22 @@ const CONSENSUS_SLOT_TIME_VAR: &str = "CONSENSUS_SLOT_TIME";
 const DEFAULT_NTP_SERVER: &str = "pool.ntp.org:123";
 const DEFAULT_NTP_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_NTP_UPDATE_INTERVAL: Duration = Duration::from_secs(16);
+const TIME_BACKEND_VAR: &str = "LOGOS_BLOCKCHAIN_TIME_BACKEND";
+/// Backend the node itself runs when it syncs against the framework server.
+const NODE_NTP_BACKEND: &str = "ntp";
+/// Nodes resync quickly so skews and jumps take effect within a slot or two.
+const FRAMEWORK_NTP_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a node's time service takes its clock from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeSource {
+    /// Whatever backend the node selects from `LOGOS_BLOCKCHAIN_TIME_BACKEND`.
+    #[default]
+    Node,
+    /// The framework's local SNTP server listening on `port`, whose clock the
+    /// run can skew per node.
+    Framework { port: u16 },
+}
 
 #[derive(Clone, Debug)]
 pub struct GeneralTimeConfig {
@@ -17,6 +33,28 @@ pub struct GeneralTimeConfig {
     pub timeout: Duration,
     pub interface: IpAddr,
     pub update_interval: Duration,
+    pub source: TimeSource,
+}
+
+impl GeneralTimeConfig {
+    /// Points the node at the framework time server listening on
+    /// `127.0.0.1:port`.
+    pub fn use_framework_clock(&mut self, port: u16) {
+        self.ntp_server = format!("{}:{port}", Ipv4Addr::LOCALHOST);
+        self.update_interval = FRAMEWORK_NTP_UPDATE_INTERVAL;
+        self.source = TimeSource::Framework { port };
+    }
+
+    /// Environment the node process needs on top of the inherited one.
+    #[must_use]
+    pub fn node_env(&self) -> Vec<(String, String)> {
+        match self.source {
+            TimeSource::Framework { .. } => {
+                vec![(TIME_BACKEND_VAR.to_owned(), NODE_NTP_BACKEND.to_owned())]
+            }
+            TimeSource::Node => Vec::new(),
+        }
+    }
 }
 
 #[must_use]
@@ -33,5 +71,6 @@ pub fn default_time_config() -> GeneralTimeConfig {
         timeout: DEFAULT_NTP_TIMEOUT,
         interface: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
         update_interval: DEFAULT_NTP_UPDATE_INTERVAL,
+        source: TimeSource::Node,
     }
 }
@@ -3,20 +3,50 @@ use std::net::SocketAddr;
 use hex;
 use lb_chain_service::CryptarchiaInfo;
 use lb_common_http_client::CommonHttpClient;
-use lb_core::{block::Block, mantle::SignedMantleTx};
+use lb_core::{
+    block::Block,
+    mantle::{SignedMantleTx, TxHash},
+};
 use lb_http_api_common::paths::{
-    CRYPTARCHIA_HEADERS, CRYPTARCHIA_INFO, MEMPOOL_ADD_TX, NETWORK_INFO, STORAGE_BLOCK,
+    CRYPTARCHIA_HEADERS, CRYPTARCHIA_INFO, MANTLE_METRICS, MANTLE_STATUS, MEMPOOL_ADD_TX,
+    NETWORK_INFO, STORAGE_BLOCK,
 };
 use lb_network_service::backends::libp2p::Libp2pInfo;
 use lb_node::HeaderId;
 use reqwest::{Client, RequestBuilder, Response, Url};
-use serde::{Serialize, de::DeserializeOwned};
-use serde_json::Value;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use tracing::error;
 
 pub const DA_GET_TESTING_ENDPOINT_ERROR: &str = "Failed to connect to testing endpoint. The binary was likely built without the 'testing' \
      feature. Try: cargo build --workspace --all-features";
 
+/// Counters reported by the mantle mempool's metrics endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct MempoolMetrics {
+    pub pending_items: usize,
+    /// Unix timestamp (ms) of the most recently added item.
+    #[serde(default)]
+    pub last_item_timestamp: u64,
+}
+
+/// What a node knows about a transaction hash.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum MempoolTxStatus {
+    Unknown,
+    Pending,
+    Rejected,
+    InBlock { block: HeaderId },
+}
+
+impl MempoolTxStatus {
+    /// Whether the node has accepted the transaction, into its mempool or
+    /// already into a block.
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self, Self::Pending | Self::InBlock { .. })
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ApiClientError {
     #[error("{DA_GET_TESTING_ENDPOINT_ERROR}")]
@@ -295,9 +325,33 @@ impl ApiClient {
         builder.send().await
     }
 
-    /// Fetch raw mempool metrics from the testing endpoint.
-    pub async fn mempool_metrics(&self, pool: &str) -> reqwest::Result<Value> {
-        self.get_json(&format!("/{pool}/metrics")).await
+    /// Fetch metrics of the mantle mempool.
+    pub async fn mempool_metrics(&self) -> reqwest::Result<MempoolMetrics> {
+        self.get_json(MANTLE_METRICS).await
+    }
+
+    /// Number of transactions waiting in the mantle mempool.
+    pub async fn mempool_pending_count(&self) -> reqwest::Result<usize> {
+        Ok(self.mempool_metrics().await?.pending_items)
+    }
+
+    /// Status of each of `hashes` in the mantle mempool, in request order.
+    pub async fn mempool_status(&self, hashes: &[TxHash]) -> reqwest::Result<Vec<MempoolTxStatus>> {
+        self.post_json_decode(MANTLE_STATUS, hashes).await
+    }
+
+    /// The subset of `candidates` still pending in the mantle mempool.
+    pub async fn mempool_pending_hashes(
+        &self,
+        candidates: &[TxHash],
+    ) -> reqwest::Result<Vec<TxHash>> {
+        let statuses = self.mempool_status(candidates).await?;
+        Ok(candidates
+            .iter()
+            .zip(statuses)
+            .filter(|(_, status)| *status == MempoolTxStatus::Pending)
+            .map(|(hash, _)| *hash)
+            .collect())
     }
 
     #[must_use]
@@ -332,3 +386,51 @@ impl ApiClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lb_node::HeaderId;
+    use serde_json::json;
+
+    use super::{MempoolMetrics, MempoolTxStatus};
+
+    #[test]
+    fn decodes_mempool_statuses() {
+        let block = HeaderId::from([3; 32]);
+        let statuses: Vec<MempoolTxStatus> = serde_json::from_value(json!([
+            "Unknown",
+            "Pending",
+            "Rejected",
+            { "InBlock": { "block": block } },
+        ]))
+        .expect("statuses");
+
+        assert_eq!(
+            statuses,
+            vec![
+                MempoolTxStatus::Unknown,
+                MempoolTxStatus::Pending,
+                MempoolTxStatus::Rejected,
+                MempoolTxStatus::InBlock { block },
+            ]
+        );
+        let accepted = statuses
+            .iter()
+            .map(MempoolTxStatus::is_accepted)
+            .collect::<Vec<_>>();
+        assert_eq!(accepted, vec![false, true, false, true]);
+    }
+
+    #[test]
+    fn metrics_default_the_last_item_timestamp() {
+        let metrics: MempoolMetrics =
+            serde_json::from_value(json!({ "pending_items": 4 })).expect("metrics");
+        assert_eq!(
+            metrics,
+            MempoolMetrics {
+                pending_items: 4,
+                last_item_timestamp: 0,
+            }
+        );
+    }
+}
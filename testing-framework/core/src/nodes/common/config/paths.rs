@@ -31,3 +31,17 @@ pub fn ensure_recovery_paths(base_dir: &Path) -> io::Result<()> {
 
     Ok(())
 }
+
+/// Remove the node database at `db_path` and the recovery files under
+/// `base_dir`, then recreate empty recovery placeholders.
+pub fn wipe_node_state(base_dir: &Path, db_path: &Path) -> io::Result<()> {
+    for path in [db_path.to_path_buf(), base_dir.join("recovery")] {
+        match fs::remove_dir_all(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    ensure_recovery_paths(base_dir)
+}
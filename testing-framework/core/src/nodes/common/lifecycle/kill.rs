@@ -1,6 +1,87 @@
-use std::process::Child;
+use std::{io, process::Child};
 
 /// Shared cleanup helpers for child processes.
 pub fn kill_child(child: &mut Child) {
     let _ = child.kill();
 }
+
+/// Signals sent to node processes besides the SIGKILL of [`kill_child`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// Ask the process to shut down.
+    Term,
+    /// Suspend the process; it keeps its sockets but stops responding.
+    Stop,
+    /// Resume a suspended process.
+    Cont,
+}
+
+impl Signal {
+    const fn number(self) -> libc::c_int {
+        match self {
+            Self::Term => libc::SIGTERM,
+            Self::Stop => libc::SIGSTOP,
+            Self::Cont => libc::SIGCONT,
+        }
+    }
+}
+
+/// Send `signal` to the child.
+pub fn signal_child(child: &Child, signal: Signal) -> io::Result<()> {
+    let pid = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+    // SAFETY: `kill` takes no pointers. `child` is borrowed, so it has not
+    // been waited on and `pid` cannot have been reused by another process.
+    if unsafe { libc::kill(pid, signal.number()) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        os::unix::process::ExitStatusExt as _,
+        process::{Child, Command},
+    };
+
+    use super::{Signal, signal_child};
+
+    fn sleeper() -> Child {
+        Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep")
+    }
+
+    fn wait_status(child: &Child, options: libc::c_int) -> libc::c_int {
+        let pid = libc::pid_t::try_from(child.id()).expect("pid");
+        let mut status = 0;
+        // SAFETY: `status` outlives the call and `pid` is our unreaped child.
+        let waited = unsafe { libc::waitpid(pid, &mut status, options) };
+        assert_eq!(waited, pid);
+        status
+    }
+
+    #[test]
+    fn freeze_and_unfreeze_suspend_and_resume_the_child() {
+        let mut child = sleeper();
+
+        signal_child(&child, Signal::Stop).expect("stop");
+        assert!(libc::WIFSTOPPED(wait_status(&child, libc::WUNTRACED)));
+        signal_child(&child, Signal::Cont).expect("cont");
+        assert!(libc::WIFCONTINUED(wait_status(&child, libc::WCONTINUED)));
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    #[test]
+    fn terminate_ends_the_child_with_sigterm() {
+        let mut child = sleeper();
+
+        signal_child(&child, Signal::Term).expect("term");
+        let status = child.wait().expect("wait");
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod kill;
 pub mod monitor;
+pub mod output;
 pub mod spawn;
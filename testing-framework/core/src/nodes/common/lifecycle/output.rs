@@ -0,0 +1,321 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead as _, BufReader, Read, Write as _},
+    path::{Path, PathBuf},
+    process::Child,
+    sync::{Arc, LazyLock, Mutex, MutexGuard},
+    thread,
+};
+
+use testing_framework_env as tf_env;
+
+pub const STDOUT_FILE: &str = "stdout.log";
+pub const STDERR_FILE: &str = "stderr.log";
+const DEFAULT_TAIL_LINES: usize = 50;
+
+static TAIL_LINES: LazyLock<usize> =
+    LazyLock::new(|| tf_env::node_log_tail_lines().unwrap_or(DEFAULT_TAIL_LINES));
+
+#[derive(Clone, Copy, Debug)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug)]
+struct OutputInner {
+    label: String,
+    stdout: PathBuf,
+    stderr: PathBuf,
+    echo: bool,
+    capacity: usize,
+    tail: Mutex<VecDeque<String>>,
+}
+
+/// Captured stdout/stderr of one node process.
+///
+/// Both streams are appended to `stdout.log`/`stderr.log` in the node
+/// directory, and the last lines of either are kept in memory. The same
+/// handle is reattached on restarts, so the files and the tail cover every
+/// process the node ran as.
+#[derive(Clone, Debug)]
+pub struct NodeOutput {
+    inner: Arc<OutputInner>,
+}
+
+impl NodeOutput {
+    /// Create empty log files in `dir`. With `echo` set every line is also
+    /// forwarded to the test process' own stdout/stderr.
+    pub fn create(dir: &Path, label: &str, echo: bool) -> io::Result<Self> {
+        let stdout = dir.join(STDOUT_FILE);
+        let stderr = dir.join(STDERR_FILE);
+        File::create(&stdout)?;
+        File::create(&stderr)?;
+
+        Ok(Self {
+            inner: Arc::new(OutputInner {
+                label: label.to_owned(),
+                stdout,
+                stderr,
+                echo,
+                capacity: (*TAIL_LINES).max(1),
+                tail: Mutex::new(VecDeque::new()),
+            }),
+        })
+    }
+
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.inner.label
+    }
+
+    #[must_use]
+    pub fn stdout_path(&self) -> &Path {
+        &self.inner.stdout
+    }
+
+    #[must_use]
+    pub fn stderr_path(&self) -> &Path {
+        &self.inner.stderr
+    }
+
+    /// Last captured lines of both streams, oldest first. Stderr lines are
+    /// prefixed with `[stderr]`.
+    #[must_use]
+    pub fn tail(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Snapshot of the tail together with the log file paths.
+    #[must_use]
+    pub fn log_tail(&self) -> NodeLogTail {
+        NodeLogTail {
+            node: self.inner.label.clone(),
+            stdout: self.inner.stdout.clone(),
+            stderr: self.inner.stderr.clone(),
+            lines: self.tail(),
+        }
+    }
+
+    /// Record a framework event (such as a restart) in the tail.
+    pub fn note(&self, message: &str) {
+        self.push(format!("--- {message} ---"));
+    }
+
+    /// Take the piped stdout/stderr of `child` and copy them into the log
+    /// files and the tail on background threads, which end when the process
+    /// closes its streams.
+    pub fn attach(&self, child: &mut Child) -> io::Result<()> {
+        if let Some(stdout) = child.stdout.take() {
+            self.spawn_copy(stdout, Stream::Stdout)?;
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.spawn_copy(stderr, Stream::Stderr)?;
+        }
+        Ok(())
+    }
+
+    fn spawn_copy<R>(&self, reader: R, stream: Stream) -> io::Result<()>
+    where
+        R: Read + Send + 'static,
+    {
+        let path = match stream {
+            Stream::Stdout => &self.inner.stdout,
+            Stream::Stderr => &self.inner.stderr,
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let output = self.clone();
+
+        thread::Builder::new()
+            .name(format!("{}-output", self.inner.label))
+            .spawn(move || {
+                let mut reader = BufReader::new(reader);
+                let mut line = Vec::new();
+                loop {
+                    line.clear();
+                    match reader.read_until(b'\n', &mut line) {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => {}
+                    }
+                    let _ = file.write_all(&line);
+
+                    let text = String::from_utf8_lossy(&line).trim_end().to_owned();
+                    match stream {
+                        Stream::Stdout => {
+                            if output.inner.echo {
+                                println!("{text}");
+                            }
+                            output.push(text);
+                        }
+                        Stream::Stderr => {
+                            if output.inner.echo {
+                                eprintln!("{text}");
+                            }
+                            output.push(format!("[stderr] {text}"));
+                        }
+                    }
+                }
+            })?;
+        Ok(())
+    }
+
+    fn push(&self, line: String) {
+        let mut tail = self.lock();
+        if tail.len() == self.inner.capacity {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<String>> {
+        self.inner
+            .tail
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Last output lines of one node and where its full logs are.
+#[derive(Clone, Debug)]
+pub struct NodeLogTail {
+    pub node: String,
+    pub stdout: PathBuf,
+    pub stderr: PathBuf,
+    pub lines: Vec<String>,
+}
+
+/// Output tails attached to a failed run, one entry per node.
+#[derive(Clone, Debug, Default)]
+pub struct NodeLogTails(pub Vec<NodeLogTail>);
+
+impl NodeLogTails {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Errors that can carry the output tails of the nodes involved in a failure.
+///
+/// Implementors provide a variant that wraps another error together with the
+/// tails; attaching replaces earlier tails instead of nesting them.
+pub trait AttachNodeLogs: Sized {
+    /// Wraps `source`, which never carries tails itself, with `logs`.
+    fn wrap_node_logs(source: Self, logs: NodeLogTails) -> Self;
+
+    /// Splits off attached node output tails.
+    #[must_use]
+    fn into_parts(self) -> (Self, Option<NodeLogTails>);
+
+    /// Node output tails attached to this error, if any.
+    #[must_use]
+    fn node_logs(&self) -> Option<&NodeLogTails>;
+
+    /// Attach the output tails of the nodes; empty tails leave the error as
+    /// it is.
+    #[must_use]
+    fn with_node_logs(self, logs: NodeLogTails) -> Self {
+        if logs.is_empty() {
+            return self;
+        }
+        let (source, _) = self.into_parts();
+        Self::wrap_node_logs(source, logs)
+    }
+}
+
+impl fmt::Display for NodeLogTails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "node output:")?;
+        for tail in &self.0 {
+            writeln!(
+                f,
+                "== {} (stdout: {}, stderr: {}) ==",
+                tail.node,
+                tail.stdout.display(),
+                tail.stderr.display()
+            )?;
+            if tail.lines.is_empty() {
+                writeln!(f, "  <no output>")?;
+            }
+            for line in &tail.lines {
+                writeln!(f, "  {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Output handles of the nodes in a run; clones share the same set.
+#[derive(Clone, Debug, Default)]
+pub struct NodeLogs {
+    outputs: Arc<Mutex<Vec<NodeOutput>>>,
+}
+
+impl NodeLogs {
+    /// Track `output`, replacing any earlier output with the same label.
+    pub fn register(&self, output: NodeOutput) {
+        let mut outputs = self.lock();
+        outputs.retain(|existing| existing.label() != output.label());
+        outputs.push(output);
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    #[must_use]
+    pub fn tails(&self) -> NodeLogTails {
+        NodeLogTails(self.lock().iter().map(NodeOutput::log_tail).collect())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<NodeOutput>> {
+        self.outputs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        process::{Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use super::NodeOutput;
+
+    #[test]
+    fn captures_both_streams_into_files_and_tail() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = NodeOutput::create(dir.path(), "node-0", false).expect("create output");
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("echo out; echo err >&2")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("spawn sh");
+        output.attach(&mut child).expect("attach");
+        child.wait().expect("wait");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while output.tail().len() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let mut tail = output.tail();
+        tail.sort();
+        assert_eq!(tail, ["[stderr] err", "out"]);
+        assert_eq!(
+            std::fs::read_to_string(output.stdout_path()).unwrap(),
+            "out\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.stderr_path()).unwrap(),
+            "err\n"
+        );
+    }
+}
@@ -16,7 +16,7 @@ use tracing::{debug, info};
 
 use super::lifecycle::monitor::is_running;
 use crate::nodes::{
-    ApiClient,
+    ApiClient, LOGS_PREFIX, NodeOutput,
     common::{config::paths::ensure_recovery_paths, lifecycle::spawn::configure_logging},
     create_tempdir, persist_tempdir,
 };
@@ -52,13 +52,35 @@ pub enum SpawnNodeError {
         #[source]
         source: io::Error,
     },
+    #[error("failed to capture node output: {source}")]
+    CaptureOutput {
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to wipe node state: {source}")]
+    WipeState {
+        #[source]
+        source: io::Error,
+    },
     #[error("node did not become ready before timeout: {source}")]
     Readiness {
         #[source]
         source: tokio::time::error::Elapsed,
+        output: NodeOutput,
     },
 }
 
+impl SpawnNodeError {
+    /// Output captured from the node process, if it got far enough to start.
+    #[must_use]
+    pub const fn output(&self) -> Option<&NodeOutput> {
+        match self {
+            Self::Readiness { output, .. } => Some(output),
+            _ => None,
+        }
+    }
+}
+
 /// Minimal interface to apply common node setup.
 pub trait NodeConfigCommon {
     fn set_logger(&mut self, logger: LoggerLayer);
@@ -72,15 +94,23 @@ pub struct NodeHandle<T> {
     pub(crate) tempdir: TempDir,
     pub(crate) config: T,
     pub(crate) api: ApiClient,
+    pub(crate) output: NodeOutput,
 }
 
 impl<T> NodeHandle<T> {
-    pub fn new(child: Child, tempdir: TempDir, config: T, api: ApiClient) -> Self {
+    pub fn new(
+        child: Child,
+        tempdir: TempDir,
+        config: T,
+        api: ApiClient,
+        output: NodeOutput,
+    ) -> Self {
         Self {
             child,
             tempdir,
             config,
             api,
+            output,
         }
     }
 
@@ -104,6 +134,12 @@ impl<T> NodeHandle<T> {
         &self.config
     }
 
+    /// Captured stdout/stderr of the node process.
+    #[must_use]
+    pub const fn output(&self) -> &NodeOutput {
+        &self.output
+    }
+
     /// Returns true if the process exited within the timeout, false otherwise.
     pub async fn wait_for_exit(&mut self, timeout: Duration) -> bool {
         time::timeout(timeout, async {
@@ -150,28 +186,42 @@ pub fn prepare_node_config<T: NodeConfigCommon>(
 }
 
 /// Spawn a node with shared setup, config writing, and readiness wait.
+///
+/// The process output is captured into the node directory; it is echoed to
+/// the test process only when file logging is disabled.
 pub async fn spawn_node<C>(
     config: C,
-    log_prefix: &str,
+    label: &str,
     config_filename: &str,
     binary_path: PathBuf,
+    env: &[(String, String)],
     enable_logging: bool,
     persist_dir: Option<PathBuf>,
 ) -> Result<NodeHandle<C>, SpawnNodeError>
 where
     C: NodeConfigCommon + Serialize,
 {
+    let log_prefix = format!("{LOGS_PREFIX}-{label}");
     let (dir, config, addr, testing_addr) =
-        prepare_node_config(config, log_prefix, enable_logging, persist_dir)?;
+        prepare_node_config(config, &log_prefix, enable_logging, persist_dir)?;
 
     let config_path = dir.path().join(config_filename);
     write_node_config(&config, &config_path)?;
 
+    let output = NodeOutput::create(dir.path(), label, !enable_logging)
+        .map_err(|source| SpawnNodeError::CaptureOutput { source })?;
+
     debug!(config_file = %config_path.display(), binary = %binary_path.display(), "spawning node process");
 
-    let child = spawn_node_process(&binary_path, &config_path, dir.path())?;
+    let child = spawn_node_process(&binary_path, &config_path, dir.path(), env, &output)?;
 
-    let mut handle = NodeHandle::new(child, dir, config, ApiClient::new(addr, testing_addr));
+    let mut handle = NodeHandle::new(
+        child,
+        dir,
+        config,
+        ApiClient::new(addr, testing_addr),
+        output,
+    );
 
     // Wait for readiness via consensus_info
     let ready = wait_for_consensus_readiness(&handle.api).await;
@@ -179,14 +229,20 @@ where
     if let Err(err) = ready {
         // Persist tempdir to aid debugging if readiness fails.
         let _ = persist_tempdir(&mut handle.tempdir, "logos-blockchain-node");
-        return Err(SpawnNodeError::Readiness { source: err });
+        return Err(SpawnNodeError::Readiness {
+            source: err,
+            output: handle.output.clone(),
+        });
     }
 
     info!("node readiness confirmed via consensus_info");
     Ok(handle)
 }
 
-fn write_node_config<C: Serialize>(config: &C, config_path: &Path) -> Result<(), SpawnNodeError> {
+pub(crate) fn write_node_config<C: Serialize>(
+    config: &C,
+    config_path: &Path,
+) -> Result<(), SpawnNodeError> {
     super::lifecycle::spawn::write_config_with_injection(config, config_path, |yaml| {
         crate::nodes::common::config::injection::inject_ibd_into_cryptarchia(yaml);
         crate::nodes::common::config::injection::inject_blend_non_ephemeral_signing_key_id(yaml);
@@ -238,24 +294,35 @@ pub(crate) fn spawn_node_process(
     binary_path: &Path,
     config_path: &Path,
     workdir: &Path,
+    env: &[(String, String)],
+    output: &NodeOutput,
 ) -> Result<Child, SpawnNodeError> {
     let mut cmd = Command::new(binary_path);
-    cmd.arg(config_path);
+    cmd.arg(config_path)
+        .envs(env.iter().map(|(key, value)| (key, value)));
 
     let deployment_path = workdir.join("deployment.yaml");
     if deployment_path.is_file() {
         cmd.arg("--deployment").arg(deployment_path);
     }
 
-    cmd.current_dir(workdir)
+    let mut child = cmd
+        .current_dir(workdir)
         .stdin(Stdio::null())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
         .map_err(|source| SpawnNodeError::Spawn {
             binary: binary_path.to_path_buf(),
             source,
-        })
+        })?;
+
+    if let Err(source) = output.attach(&mut child) {
+        let _ = child.kill();
+        return Err(SpawnNodeError::CaptureOutput { source });
+    }
+
+    Ok(child)
 }
 
 pub(crate) async fn wait_for_consensus_readiness(
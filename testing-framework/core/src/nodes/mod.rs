@@ -9,7 +9,10 @@ use std::{
     sync::LazyLock,
 };
 
-pub use api_client::{ApiClient, ApiClientError};
+pub use api_client::{ApiClient, ApiClientError, MempoolMetrics, MempoolTxStatus};
+pub use common::lifecycle::output::{
+    AttachNodeLogs, NodeLogTail, NodeLogTails, NodeLogs, NodeOutput,
+};
 use tempfile::TempDir;
 use testing_framework_env as tf_env;
 
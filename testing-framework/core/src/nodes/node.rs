@@ -1,21 +1,22 @@
-use std::{ops::Deref, path::PathBuf, time::Duration};
+use std::{io, ops::Deref, path::PathBuf, process::ExitStatus, time::Duration};
 
 use lb_node::config::RunConfig;
 use lb_tracing_service::LoggerLayer;
 pub use testing_framework_config::nodes::node::create_node_config;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::{
     IS_DEBUG_TRACING,
-    nodes::{
-        LOGS_PREFIX,
-        common::{
-            binary::{BinaryConfig, BinaryResolver},
-            lifecycle::{kill::kill_child, monitor::is_running},
-            node::{
-                NodeAddresses, NodeConfigCommon, NodeHandle, SpawnNodeError, spawn_node,
-                spawn_node_process, wait_for_consensus_readiness,
-            },
+    nodes::common::{
+        binary::{BinaryConfig, BinaryResolver},
+        config::paths::wipe_node_state,
+        lifecycle::{
+            kill::{Signal, kill_child, signal_child},
+            monitor::is_running,
+        },
+        node::{
+            NodeAddresses, NodeConfigCommon, NodeHandle, SpawnNodeError, spawn_node,
+            spawn_node_process, wait_for_consensus_readiness, write_node_config,
         },
     },
     scenario::DynError,
@@ -37,6 +38,8 @@ fn binary_path() -> PathBuf {
 
 pub struct Node {
     handle: NodeHandle<RunConfig>,
+    binary: PathBuf,
+    env: Vec<(String, String)>,
 }
 
 pub fn apply_node_config_patches<'a>(
@@ -83,6 +86,11 @@ impl Node {
         is_running(&mut self.handle.child)
     }
 
+    /// Exit status of the node process, or `None` while it is running.
+    pub fn exit_status(&mut self) -> Option<ExitStatus> {
+        self.handle.child.try_wait().ok().flatten()
+    }
+
     /// Wait for the node process to exit, with a timeout
     /// Returns true if the process exited within the timeout, false otherwise
     pub async fn wait_for_exit(&mut self, timeout: Duration) -> bool {
@@ -94,20 +102,49 @@ impl Node {
         label: &str,
         persist_dir: Option<PathBuf>,
     ) -> Result<Self, SpawnNodeError> {
-        let log_prefix = format!("{LOGS_PREFIX}-{label}");
+        Self::spawn_with_binary(config, label, persist_dir, binary_path()).await
+    }
+
+    /// Spawn the node from `binary` instead of the resolved default. Restarts
+    /// keep using the same binary.
+    pub async fn spawn_with_binary(
+        config: RunConfig,
+        label: &str,
+        persist_dir: Option<PathBuf>,
+        binary: PathBuf,
+    ) -> Result<Self, SpawnNodeError> {
+        Self::spawn_with_env(config, label, persist_dir, Some(binary), Vec::new()).await
+    }
+
+    /// Spawn the node with extra environment variables on top of the
+    /// inherited ones, from `binary` or the resolved default. Restarts keep
+    /// the same binary and environment.
+    pub async fn spawn_with_env(
+        config: RunConfig,
+        label: &str,
+        persist_dir: Option<PathBuf>,
+        binary: Option<PathBuf>,
+        env: Vec<(String, String)>,
+    ) -> Result<Self, SpawnNodeError> {
+        let binary = binary.unwrap_or_else(binary_path);
         let handle = spawn_node(
             config,
-            &log_prefix,
+            label,
             "node.yaml",
-            binary_path(),
+            binary.clone(),
+            &env,
             !*IS_DEBUG_TRACING,
             persist_dir,
         )
         .await?;
 
-        info!("node spawned and ready");
+        info!(binary = %binary.display(), "node spawned and ready");
 
-        Ok(Self { handle })
+        Ok(Self {
+            handle,
+            binary,
+            env,
+        })
     }
 
     /// Restart the node process using the existing config and data directory.
@@ -119,13 +156,23 @@ impl Node {
         let _ = self.wait_for_exit(RESTART_SHUTDOWN_TIMEOUT).await;
 
         let config_path = self.handle.tempdir.path().join("node.yaml");
-        let child = spawn_node_process(&binary_path(), &config_path, self.handle.tempdir.path())?;
+        self.handle.output.note("restarting node");
+        let child = spawn_node_process(
+            &self.binary,
+            &config_path,
+            self.handle.tempdir.path(),
+            &self.env,
+            &self.handle.output,
+        )?;
         self.handle.child = child;
 
         let new_pid = self.pid();
         wait_for_consensus_readiness(&self.handle.api)
             .await
-            .map_err(|source| SpawnNodeError::Readiness { source })?;
+            .map_err(|source| SpawnNodeError::Readiness {
+                source,
+                output: self.handle.output.clone(),
+            })?;
 
         info!(
             old_pid,
@@ -135,6 +182,20 @@ impl Node {
         Ok(())
     }
 
+    /// Apply `update` to the node config, persist it and restart the process
+    /// against the existing data directory.
+    pub async fn restart_with_config(
+        &mut self,
+        update: impl FnOnce(&mut RunConfig),
+    ) -> Result<(), SpawnNodeError> {
+        update(&mut self.handle.config);
+
+        let config_path = self.handle.tempdir.path().join("node.yaml");
+        write_node_config(&self.handle.config, &config_path)?;
+
+        self.restart().await
+    }
+
     /// Stop the node process without restarting it.
     pub async fn stop(&mut self) {
         let pid = self.pid();
@@ -143,6 +204,51 @@ impl Node {
         kill_child(&mut self.handle.child);
         let _ = self.wait_for_exit(RESTART_SHUTDOWN_TIMEOUT).await;
     }
+
+    /// Ask the node to shut down with SIGTERM, killing it if it has not
+    /// exited within `timeout`. Returns whether it shut down on its own.
+    pub async fn terminate(&mut self, timeout: Duration) -> io::Result<bool> {
+        let pid = self.pid();
+        debug!(pid, ?timeout, "terminating node process");
+
+        signal_child(&self.handle.child, Signal::Term)?;
+        // A frozen process only acts on SIGTERM once it is resumed.
+        signal_child(&self.handle.child, Signal::Cont)?;
+        if self.wait_for_exit(timeout).await {
+            return Ok(true);
+        }
+
+        warn!(pid, ?timeout, "node ignored SIGTERM, killing it");
+        self.stop().await;
+        Ok(false)
+    }
+
+    /// Suspend the node process with SIGSTOP. It keeps its sockets open but
+    /// stops responding, like a hung node.
+    pub fn freeze(&self) -> io::Result<()> {
+        debug!(pid = self.pid(), "freezing node process");
+        signal_child(&self.handle.child, Signal::Stop)
+    }
+
+    /// Resume a node suspended by [`freeze`](Self::freeze).
+    pub fn unfreeze(&self) -> io::Result<()> {
+        debug!(pid = self.pid(), "unfreezing node process");
+        signal_child(&self.handle.child, Signal::Cont)
+    }
+
+    /// Restart the node with the same config on an empty database and
+    /// recovery state, so it has to resync from its peers.
+    pub async fn restart_wiped(&mut self) -> Result<(), SpawnNodeError> {
+        self.stop().await;
+
+        wipe_node_state(
+            self.handle.tempdir.path(),
+            &self.handle.config.user.storage.db_path,
+        )
+        .map_err(|source| SpawnNodeError::WipeState { source })?;
+
+        self.restart().await
+    }
 }
 
 impl NodeConfigCommon for RunConfig {
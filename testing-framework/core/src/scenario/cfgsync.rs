@@ -7,7 +7,13 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use tracing::debug;
 
-use crate::topology::{configs::wallet::WalletConfig, generation::GeneratedTopology};
+use crate::topology::{
+    configs::{
+        network::Libp2pNetworkLayout, node_spec::NodeSpecs, stake::StakeDistribution,
+        wallet::WalletConfig,
+    },
+    generation::GeneratedTopology,
+};
 
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +26,12 @@ pub struct CfgSyncConfig {
     #[serde(default)]
     pub wallet: WalletConfig,
     #[serde(default)]
+    pub stake_distribution: StakeDistribution,
+    #[serde(default)]
+    pub node_specs: NodeSpecs,
+    #[serde(default)]
+    pub network_layout: Libp2pNetworkLayout,
+    #[serde(default)]
     pub ids: Option<Vec<[u8; 32]>>,
     #[serde(default)]
     pub blend_ports: Option<Vec<u16>>,
@@ -74,9 +86,12 @@ pub fn apply_topology_overrides(cfg: &mut CfgSyncConfig, topology: &GeneratedTop
     let consensus = &topology.config().consensus_params;
     cfg.security_param = consensus.security_param;
     cfg.active_slot_coeff = consensus.active_slot_coeff;
+    cfg.stake_distribution = consensus.stake_distribution.clone();
 
     let config = topology.config();
     cfg.wallet = config.wallet_config.clone();
+    cfg.node_specs = config.node_specs.clone();
+    cfg.network_layout = config.network_params.libp2p_network_layout.clone();
     cfg.ids = Some(topology.nodes().iter().map(|node| node.id).collect());
     cfg.blend_ports = Some(
         topology
@@ -96,6 +111,10 @@ struct SerializableCfgSyncConfig {
     security_param: NonZero<u32>,
     active_slot_coeff: f64,
     wallet: WalletConfig,
+    stake_distribution: StakeDistribution,
+    #[serde(skip_serializing_if = "NodeSpecs::is_empty")]
+    node_specs: NodeSpecs,
+    network_layout: Libp2pNetworkLayout,
     #[serde(skip_serializing_if = "Option::is_none")]
     ids: Option<Vec<[u8; 32]>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -128,6 +147,9 @@ impl From<&CfgSyncConfig> for SerializableCfgSyncConfig {
             security_param: cfg.security_param,
             active_slot_coeff: cfg.active_slot_coeff,
             wallet: cfg.wallet.clone(),
+            stake_distribution: cfg.stake_distribution.clone(),
+            node_specs: cfg.node_specs.clone(),
+            network_layout: cfg.network_layout.clone(),
             ids: cfg.ids.clone(),
             blend_ports: cfg.blend_ports.clone(),
             subnetwork_size: cfg.subnetwork_size,
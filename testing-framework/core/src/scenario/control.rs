@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
 use crate::{
@@ -28,6 +30,54 @@ pub trait NodeControlHandle: Send + Sync {
         Err("stop_node not supported by this deployer".into())
     }
 
+    /// Ask the named node to shut down gracefully (SIGTERM), killing it if
+    /// it is still running after `timeout`.
+    async fn terminate_node(&self, _name: &str, _timeout: Duration) -> Result<(), DynError> {
+        Err("terminate_node not supported by this deployer".into())
+    }
+
+    /// Kill the named node abruptly (SIGKILL), as a crash would.
+    async fn crash_node(&self, _name: &str) -> Result<(), DynError> {
+        Err("crash_node not supported by this deployer".into())
+    }
+
+    /// Suspend the named node without closing its connections, simulating a
+    /// hung process.
+    async fn freeze_node(&self, _name: &str) -> Result<(), DynError> {
+        Err("freeze_node not supported by this deployer".into())
+    }
+
+    /// Resume a node suspended by [`freeze_node`](Self::freeze_node).
+    async fn unfreeze_node(&self, _name: &str) -> Result<(), DynError> {
+        Err("unfreeze_node not supported by this deployer".into())
+    }
+
+    /// Restart the named node on empty state;
+    /// [`restart_node`](Self::restart_node) keeps its data.
+    async fn restart_node_wiped(&self, _name: &str) -> Result<(), DynError> {
+        Err("restart_node_wiped not supported by this deployer".into())
+    }
+
+    /// Cut the named node off from every other node in the topology.
+    async fn isolate_node(&self, _name: &str) -> Result<(), DynError> {
+        Err("isolate_node not supported by this deployer".into())
+    }
+
+    /// Move the named nodes into a partition of their own. Members of the
+    /// group stay connected to each other where the deployer supports it;
+    /// the default falls back to isolating each node individually.
+    async fn partition_nodes(&self, names: &[String]) -> Result<(), DynError> {
+        for name in names {
+            self.isolate_node(name).await?;
+        }
+        Ok(())
+    }
+
+    /// Undo a previous isolation/partition and rejoin the original topology.
+    async fn reconnect_node(&self, _name: &str) -> Result<(), DynError> {
+        Err("reconnect_node not supported by this deployer".into())
+    }
+
     fn node_client(&self, _name: &str) -> Option<ApiClient> {
         None
     }
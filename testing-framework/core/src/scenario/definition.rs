@@ -5,12 +5,15 @@ use thiserror::Error;
 use tracing::{debug, info};
 
 use super::{
-    DynError, NodeControlCapability, expectation::Expectation, runtime::context::RunMetrics,
-    workload::Workload,
+    BlockFeedMode, DynError, NodeControlCapability, expectation::Expectation,
+    runtime::context::RunMetrics, seed::resolve_seed, workload::Workload,
 };
 use crate::topology::{
     config::{NodeConfigPatch, TopologyBuildError, TopologyBuilder, TopologyConfig},
-    configs::{network::Libp2pNetworkLayout, wallet::WalletConfig},
+    configs::{
+        network::Libp2pNetworkLayout, node_spec::NodeSpec, stake::StakeDistribution,
+        wallet::WalletConfig,
+    },
     generation::GeneratedTopology,
 };
 
@@ -30,6 +33,8 @@ pub enum ScenarioBuildError {
     WorkloadInit { name: String, source: DynError },
     #[error("expectation '{name}' failed to initialize")]
     ExpectationInit { name: String, source: DynError },
+    #[error("LOGOS_BLOCKCHAIN_SCENARIO_SEED is not a valid u64 seed")]
+    InvalidSeed { source: std::num::ParseIntError },
 }
 
 /// Immutable scenario definition shared between the runner, workloads, and
@@ -39,6 +44,8 @@ pub struct Scenario<Caps = ()> {
     workloads: Vec<Arc<dyn Workload>>,
     expectations: Vec<Box<dyn Expectation>>,
     duration: Duration,
+    block_feed_mode: BlockFeedMode,
+    seed: u64,
     capabilities: Caps,
 }
 
@@ -48,6 +55,8 @@ impl<Caps> Scenario<Caps> {
         workloads: Vec<Arc<dyn Workload>>,
         expectations: Vec<Box<dyn Expectation>>,
         duration: Duration,
+        block_feed_mode: BlockFeedMode,
+        seed: u64,
         capabilities: Caps,
     ) -> Self {
         Self {
@@ -55,6 +64,8 @@ impl<Caps> Scenario<Caps> {
             workloads,
             expectations,
             duration,
+            block_feed_mode,
+            seed,
             capabilities,
         }
     }
@@ -84,6 +95,22 @@ impl<Caps> Scenario<Caps> {
         self.duration
     }
 
+    #[must_use]
+    /// Which nodes deployers should scan for the run's block feed.
+    pub const fn block_feed_mode(&self) -> BlockFeedMode {
+        self.block_feed_mode
+    }
+
+    #[must_use]
+    /// Seed for all scenario-level randomness; rerun with the same seed to
+    /// replay chaos target and delay choices and every other
+    /// [`RunContext::seeded_rng`] stream.
+    ///
+    /// [`RunContext::seeded_rng`]: crate::scenario::RunContext::seeded_rng
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
     #[must_use]
     pub const fn capabilities(&self) -> &Caps {
         &self.capabilities
@@ -97,6 +124,8 @@ pub struct Builder<Caps = ()> {
     expectations: Vec<Box<dyn Expectation>>,
     duration: Duration,
     wallet_users: Option<usize>,
+    block_feed_mode: BlockFeedMode,
+    seed: Option<u64>,
     capabilities: Caps,
 }
 
@@ -120,6 +149,8 @@ impl<Caps: Default> Builder<Caps> {
             expectations: Vec::new(),
             duration: Duration::ZERO,
             wallet_users: None,
+            block_feed_mode: BlockFeedMode::default(),
+            seed: None,
             capabilities: Caps::default(),
         }
     }
@@ -157,6 +188,8 @@ impl<Caps> Builder<Caps> {
             expectations,
             duration,
             wallet_users,
+            block_feed_mode,
+            seed,
             ..
         } = self;
 
@@ -166,6 +199,8 @@ impl<Caps> Builder<Caps> {
             expectations,
             duration,
             wallet_users,
+            block_feed_mode,
+            seed,
             capabilities,
         }
     }
@@ -207,6 +242,21 @@ impl<Caps> Builder<Caps> {
         self
     }
 
+    #[must_use]
+    /// Choose which nodes the block feed scans during the run.
+    pub const fn with_block_feed_mode(mut self, mode: BlockFeedMode) -> Self {
+        self.block_feed_mode = mode;
+        self
+    }
+
+    #[must_use]
+    /// Fix the scenario seed. Without it the seed comes from
+    /// `LOGOS_BLOCKCHAIN_SCENARIO_SEED` or is chosen at random.
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     #[must_use]
     /// Transform the topology builder.
     pub fn map_topology(mut self, f: impl FnOnce(TopologyBuilder) -> TopologyBuilder) -> Self {
@@ -229,6 +279,16 @@ impl<Caps> Builder<Caps> {
         builder
     }
 
+    #[must_use]
+    /// Sync nodes against the framework time server so workloads can skew
+    /// their clocks through [`RunContext::clock`]. Only the local deployer
+    /// runs the server.
+    ///
+    /// [`RunContext::clock`]: crate::scenario::RunContext::clock
+    pub fn with_framework_time(self) -> Self {
+        self.map_topology(|topology| topology.with_framework_time(true))
+    }
+
     #[must_use]
     /// Finalize the scenario, computing run metrics and initializing
     /// components.
@@ -239,6 +299,8 @@ impl<Caps> Builder<Caps> {
             mut expectations,
             duration,
             wallet_users,
+            block_feed_mode,
+            seed,
             capabilities,
             ..
         } = self;
@@ -262,8 +324,10 @@ impl<Caps> Builder<Caps> {
         let run_metrics = RunMetrics::from_topology(&generated, duration);
         initialize_components(&generated, &run_metrics, &mut workloads, &mut expectations)?;
         let workloads: Vec<Arc<dyn Workload>> = workloads.into_iter().map(Arc::from).collect();
+        let seed = resolve_seed(seed)?;
 
         info!(
+            seed,
             nodes = generated.nodes().len(),
             duration_secs = duration.as_secs(),
             workloads = workloads.len(),
@@ -276,6 +340,8 @@ impl<Caps> Builder<Caps> {
             workloads,
             expectations,
             duration,
+            block_feed_mode,
+            seed,
             capabilities,
         ))
     }
@@ -305,6 +371,29 @@ impl<Caps> TopologyConfigurator<Caps> {
         self
     }
 
+    /// Spread genesis leader stake across nodes, e.g. one whale and many
+    /// minnows or a Zipf curve, instead of giving every node the same stake.
+    #[must_use]
+    pub fn stake_distribution(mut self, distribution: StakeDistribution) -> Self {
+        self.builder.topology = self.builder.topology.with_stake_distribution(distribution);
+        self
+    }
+
+    /// Give each node an explicit leader stake, in node index order.
+    #[must_use]
+    pub fn stakes(self, stakes: Vec<u64>) -> Self {
+        self.stake_distribution(StakeDistribution::Explicit(stakes))
+    }
+
+    /// Describe a node declaratively: role, stake, initial peers, binary,
+    /// log level, time settings and start delay. Unlike config patches, specs
+    /// are carried to compose/k8s deployments through cfgsync.
+    #[must_use]
+    pub fn node_spec(mut self, index: usize, spec: NodeSpec) -> Self {
+        self.builder.topology = self.builder.topology.with_node_spec(index, spec);
+        self
+    }
+
     /// Use a star libp2p network layout.
     #[must_use]
     pub fn network_star(mut self) -> Self {
@@ -312,6 +401,15 @@ impl<Caps> TopologyConfigurator<Caps> {
         self
     }
 
+    /// Use any libp2p network layout, e.g. a ring, a random regular graph or
+    /// an explicit adjacency list. Replaces an earlier `network_star`.
+    #[must_use]
+    pub fn network_layout(mut self, layout: Libp2pNetworkLayout) -> Self {
+        self.network_star = false;
+        self.builder.topology = self.builder.topology.with_network_layout(layout);
+        self
+    }
+
     /// Apply a config patch for a specific node index.
     #[must_use]
     pub fn node_config_patch(mut self, index: usize, patch: NodeConfigPatch) -> Self {
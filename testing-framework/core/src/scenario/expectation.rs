@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
 use super::{DynError, RunContext, runtime::context::RunMetrics};
@@ -20,5 +22,24 @@ pub trait Expectation: Send + Sync {
         Ok(())
     }
 
+    /// How often [`Expectation::check`] should run while workloads are
+    /// active. `None` (the default) disables continuous checking.
+    fn check_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Whether a failed [`Expectation::check`] should abort the run
+    /// immediately. When `false`, violations are recorded and the expectation
+    /// fails at evaluation time instead.
+    fn fail_fast(&self) -> bool {
+        true
+    }
+
+    /// Invariant polled every [`Expectation::check_interval`] during the
+    /// workload and cooldown phases.
+    async fn check(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        Ok(())
+    }
+
     async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError>;
 }
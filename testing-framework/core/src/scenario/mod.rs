@@ -8,6 +8,7 @@ mod expectation;
 pub mod http_probe;
 mod observability;
 mod runtime;
+mod seed;
 mod workload;
 
 pub type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -23,12 +24,17 @@ pub use definition::{
 pub use expectation::Expectation;
 pub use observability::{ObservabilityCapabilityProvider, ObservabilityInputs};
 pub use runtime::{
-    BlockFeed, BlockFeedTask, BlockRecord, BlockStats, CleanupGuard, Deployer, NodeClients,
-    RunContext, RunHandle, RunMetrics, Runner, ScenarioError,
+    BlockFeed, BlockFeedEvent, BlockFeedMode, BlockFeedTask, BlockRecord, BlockSighting,
+    BlockStats, BlockSummary, CleanupGuard, ClockControl, ClockError, ClockSkew, Deployer,
+    ExitWatchTask, ExpectationPhase, ExpectationReport, ExpectationStatus, NodeClients, NodeExit,
+    NodeExits, NodeReport, NodeTip, PhaseTimings, REPORT_JSON_FILE, REPORT_JUNIT_FILE, Reorg,
+    ResourceSample, ResourceSamplerTask, ResourceUsage, RunContext, RunHandle, RunMetrics,
+    RunOutcome, Runner, ScenarioError, ScenarioReport, ViolationReport, WorkloadReport,
+    WorkloadStatus, describe_exits,
     metrics::{
         CONSENSUS_PROCESSED_BLOCKS, CONSENSUS_TRANSACTIONS_TOTAL, Metrics, MetricsError,
         PrometheusEndpoint, PrometheusInstantSample,
     },
-    spawn_block_feed,
+    spawn_block_feed, spawn_exit_watcher, spawn_multi_node_block_feed, spawn_resource_sampler,
 };
 pub use workload::Workload;
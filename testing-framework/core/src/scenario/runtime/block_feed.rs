@@ -1,23 +1,29 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, VecDeque},
+    fmt,
     sync::{
-        Arc,
+        Arc, Mutex, RwLock,
         atomic::{AtomicU64, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use anyhow::{Context as _, Result};
+use anyhow::{Context as _, Result, anyhow};
+use futures::future::join_all;
 use lb_core::{block::Block, mantle::SignedMantleTx};
 use lb_http_api_common::paths::STORAGE_BLOCK;
 use lb_node::HeaderId;
 use tokio::{sync::broadcast, task::JoinHandle, time::sleep};
 use tracing::{debug, error};
 
-use super::context::CleanupGuard;
+use super::{context::CleanupGuard, node_clients::NodeClients};
 use crate::nodes::ApiClient;
 
 const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Number of most recently observed blocks whose records and sightings the
+/// feed keeps, and of canonical headers it tracks; older entries are dropped
+/// so long runs stay bounded.
+const RETAINED_BLOCKS: usize = 16_384;
 
 /// Broadcasts observed blocks to subscribers while tracking simple stats.
 #[derive(Clone)]
@@ -27,14 +33,127 @@ pub struct BlockFeed {
 
 struct BlockFeedInner {
     sender: broadcast::Sender<Arc<BlockRecord>>,
+    events: broadcast::Sender<BlockFeedEvent>,
+    canonical: RwLock<CanonicalChain>,
+    observations: Mutex<Observations>,
+    tips: RwLock<HashMap<String, NodeTip>>,
     stats: Arc<BlockStats>,
 }
 
+/// Which nodes the block feed scans.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockFeedMode {
+    /// Follow a single node chosen by the deployer.
+    #[default]
+    SingleNode,
+    /// Scan every node concurrently. The first node drives the canonical
+    /// chain and reorg events; every node contributes tips and sightings.
+    AllNodes,
+}
+
 /// Block header + payload snapshot emitted by the feed.
 #[derive(Clone)]
 pub struct BlockRecord {
     pub header: HeaderId,
     pub block: Arc<Block<SignedMantleTx>>,
+    /// Node that observed the block first.
+    pub observer: String,
+    /// When the block was first observed by any node.
+    pub first_seen: Instant,
+}
+
+impl fmt::Debug for BlockRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockRecord")
+            .field("header", &self.header)
+            .field("transactions", &self.block.transactions().len())
+            .field("observer", &self.observer)
+            .field("first_seen", &self.first_seen)
+            .finish()
+    }
+}
+
+/// A node reporting a block, used for propagation measurements.
+#[derive(Clone, Debug)]
+pub struct BlockSighting {
+    pub observer: String,
+    pub observed_at: Instant,
+}
+
+/// Latest consensus tip reported by a node.
+#[derive(Clone, Debug)]
+pub struct NodeTip {
+    pub header: HeaderId,
+    pub height: u64,
+    pub updated_at: Instant,
+    /// Number of branch switches this node went through.
+    pub reorgs: u64,
+}
+
+struct Observation {
+    record: Arc<BlockRecord>,
+    sightings: Vec<BlockSighting>,
+}
+
+/// Observations of the last [`RETAINED_BLOCKS`] blocks, evicted oldest first.
+#[derive(Default)]
+struct Observations {
+    by_header: HashMap<HeaderId, Observation>,
+    order: VecDeque<HeaderId>,
+}
+
+impl Observations {
+    fn get(&self, header: &HeaderId) -> Option<&Observation> {
+        self.by_header.get(header)
+    }
+
+    fn get_mut(&mut self, header: &HeaderId) -> Option<&mut Observation> {
+        self.by_header.get_mut(header)
+    }
+
+    fn insert(&mut self, header: HeaderId, observation: Observation) {
+        self.by_header.insert(header, observation);
+        self.order.push_back(header);
+        while self.order.len() > RETAINED_BLOCKS {
+            if let Some(evicted) = self.order.pop_front() {
+                self.by_header.remove(&evicted);
+            }
+        }
+    }
+}
+
+/// Canonical chain updates emitted by the feed, in order.
+#[derive(Clone, Debug)]
+pub enum BlockFeedEvent {
+    /// A block extending the current canonical tip.
+    Block(Arc<BlockRecord>),
+    /// The tip switched branches. Adopted blocks are only delivered here, not
+    /// as separate `Block` events.
+    Reorg(Arc<Reorg>),
+}
+
+/// Branch switch observed by the feed.
+#[derive(Debug)]
+pub struct Reorg {
+    /// Previously canonical blocks that were orphaned and whose records are
+    /// still retained, oldest first.
+    pub dropped: Vec<Arc<BlockRecord>>,
+    /// Headers of every orphaned block, oldest first.
+    pub dropped_headers: Vec<HeaderId>,
+    /// Blocks of the new branch above the common ancestor, oldest first.
+    pub adopted: Vec<Arc<BlockRecord>>,
+    /// Last block shared by both branches. `None` when the new branch does
+    /// not connect to the tracked chain, in which case every tracked block is
+    /// reported as dropped.
+    pub common_ancestor: Option<HeaderId>,
+}
+
+impl Reorg {
+    /// Number of canonical blocks rolled back by this reorg.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.dropped_headers.len()
+    }
 }
 
 /// Join handle for the background block feed task.
@@ -43,24 +162,271 @@ pub struct BlockFeedTask {
 }
 
 impl BlockFeed {
+    /// Subscribe to every newly observed block, regardless of branch. Each
+    /// block is delivered once, even if it is later orphaned or re-adopted.
     #[must_use]
     pub fn subscribe(&self) -> broadcast::Receiver<Arc<BlockRecord>> {
         self.inner.sender.subscribe()
     }
 
+    /// Subscribe to canonical chain updates, including reorgs.
+    #[must_use]
+    pub fn subscribe_events(&self) -> broadcast::Receiver<BlockFeedEvent> {
+        self.inner.events.subscribe()
+    }
+
     #[must_use]
     pub fn stats(&self) -> Arc<BlockStats> {
         Arc::clone(&self.inner.stats)
     }
 
-    fn ingest(&self, header: HeaderId, block: Block<SignedMantleTx>) {
-        self.inner.stats.record_block(&block);
-        let record = Arc::new(BlockRecord {
-            header,
-            block: Arc::new(block),
-        });
+    /// Header ids of the canonical chain as currently observed, oldest first.
+    /// Only the most recent blocks are tracked; see
+    /// [`Self::pruned_canonical_blocks`].
+    #[must_use]
+    pub fn canonical_chain(&self) -> Vec<HeaderId> {
+        self.canonical().headers.iter().copied().collect()
+    }
+
+    /// Number of canonical blocks below [`Self::canonical_chain`] that the
+    /// feed no longer tracks.
+    #[must_use]
+    pub fn pruned_canonical_blocks(&self) -> usize {
+        self.canonical().offset
+    }
+
+    /// Tip of the canonical chain as currently observed.
+    #[must_use]
+    pub fn canonical_tip(&self) -> Option<HeaderId> {
+        self.canonical().headers.back().copied()
+    }
+
+    /// Whether `header` is part of the observed canonical chain.
+    #[must_use]
+    pub fn is_canonical(&self, header: &HeaderId) -> bool {
+        self.canonical().contains(header)
+    }
+
+    /// Latest tip reported by each scanned node, keyed by node label.
+    #[must_use]
+    pub fn node_tips(&self) -> HashMap<String, NodeTip> {
+        self.inner
+            .tips
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Every node sighting of `header`, in observation order. Only the most
+    /// recently observed blocks are retained.
+    #[must_use]
+    pub fn sightings(&self, header: &HeaderId) -> Vec<BlockSighting> {
+        self.observations()
+            .get(header)
+            .map(|observation| observation.sightings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Time between the first and the last node observing `header`.
+    #[must_use]
+    pub fn propagation_delay(&self, header: &HeaderId) -> Option<Duration> {
+        let sightings = self.sightings(header);
+        let first = sightings.iter().map(|s| s.observed_at).min()?;
+        let last = sightings.iter().map(|s| s.observed_at).max()?;
+        Some(last.duration_since(first))
+    }
+
+    fn observations(&self) -> std::sync::MutexGuard<'_, Observations> {
+        self.inner
+            .observations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Stored record for `header`, if any scanned node has reported it and it
+    /// is still retained.
+    #[must_use]
+    pub fn known_record(&self, header: &HeaderId) -> Option<Arc<BlockRecord>> {
+        self.observations()
+            .get(header)
+            .map(|observation| Arc::clone(&observation.record))
+    }
+
+    /// Register `observer` seeing `record`. The first sighting of a block
+    /// publishes it to subscribers; the stored record is returned so every
+    /// scanner shares the same instance.
+    fn observe(&self, observer: &str, record: Arc<BlockRecord>) -> Arc<BlockRecord> {
+        let sighting = BlockSighting {
+            observer: observer.to_owned(),
+            observed_at: Instant::now(),
+        };
+
+        let mut observations = self.observations();
+        if let Some(observation) = observations.get_mut(&record.header) {
+            if !observation
+                .sightings
+                .iter()
+                .any(|existing| existing.observer == observer)
+            {
+                observation.sightings.push(sighting);
+            }
+            return Arc::clone(&observation.record);
+        }
+
+        observations.insert(
+            record.header,
+            Observation {
+                record: Arc::clone(&record),
+                sightings: vec![sighting],
+            },
+        );
+        drop(observations);
+
+        self.publish(&record);
+        record
+    }
+
+    fn update_tip(&self, observer: &str, header: HeaderId, height: u64, reorged: bool) {
+        let mut tips = self
+            .inner
+            .tips
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let reorgs = tips.get(observer).map_or(0, |tip| tip.reorgs) + u64::from(reorged);
+        tips.insert(
+            observer.to_owned(),
+            NodeTip {
+                header,
+                height,
+                updated_at: Instant::now(),
+                reorgs,
+            },
+        );
+    }
+
+    fn canonical(&self) -> std::sync::RwLockReadGuard<'_, CanonicalChain> {
+        self.inner
+            .canonical
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn publish(&self, record: &Arc<BlockRecord>) {
+        self.inner.stats.record_block(&record.block);
+        let _ = self.inner.sender.send(Arc::clone(record));
+    }
+
+    fn set_root(&self, header: HeaderId) {
+        self.inner
+            .canonical
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .set_root(header);
+    }
+
+    fn advance(&self, ancestor: Option<HeaderId>, adopted: Vec<Arc<BlockRecord>>) {
+        if adopted.is_empty() {
+            return;
+        }
+
+        let (common_ancestor, dropped_headers) = self
+            .inner
+            .canonical
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .switch_to(ancestor, adopted.iter().map(|record| record.header));
+
+        if dropped_headers.is_empty() {
+            for record in adopted {
+                let _ = self.inner.events.send(BlockFeedEvent::Block(record));
+            }
+            return;
+        }
 
-        let _ = self.inner.sender.send(record);
+        let dropped = dropped_headers
+            .iter()
+            .filter_map(|header| self.known_record(header))
+            .collect::<Vec<_>>();
+        self.inner.stats.record_reorg(dropped_headers.len());
+
+        tracing::info!(
+            common_ancestor = ?common_ancestor,
+            depth = dropped_headers.len(),
+            adopted = adopted.len(),
+            "block feed observed reorg"
+        );
+
+        let _ = self
+            .inner
+            .events
+            .send(BlockFeedEvent::Reorg(Arc::new(Reorg {
+                dropped,
+                dropped_headers,
+                adopted,
+                common_ancestor,
+            })));
+    }
+}
+
+/// Last [`RETAINED_BLOCKS`] blocks of the canonical chain as followed by the
+/// scanner, oldest first.
+#[derive(Default)]
+struct CanonicalChain {
+    headers: VecDeque<HeaderId>,
+    /// Position of each tracked header counted from the first block ever
+    /// tracked.
+    positions: HashMap<HeaderId, usize>,
+    /// Position of `headers[0]`, i.e. the number of pruned blocks.
+    offset: usize,
+}
+
+impl CanonicalChain {
+    fn contains(&self, header: &HeaderId) -> bool {
+        self.positions.contains_key(header)
+    }
+
+    fn set_root(&mut self, header: HeaderId) {
+        if self.headers.is_empty() {
+            self.push(header);
+        }
+    }
+
+    fn push(&mut self, header: HeaderId) {
+        self.positions
+            .insert(header, self.offset + self.headers.len());
+        self.headers.push_back(header);
+        while self.headers.len() > RETAINED_BLOCKS {
+            if let Some(pruned) = self.headers.pop_front() {
+                self.positions.remove(&pruned);
+                self.offset += 1;
+            }
+        }
+    }
+
+    /// Roll back to `ancestor` and append `adopted`. Returns the ancestor and
+    /// the rolled back headers, oldest first and empty for a plain extension.
+    /// When the ancestor is not on the tracked chain, tracking restarts from
+    /// the adopted branch: the ancestor is `None` and every previously tracked
+    /// header is rolled back.
+    fn switch_to(
+        &mut self,
+        ancestor: Option<HeaderId>,
+        adopted: impl IntoIterator<Item = HeaderId>,
+    ) -> (Option<HeaderId>, Vec<HeaderId>) {
+        let known = ancestor.and_then(|ancestor| Some((ancestor, *self.positions.get(&ancestor)?)));
+        let (ancestor, keep) = match known {
+            Some((ancestor, position)) => (Some(ancestor), position - self.offset + 1),
+            None => (None, 0),
+        };
+
+        let dropped = self.headers.drain(keep..).collect::<Vec<_>>();
+        for header in &dropped {
+            self.positions.remove(header);
+        }
+        adopted.into_iter().for_each(|header| self.push(header));
+
+        (ancestor, dropped)
     }
 }
 
@@ -72,18 +438,31 @@ impl BlockFeedTask {
     }
 }
 
-/// Spawn a background task to poll blocks from the given client and broadcast
-/// them.
-pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeedTask)> {
+fn new_feed() -> BlockFeed {
     let (sender, _) = broadcast::channel(1024);
-    let feed = BlockFeed {
+    let (events, _) = broadcast::channel(1024);
+    BlockFeed {
         inner: Arc::new(BlockFeedInner {
             sender,
+            events,
+            canonical: RwLock::new(CanonicalChain::default()),
+            observations: Mutex::new(Observations::default()),
+            tips: RwLock::new(HashMap::new()),
             stats: Arc::new(BlockStats::default()),
         }),
-    };
+    }
+}
 
-    let mut scanner = BlockScanner::new(client, feed.clone());
+/// Spawn a background task to poll blocks from the given client and broadcast
+/// them. `index` is the node's position in the topology; the node is labelled
+/// `node-{index}`, as in the multi-node feed.
+pub async fn spawn_block_feed(
+    client: ApiClient,
+    index: usize,
+) -> Result<(BlockFeed, BlockFeedTask)> {
+    let feed = new_feed();
+
+    let mut scanner = BlockScanner::new(client, feed.clone(), node_label(index), true);
     scanner.catch_up().await?;
 
     let handle = tokio::spawn(async move { scanner.run().await });
@@ -91,18 +470,83 @@ pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeed
     Ok((feed, BlockFeedTask::new(handle)))
 }
 
+/// Spawn a background task that scans every node in `node_clients`
+/// concurrently. Nodes added later are picked up on the next poll and are
+/// labelled `node-{index}` by their position in `node_clients`.
+pub async fn spawn_multi_node_block_feed(
+    node_clients: NodeClients,
+) -> Result<(BlockFeed, BlockFeedTask)> {
+    let feed = new_feed();
+    let mut scanners = Vec::new();
+    sync_scanners(&mut scanners, &node_clients, &feed);
+
+    if scanners.is_empty() {
+        return Err(anyhow!("multi-node block feed requires at least one node"));
+    }
+
+    let results = join_all(scanners.iter_mut().map(|scanner| scanner.catch_up())).await;
+    if results.iter().all(Result::is_err) {
+        let mut errors = results.into_iter().filter_map(Result::err);
+        return Err(errors
+            .next()
+            .unwrap_or_else(|| anyhow!("multi-node block feed catch up failed")));
+    }
+
+    let handle = tokio::spawn(async move {
+        loop {
+            sync_scanners(&mut scanners, &node_clients, &feed);
+            let results = join_all(scanners.iter_mut().map(|scanner| scanner.catch_up())).await;
+            for (scanner, result) in scanners.iter().zip(results) {
+                if let Err(err) = result {
+                    debug!(node = %scanner.observer, error = %err, "block feed catch up failed");
+                }
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    });
+
+    Ok((feed, BlockFeedTask::new(handle)))
+}
+
+fn sync_scanners(scanners: &mut Vec<BlockScanner>, node_clients: &NodeClients, feed: &BlockFeed) {
+    for (index, client) in node_clients
+        .node_clients()
+        .into_iter()
+        .enumerate()
+        .skip(scanners.len())
+    {
+        scanners.push(BlockScanner::new(
+            client,
+            feed.clone(),
+            node_label(index),
+            index == 0,
+        ));
+    }
+}
+
+/// Label the feed reports a node under, keyed into [`BlockFeed::node_tips`].
+fn node_label(index: usize) -> String {
+    format!("node-{index}")
+}
+
+/// Follows a single node's chain. Only the primary scanner drives the feed's
+/// canonical chain and events; every scanner reports tips and sightings.
 struct BlockScanner {
     client: ApiClient,
     feed: BlockFeed,
-    seen: HashSet<HeaderId>,
+    observer: String,
+    primary: bool,
+    chain: CanonicalChain,
 }
 
 impl BlockScanner {
-    fn new(client: ApiClient, feed: BlockFeed) -> Self {
+    fn new(client: ApiClient, feed: BlockFeed, observer: String, primary: bool) -> Self {
         Self {
             client,
             feed,
-            seen: HashSet::new(),
+            observer,
+            primary,
+            chain: CanonicalChain::default(),
         }
     }
 
@@ -115,44 +559,48 @@ impl BlockScanner {
         }
     }
 
+    /// Walk back from the tip until reaching a block on this node's chain,
+    /// then adopt the walked branch. Blocks already observed (by this or any
+    /// other scanner) are reused instead of being fetched again.
     async fn catch_up(&mut self) -> Result<()> {
         let info = self.client.consensus_info().await?;
         let tip = info.tip;
         let mut remaining_height = info.height;
-        let mut stack = Vec::new();
+        let mut branch = Vec::new();
+        let mut ancestor = None;
         let mut cursor = tip;
 
         loop {
-            if self.seen.contains(&cursor) {
+            if self.chain.contains(&cursor) {
+                ancestor = Some(cursor);
                 break;
             }
 
             if remaining_height == 0 {
-                self.seen.insert(cursor);
+                self.chain.set_root(cursor);
+                if self.primary {
+                    self.feed.set_root(cursor);
+                }
+                ancestor = Some(cursor);
                 break;
             }
 
-            let block = match self.client.storage_block(&cursor).await {
-                Ok(block) => block,
-                Err(err) => {
-                    if err.is_decode() {
-                        if let Ok(resp) =
-                            self.client.post_json_response(STORAGE_BLOCK, &cursor).await
-                        {
-                            if let Ok(body) = resp.text().await {
-                                error!(header = ?cursor, %body, "failed to decode block response");
-                            }
-                        }
-                    }
-                    return Err(err.into());
+            let record = match self.feed.known_record(&cursor) {
+                Some(record) => record,
+                None => {
+                    let block = self.fetch_block(&cursor).await?;
+                    Arc::new(BlockRecord {
+                        header: cursor,
+                        block: Arc::new(block),
+                        observer: self.observer.clone(),
+                        first_seen: Instant::now(),
+                    })
                 }
-            }
-            .context("missing block while catching up")?;
-
-            let parent = block.header().parent();
-            stack.push((cursor, block));
+            };
+            let parent = record.block.header().parent();
+            branch.push(record);
 
-            if self.seen.contains(&parent) || parent == cursor {
+            if parent == cursor {
                 break;
             }
 
@@ -160,16 +608,51 @@ impl BlockScanner {
             remaining_height = remaining_height.saturating_sub(1);
         }
 
-        let mut processed = 0usize;
-        while let Some((header, block)) = stack.pop() {
-            self.feed.ingest(header, block);
-            self.seen.insert(header);
-            processed += 1;
+        let processed = branch.len();
+        let adopted = branch
+            .into_iter()
+            .rev()
+            .map(|record| self.feed.observe(&self.observer, record))
+            .collect::<Vec<_>>();
+
+        let reorged = !adopted.is_empty()
+            && !self
+                .chain
+                .switch_to(ancestor, adopted.iter().map(|record| record.header))
+                .1
+                .is_empty();
+        self.feed
+            .update_tip(&self.observer, tip, info.height, reorged);
+
+        if self.primary {
+            self.feed.advance(ancestor, adopted);
         }
 
-        debug!(processed, "block feed processed catch up batch");
+        debug!(
+            processed,
+            node = %self.observer,
+            "block feed processed catch up batch"
+        );
         Ok(())
     }
+
+    async fn fetch_block(&self, header: &HeaderId) -> Result<Block<SignedMantleTx>> {
+        let block = match self.client.storage_block(header).await {
+            Ok(block) => block,
+            Err(err) => {
+                if err.is_decode() {
+                    if let Ok(resp) = self.client.post_json_response(STORAGE_BLOCK, header).await {
+                        if let Ok(body) = resp.text().await {
+                            error!(header = ?header, %body, "failed to decode block response");
+                        }
+                    }
+                }
+                return Err(err.into());
+            }
+        };
+
+        block.context("missing block while catching up")
+    }
 }
 
 impl CleanupGuard for BlockFeedTask {
@@ -182,6 +665,8 @@ impl CleanupGuard for BlockFeedTask {
 #[derive(Default)]
 pub struct BlockStats {
     total_transactions: AtomicU64,
+    reorgs: AtomicU64,
+    max_reorg_depth: AtomicU64,
 }
 
 impl BlockStats {
@@ -190,8 +675,136 @@ impl BlockStats {
             .fetch_add(block.transactions().len() as u64, Ordering::Relaxed);
     }
 
+    fn record_reorg(&self, depth: usize) {
+        self.reorgs.fetch_add(1, Ordering::Relaxed);
+        self.max_reorg_depth
+            .fetch_max(depth as u64, Ordering::Relaxed);
+    }
+
     #[must_use]
     pub fn total_transactions(&self) -> u64 {
         self.total_transactions.load(Ordering::Relaxed)
     }
+
+    /// Number of reorgs observed so far.
+    #[must_use]
+    pub fn reorgs(&self) -> u64 {
+        self.reorgs.load(Ordering::Relaxed)
+    }
+
+    /// Deepest reorg observed so far, in dropped blocks.
+    #[must_use]
+    pub fn max_reorg_depth(&self) -> u64 {
+        self.max_reorg_depth.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_node::HeaderId;
+    use reqwest::Url;
+
+    use super::{CanonicalChain, RETAINED_BLOCKS, new_feed, sync_scanners};
+    use crate::{nodes::ApiClient, scenario::NodeClients};
+
+    fn header(byte: u8) -> HeaderId {
+        HeaderId::from([byte; 32])
+    }
+
+    #[test]
+    fn canonical_chain_reports_dropped_blocks_on_branch_switch() {
+        let mut chain = CanonicalChain::default();
+        chain.set_root(header(0));
+
+        let extended = chain.switch_to(Some(header(0)), [header(1), header(2), header(3)]);
+        assert_eq!(extended, (Some(header(0)), Vec::new()));
+
+        let reorg = chain.switch_to(Some(header(1)), [header(4), header(5)]);
+        assert_eq!(reorg, (Some(header(1)), vec![header(2), header(3)]));
+        assert_eq!(
+            chain.headers,
+            vec![header(0), header(1), header(4), header(5)]
+        );
+        assert!(!chain.contains(&header(3)));
+        assert!(chain.contains(&header(5)));
+    }
+
+    #[test]
+    fn canonical_chain_drops_everything_when_the_ancestor_is_unknown() {
+        let mut chain = CanonicalChain::default();
+        chain.set_root(header(0));
+        chain.switch_to(Some(header(0)), [header(1), header(2)]);
+
+        let reset = chain.switch_to(Some(header(9)), [header(7), header(8)]);
+        assert_eq!(reset, (None, vec![header(0), header(1), header(2)]));
+        assert_eq!(chain.headers, vec![header(7), header(8)]);
+        assert!(!chain.contains(&header(0)));
+        assert!(chain.contains(&header(8)));
+    }
+
+    #[test]
+    fn canonical_chain_keeps_a_bounded_window() {
+        let numbered = |n: usize| {
+            let mut bytes = [0xff; 32];
+            bytes[..8].copy_from_slice(&(n as u64).to_le_bytes());
+            HeaderId::from(bytes)
+        };
+        let mut chain = CanonicalChain::default();
+        chain.set_root(numbered(0));
+        chain.switch_to(Some(numbered(0)), (1..RETAINED_BLOCKS + 10).map(numbered));
+
+        assert_eq!(chain.headers.len(), RETAINED_BLOCKS);
+        assert_eq!(chain.positions.len(), RETAINED_BLOCKS);
+        assert_eq!(chain.offset, 10);
+        assert!(!chain.contains(&numbered(9)));
+
+        let tip = RETAINED_BLOCKS + 9;
+        let reorg = chain.switch_to(Some(numbered(tip - 2)), [header(1)]);
+        assert_eq!(
+            reorg,
+            (
+                Some(numbered(tip - 2)),
+                vec![numbered(tip - 1), numbered(tip)]
+            )
+        );
+        assert_eq!(chain.headers.back(), Some(&header(1)));
+    }
+
+    fn client(port: u16) -> ApiClient {
+        let url = Url::parse(&format!("http://127.0.0.1:{port}")).expect("valid url");
+        ApiClient::from_urls(url, None)
+    }
+
+    #[test]
+    fn multi_node_feed_labels_nodes_by_index() {
+        let feed = new_feed();
+        let node_clients = NodeClients::new(vec![client(18080), client(1808)]);
+        let mut scanners = Vec::new();
+        sync_scanners(&mut scanners, &node_clients, &feed);
+
+        node_clients.add_node(client(18081));
+        sync_scanners(&mut scanners, &node_clients, &feed);
+
+        let labels = scanners
+            .iter()
+            .map(|scanner| (scanner.observer.as_str(), scanner.primary))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [("node-0", true), ("node-1", false), ("node-2", false)]
+        );
+
+        for (height, scanner) in scanners.iter().enumerate() {
+            feed.update_tip(
+                &scanner.observer,
+                header(height as u8),
+                height as u64,
+                false,
+            );
+        }
+        let tips = feed.node_tips();
+        assert_eq!(tips.len(), 3);
+        assert_eq!(tips["node-1"].header, header(1));
+        assert_eq!(tips["node-2"].height, 2);
+    }
 }
@@ -0,0 +1,281 @@
+use std::{
+    collections::HashMap,
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+use tokio::{net::UdpSocket, task::JoinHandle};
+use tracing::{debug, info};
+
+const NTP_PACKET_LEN: usize = 48;
+/// Seconds between the NTP epoch (1900) and the Unix epoch.
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+const MODE_CLIENT: u8 = 3;
+const MODE_SERVER: u8 = 4;
+const STRATUM: u8 = 1;
+/// Roughly a microsecond, as a power of two seconds.
+const PRECISION: i8 = -20;
+const REFERENCE_ID: &[u8; 4] = b"LOCL";
+
+/// Offset of one node's clock from the shared framework clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockSkew {
+    Ahead(Duration),
+    Behind(Duration),
+}
+
+impl ClockSkew {
+    fn apply(self, time: SystemTime) -> SystemTime {
+        match self {
+            Self::Ahead(by) => time + by,
+            Self::Behind(by) => time.checked_sub(by).unwrap_or(UNIX_EPOCH),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ClockError {
+    #[error("failed to bind time server for node {node} on port {port}: {source}")]
+    Bind {
+        node: usize,
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+    #[error("node {node} does not sync against the framework clock")]
+    UnknownNode { node: usize },
+}
+
+#[derive(Default)]
+struct ClockState {
+    advanced: Duration,
+    skews: HashMap<usize, ClockSkew>,
+}
+
+impl ClockState {
+    fn time_for(&self, node: usize, wall: SystemTime) -> SystemTime {
+        let shared = wall + self.advanced;
+        match self.skews.get(&node) {
+            Some(skew) => skew.apply(shared),
+            None => shared,
+        }
+    }
+}
+
+struct ClockServers {
+    state: Arc<Mutex<ClockState>>,
+    addrs: HashMap<usize, SocketAddr>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Drop for ClockServers {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Framework time server the nodes of a run sync against over SNTP.
+///
+/// Every node gets its own loopback port, so each can be served a different
+/// time: the wall clock, plus any [`advance`](Self::advance) applied to the
+/// whole cluster, plus the node's own [`ClockSkew`]. Nodes pick changes up on
+/// their next sync. The servers stop once the last handle is dropped.
+#[derive(Clone)]
+pub struct ClockControl {
+    servers: Arc<ClockServers>,
+}
+
+impl ClockControl {
+    /// Starts one server per `(node index, port)` pair on `127.0.0.1`.
+    pub async fn start(ports: impl IntoIterator<Item = (usize, u16)>) -> Result<Self, ClockError> {
+        let state = Arc::new(Mutex::new(ClockState::default()));
+        let mut addrs = HashMap::new();
+        let mut tasks = Vec::new();
+
+        for (node, port) in ports {
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+            let socket = UdpSocket::bind(addr)
+                .await
+                .map_err(|source| ClockError::Bind { node, port, source })?;
+            addrs.insert(node, addr);
+            tasks.push(tokio::spawn(serve(socket, node, Arc::clone(&state))));
+        }
+
+        info!(nodes = addrs.len(), "framework time servers started");
+        Ok(Self {
+            servers: Arc::new(ClockServers {
+                state,
+                addrs,
+                tasks,
+            }),
+        })
+    }
+
+    /// Indices of the nodes served by this clock, in ascending order.
+    #[must_use]
+    pub fn nodes(&self) -> Vec<usize> {
+        let mut nodes = self.servers.addrs.keys().copied().collect::<Vec<_>>();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// Address of the server `node` syncs against.
+    #[must_use]
+    pub fn server_addr(&self, node: usize) -> Option<SocketAddr> {
+        self.servers.addrs.get(&node).copied()
+    }
+
+    /// Replaces the skew of `node`.
+    pub fn skew(&self, node: usize, skew: ClockSkew) -> Result<(), ClockError> {
+        self.ensure_known(node)?;
+        debug!(node, ?skew, "skewing node clock");
+        self.lock().skews.insert(node, skew);
+        Ok(())
+    }
+
+    /// Puts `node` back on the shared clock.
+    pub fn clear_skew(&self, node: usize) -> Result<(), ClockError> {
+        self.ensure_known(node)?;
+        self.lock().skews.remove(&node);
+        Ok(())
+    }
+
+    /// Jumps every node's clock forward by `by`. Jumps accumulate and cannot
+    /// be undone: time never runs backwards for the whole cluster.
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.lock();
+        state.advanced += by;
+        info!(by = ?by, total = ?state.advanced, "advanced framework clock");
+    }
+
+    /// Time currently served to `node`.
+    #[must_use]
+    pub fn now(&self, node: usize) -> Option<SystemTime> {
+        self.servers
+            .addrs
+            .contains_key(&node)
+            .then(|| self.lock().time_for(node, SystemTime::now()))
+    }
+
+    fn ensure_known(&self, node: usize) -> Result<(), ClockError> {
+        if self.servers.addrs.contains_key(&node) {
+            Ok(())
+        } else {
+            Err(ClockError::UnknownNode { node })
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ClockState> {
+        lock_state(&self.servers.state)
+    }
+}
+
+fn lock_state(state: &Mutex<ClockState>) -> MutexGuard<'_, ClockState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn serve(socket: UdpSocket, node: usize, state: Arc<Mutex<ClockState>>) {
+    let mut buf = [0u8; 512];
+    loop {
+        let (len, peer) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(err) => {
+                debug!(node, %err, "time server receive failed");
+                continue;
+            }
+        };
+        let now = lock_state(&state).time_for(node, SystemTime::now());
+        let Some(reply) = ntp_reply(&buf[..len], now) else {
+            debug!(node, %peer, len, "ignoring non-client time request");
+            continue;
+        };
+        if let Err(err) = socket.send_to(&reply, peer).await {
+            debug!(node, %peer, %err, "time server reply failed");
+        }
+    }
+}
+
+/// Server reply to an SNTP client request, stamped with `now`.
+fn ntp_reply(request: &[u8], now: SystemTime) -> Option<[u8; NTP_PACKET_LEN]> {
+    if request.len() < NTP_PACKET_LEN || request[0] & 0x07 != MODE_CLIENT {
+        return None;
+    }
+
+    let version = (request[0] >> 3) & 0x07;
+    let timestamp = ntp_timestamp(now);
+    let mut reply = [0u8; NTP_PACKET_LEN];
+    reply[0] = (version << 3) | MODE_SERVER;
+    reply[1] = STRATUM;
+    reply[2] = request[2];
+    reply[3] = PRECISION as u8;
+    reply[12..16].copy_from_slice(REFERENCE_ID);
+    reply[16..24].copy_from_slice(&timestamp);
+    // The client's transmit time comes back as our originate time.
+    reply[24..32].copy_from_slice(&request[40..48]);
+    reply[32..40].copy_from_slice(&timestamp);
+    reply[40..48].copy_from_slice(&timestamp);
+    Some(reply)
+}
+
+fn ntp_timestamp(time: SystemTime) -> [u8; 8] {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    // NTP seconds wrap every era; truncating to 32 bits is the wire format.
+    let secs = (since_epoch.as_secs() + NTP_UNIX_OFFSET_SECS) as u32;
+    let fraction = ((u64::from(since_epoch.subsec_nanos()) << 32) / 1_000_000_000) as u32;
+
+    let mut out = [0u8; 8];
+    out[..4].copy_from_slice(&secs.to_be_bytes());
+    out[4..].copy_from_slice(&fraction.to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{ClockSkew, ClockState, NTP_PACKET_LEN, ntp_reply};
+
+    #[test]
+    fn reply_echoes_client_transmit_time_and_encodes_now() {
+        let mut request = [0u8; NTP_PACKET_LEN];
+        request[0] = (4 << 3) | 3;
+        request[40..48].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let now = UNIX_EPOCH + Duration::from_millis(1_500);
+        let reply = ntp_reply(&request, now).expect("client request gets a reply");
+
+        assert_eq!(reply[0], (4 << 3) | 4);
+        assert_eq!(&reply[24..32], &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            u32::from_be_bytes(reply[40..44].try_into().unwrap()),
+            2_208_988_801
+        );
+        assert_eq!(
+            u32::from_be_bytes(reply[44..48].try_into().unwrap()),
+            1 << 31
+        );
+
+        request[0] = (4 << 3) | 4;
+        assert!(ntp_reply(&request, now).is_none());
+    }
+
+    #[test]
+    fn skews_apply_on_top_of_shared_advance() {
+        let wall = UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut state = ClockState::default();
+        state.advanced = Duration::from_secs(60);
+        state
+            .skews
+            .insert(1, ClockSkew::Behind(Duration::from_secs(5)));
+
+        assert_eq!(state.time_for(0, wall), wall + Duration::from_secs(60));
+        assert_eq!(state.time_for(1, wall), wall + Duration::from_secs(55));
+    }
+}
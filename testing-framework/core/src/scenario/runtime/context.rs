@@ -1,9 +1,14 @@
 use std::{sync::Arc, time::Duration};
 
-use super::{block_feed::BlockFeed, metrics::Metrics, node_clients::ClusterClient};
+use rand::rngs::StdRng;
+
+use super::{
+    block_feed::BlockFeed, clock::ClockControl, exits::NodeExits, metrics::Metrics,
+    node_clients::ClusterClient, report::ScenarioReport, resources::ResourceUsage,
+};
 use crate::{
-    nodes::ApiClient,
-    scenario::{NodeClients, NodeControlHandle},
+    nodes::{ApiClient, NodeLogs},
+    scenario::{NodeClients, NodeControlHandle, seed::seeded_rng},
     topology::{
         configs::wallet::WalletAccount, deployment::Topology, generation::GeneratedTopology,
     },
@@ -15,9 +20,14 @@ pub struct RunContext {
     cluster: Option<Topology>,
     node_clients: NodeClients,
     metrics: RunMetrics,
+    seed: u64,
     telemetry: Metrics,
     block_feed: BlockFeed,
     node_control: Option<Arc<dyn NodeControlHandle>>,
+    clock: Option<ClockControl>,
+    node_logs: Option<NodeLogs>,
+    node_exits: Option<NodeExits>,
+    resource_usage: Option<ResourceUsage>,
 }
 
 impl RunContext {
@@ -30,6 +40,7 @@ impl RunContext {
         cluster: Option<Topology>,
         node_clients: NodeClients,
         run_duration: Duration,
+        seed: u64,
         telemetry: Metrics,
         block_feed: BlockFeed,
         node_control: Option<Arc<dyn NodeControlHandle>>,
@@ -41,12 +52,47 @@ impl RunContext {
             cluster,
             node_clients,
             metrics,
+            seed,
             telemetry,
             block_feed,
             node_control,
+            clock: None,
+            node_logs: None,
+            node_exits: None,
+            resource_usage: None,
         }
     }
 
+    #[must_use]
+    /// Attach the framework clock the nodes sync against.
+    pub fn with_clock(mut self, clock: ClockControl) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    #[must_use]
+    /// Attach the captured node output, so failures can include its tail.
+    pub fn with_node_logs(mut self, logs: NodeLogs) -> Self {
+        self.node_logs = Some(logs);
+        self
+    }
+
+    #[must_use]
+    /// Attach the exit log the deployer's watcher records into; the runner
+    /// fails the scenario on the first unexpected exit.
+    pub fn with_node_exits(mut self, exits: NodeExits) -> Self {
+        self.node_exits = Some(exits);
+        self
+    }
+
+    #[must_use]
+    /// Attach the per-node resource series the deployer's sampler records
+    /// into.
+    pub fn with_resource_usage(mut self, usage: ResourceUsage) -> Self {
+        self.resource_usage = Some(usage);
+        self
+    }
+
     #[must_use]
     pub const fn descriptors(&self) -> &GeneratedTopology {
         &self.descriptors
@@ -72,6 +118,21 @@ impl RunContext {
         self.block_feed.clone()
     }
 
+    #[must_use]
+    /// Scenario seed this run was started with.
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[must_use]
+    /// Deterministic RNG for a named stream, derived from the scenario seed.
+    ///
+    /// Use one stream per consumer (typically the workload name) so replaying
+    /// a seed reproduces that consumer's choices regardless of scheduling.
+    pub fn seeded_rng(&self, stream: &str) -> StdRng {
+        seeded_rng(self.seed, stream)
+    }
+
     #[must_use]
     pub fn wallet_accounts(&self) -> &[WalletAccount] {
         self.descriptors.wallet_accounts()
@@ -102,6 +163,32 @@ impl RunContext {
         self.node_control.clone()
     }
 
+    #[must_use]
+    /// Framework clock for skewing or advancing node time; `None` unless the
+    /// scenario enabled framework time and the deployer supports it.
+    pub const fn clock(&self) -> Option<&ClockControl> {
+        self.clock.as_ref()
+    }
+
+    #[must_use]
+    /// Captured node output; `None` when the deployer does not capture it.
+    pub const fn node_logs(&self) -> Option<&NodeLogs> {
+        self.node_logs.as_ref()
+    }
+
+    #[must_use]
+    /// Unexpected node exits; `None` when the deployer does not watch nodes.
+    pub const fn node_exits(&self) -> Option<&NodeExits> {
+        self.node_exits.as_ref()
+    }
+
+    #[must_use]
+    /// Sampled CPU, memory, thread, fd and disk IO usage per node; `None`
+    /// when the deployer does not sample its nodes.
+    pub const fn resource_usage(&self) -> Option<&ResourceUsage> {
+        self.resource_usage.as_ref()
+    }
+
     #[must_use]
     pub const fn cluster_client(&self) -> ClusterClient<'_> {
         self.node_clients.cluster_client()
@@ -112,6 +199,7 @@ impl RunContext {
 pub struct RunHandle {
     run_context: Arc<RunContext>,
     cleanup_guard: Option<Box<dyn CleanupGuard>>,
+    report: Option<ScenarioReport>,
 }
 
 impl Drop for RunHandle {
@@ -129,6 +217,7 @@ impl RunHandle {
         Self {
             run_context: Arc::new(context),
             cleanup_guard,
+            report: None,
         }
     }
 
@@ -141,14 +230,27 @@ impl RunHandle {
         Self {
             run_context: context,
             cleanup_guard,
+            report: None,
         }
     }
 
+    #[must_use]
+    pub(crate) fn with_report(mut self, report: ScenarioReport) -> Self {
+        self.report = Some(report);
+        self
+    }
+
     #[must_use]
     /// Access the shared run context.
     pub fn context(&self) -> &RunContext {
         &self.run_context
     }
+
+    #[must_use]
+    /// Structured report of the run that produced this handle.
+    pub const fn report(&self) -> Option<&ScenarioReport> {
+        self.report.as_ref()
+    }
 }
 
 /// Derived metrics about the current run timing.
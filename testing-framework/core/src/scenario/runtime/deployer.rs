@@ -1,7 +1,15 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
-use super::runner::Runner;
-use crate::scenario::{DynError, Scenario};
+use super::{
+    exits::{NodeExit, describe_exits},
+    runner::Runner,
+};
+use crate::{
+    nodes::{AttachNodeLogs, NodeLogTails},
+    scenario::{DynError, Scenario},
+};
 
 /// Error returned when executing workloads or expectations.
 #[derive(Debug, thiserror::Error)]
@@ -12,6 +20,45 @@ pub enum ScenarioError {
     ExpectationCapture(#[source] DynError),
     #[error("expectations failed:\n{0}")]
     Expectations(#[source] DynError),
+    #[error("invariant '{expectation}' violated after {elapsed:?}: {source}")]
+    Invariant {
+        expectation: String,
+        /// Time since the workload phase started.
+        elapsed: Duration,
+        #[source]
+        source: DynError,
+    },
+    #[error("{} node(s) exited unexpectedly:\n{}", .exits.len(), describe_exits(.exits))]
+    NodeExited { exits: Vec<NodeExit> },
+    #[error("{source}\n{logs}")]
+    WithNodeLogs {
+        #[source]
+        source: Box<ScenarioError>,
+        logs: NodeLogTails,
+    },
+}
+
+impl AttachNodeLogs for ScenarioError {
+    fn wrap_node_logs(source: Self, logs: NodeLogTails) -> Self {
+        Self::WithNodeLogs {
+            source: Box::new(source),
+            logs,
+        }
+    }
+
+    fn into_parts(self) -> (Self, Option<NodeLogTails>) {
+        match self {
+            Self::WithNodeLogs { source, logs } => (*source, Some(logs)),
+            error => (error, None),
+        }
+    }
+
+    fn node_logs(&self) -> Option<&NodeLogTails> {
+        match self {
+            Self::WithNodeLogs { logs, .. } => Some(logs),
+            _ => None,
+        }
+    }
 }
 
 /// Deploys a scenario into a target environment and returns a `Runner`.
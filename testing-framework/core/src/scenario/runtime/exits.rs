@@ -0,0 +1,168 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    future::Future,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use tokio::{sync::watch, task::JoinHandle, time::sleep};
+use tracing::warn;
+
+use super::context::CleanupGuard;
+
+/// A node process or container that stopped without being asked to.
+#[derive(Clone, Debug)]
+pub struct NodeExit {
+    pub node: String,
+    /// What exited, e.g. `pid 4242` or a container id. Together with `node`
+    /// it identifies the exit, so a watcher reports each one once.
+    pub instance: String,
+    /// When the watcher noticed the exit.
+    pub observed_at: SystemTime,
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    /// Last output lines of the node, oldest first.
+    pub log_tail: Vec<String>,
+}
+
+impl fmt::Display for NodeExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.node, self.instance)?;
+        match (self.code, self.signal) {
+            (_, Some(signal)) => write!(f, " was killed by signal {signal}")?,
+            (Some(code), None) => write!(f, " exited with code {code}")?,
+            (None, None) => write!(f, " exited")?,
+        }
+        if let Ok(since_epoch) = self.observed_at.duration_since(SystemTime::UNIX_EPOCH) {
+            write!(f, " (observed at unix time {})", since_epoch.as_secs())?;
+        }
+        for line in &self.log_tail {
+            write!(f, "\n  {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Unexpected node exits seen during a run; clones share the same log.
+///
+/// Deployers record exits from their watchers and the runner fails the
+/// scenario as soon as one is recorded.
+#[derive(Clone, Debug)]
+pub struct NodeExits {
+    sender: Arc<watch::Sender<Vec<NodeExit>>>,
+}
+
+impl Default for NodeExits {
+    fn default() -> Self {
+        Self {
+            sender: Arc::new(watch::Sender::new(Vec::new())),
+        }
+    }
+}
+
+impl NodeExits {
+    pub fn record(&self, exit: NodeExit) {
+        warn!(node = %exit.node, instance = %exit.instance, code = ?exit.code, signal = ?exit.signal, "node exited unexpectedly");
+        self.sender.send_modify(|exits| exits.push(exit));
+    }
+
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<NodeExit> {
+        self.sender.borrow().clone()
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Vec<NodeExit>> {
+        self.sender.subscribe()
+    }
+}
+
+/// Join handle for a background exit watcher; aborting it stops the watch.
+pub struct ExitWatchTask {
+    handle: JoinHandle<()>,
+}
+
+impl Drop for ExitWatchTask {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl CleanupGuard for ExitWatchTask {
+    fn cleanup(self: Box<Self>) {
+        self.handle.abort();
+    }
+}
+
+/// Call `poll` every `interval` and record each exit it reports that was not
+/// reported before.
+pub fn spawn_exit_watcher<F, Fut>(
+    exits: NodeExits,
+    interval: Duration,
+    mut poll: F,
+) -> ExitWatchTask
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Vec<NodeExit>> + Send + 'static,
+{
+    let handle = tokio::spawn(async move {
+        let mut seen = HashSet::new();
+        loop {
+            sleep(interval).await;
+            for exit in poll().await {
+                if seen.insert((exit.node.clone(), exit.instance.clone())) {
+                    exits.record(exit);
+                }
+            }
+        }
+    });
+
+    ExitWatchTask { handle }
+}
+
+/// Summary of exits for error messages, one paragraph per exit.
+#[must_use]
+pub fn describe_exits(exits: &[NodeExit]) -> String {
+    exits
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use tokio::time::sleep;
+
+    use super::{NodeExit, NodeExits, spawn_exit_watcher};
+
+    #[tokio::test]
+    async fn watcher_records_each_exit_once() {
+        let exits = NodeExits::default();
+        let mut updates = exits.subscribe();
+        let _watcher = spawn_exit_watcher(exits.clone(), Duration::from_millis(5), || async {
+            vec![NodeExit {
+                node: "node-0".to_owned(),
+                instance: "pid 42".to_owned(),
+                observed_at: SystemTime::now(),
+                code: Some(1),
+                signal: None,
+                log_tail: Vec::new(),
+            }]
+        });
+
+        updates.changed().await.expect("watcher records the exit");
+        sleep(Duration::from_millis(50)).await;
+
+        let recorded = exits.snapshot();
+        assert_eq!(recorded.len(), 1);
+        assert!(
+            recorded[0]
+                .to_string()
+                .starts_with("node-0 (pid 42) exited with code 1")
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fmt::Write as _, time::Instant};
+
+use tracing::{debug, warn};
+
+use super::{
+    context::RunContext,
+    deployer::ScenarioError,
+    report::{ExpectationPhase, ScenarioReport, ViolationReport},
+};
+use crate::scenario::Expectation;
+
+/// Polls expectations that opted into continuous checking via
+/// [`Expectation::check_interval`] while workloads run.
+pub(super) struct InvariantMonitor<'a> {
+    expectations: &'a mut [Box<dyn Expectation>],
+    next_due: Vec<Option<Instant>>,
+    started: Instant,
+}
+
+impl<'a> InvariantMonitor<'a> {
+    pub(super) fn new(expectations: &'a mut [Box<dyn Expectation>]) -> Self {
+        let started = Instant::now();
+        let next_due = expectations
+            .iter()
+            .map(|expectation| {
+                expectation
+                    .check_interval()
+                    .filter(|interval| !interval.is_zero())
+                    .map(|interval| started + interval)
+            })
+            .collect();
+
+        Self {
+            expectations,
+            next_due,
+            started,
+        }
+    }
+
+    /// Earliest instant at which some invariant is due, if any are scheduled.
+    pub(super) fn next_due(&self) -> Option<Instant> {
+        self.next_due.iter().flatten().min().copied()
+    }
+
+    /// Runs every invariant whose interval has elapsed.
+    ///
+    /// Violations are recorded in the report. A violation of a fail-fast
+    /// expectation is returned as [`ScenarioError::Invariant`]; the others
+    /// are left for the evaluation phase to turn into a failure.
+    pub(super) async fn check_due(
+        &mut self,
+        context: &RunContext,
+        report: &mut ScenarioReport,
+    ) -> Result<(), ScenarioError> {
+        let now = Instant::now();
+        for (index, expectation) in self.expectations.iter_mut().enumerate() {
+            let Some(due) = self.next_due[index] else {
+                continue;
+            };
+            if due > now {
+                continue;
+            }
+
+            let result = expectation.check(context).await;
+            self.next_due[index] = expectation
+                .check_interval()
+                .filter(|interval| !interval.is_zero())
+                .map(|interval| Instant::now() + interval);
+
+            let Err(source) = result else {
+                debug!(expectation = expectation.name(), "invariant holds");
+                continue;
+            };
+
+            let elapsed = self.started.elapsed();
+            warn!(
+                expectation = expectation.name(),
+                elapsed = ?elapsed,
+                error = %source,
+                "invariant violated"
+            );
+            report.invariant_violated(index, elapsed, &source);
+
+            if expectation.fail_fast() {
+                report.expectation_failed(index, ExpectationPhase::Invariant, &source);
+                return Err(ScenarioError::Invariant {
+                    expectation: expectation.name().to_owned(),
+                    elapsed,
+                    source,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Summarises non-fail-fast violations into a single evaluation failure.
+pub(super) fn violation_summary(violations: &[ViolationReport]) -> Option<String> {
+    let (first, rest) = violations.split_first()?;
+    let mut summary = format!(
+        "invariant violated {} time(s), first after {:.1}s: {}",
+        violations.len(),
+        first.elapsed.as_secs_f64(),
+        first.error
+    );
+    if let Some(last) = rest.last() {
+        let _ = write!(
+            summary,
+            "; last after {:.1}s: {}",
+            last.elapsed.as_secs_f64(),
+            last.error
+        );
+    }
+    Some(summary)
+}
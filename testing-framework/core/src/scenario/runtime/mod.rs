@@ -1,12 +1,28 @@
 mod block_feed;
+mod clock;
 pub mod context;
 mod deployer;
+mod exits;
+mod invariants;
 pub mod metrics;
 mod node_clients;
+mod report;
+mod resources;
 mod runner;
 
-pub use block_feed::{BlockFeed, BlockFeedTask, BlockRecord, BlockStats, spawn_block_feed};
+pub use block_feed::{
+    BlockFeed, BlockFeedEvent, BlockFeedMode, BlockFeedTask, BlockRecord, BlockSighting,
+    BlockStats, NodeTip, Reorg, spawn_block_feed, spawn_multi_node_block_feed,
+};
+pub use clock::{ClockControl, ClockError, ClockSkew};
 pub use context::{CleanupGuard, RunContext, RunHandle, RunMetrics};
 pub use deployer::{Deployer, ScenarioError};
+pub use exits::{ExitWatchTask, NodeExit, NodeExits, describe_exits, spawn_exit_watcher};
 pub use node_clients::NodeClients;
+pub use report::{
+    BlockSummary, ExpectationPhase, ExpectationReport, ExpectationStatus, NodeReport, PhaseTimings,
+    REPORT_JSON_FILE, REPORT_JUNIT_FILE, RunOutcome, ScenarioReport, ViolationReport,
+    WorkloadReport, WorkloadStatus,
+};
+pub use resources::{ResourceSample, ResourceSamplerTask, ResourceUsage, spawn_resource_sampler};
 pub use runner::Runner;
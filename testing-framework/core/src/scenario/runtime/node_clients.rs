@@ -1,13 +1,15 @@
 use std::{
     pin::Pin,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
-use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
+use futures::future::join_all;
+use rand::{Rng as _, SeedableRng as _, rngs::StdRng, seq::SliceRandom as _};
+use tracing::debug;
 
 use crate::{
     nodes::ApiClient,
-    scenario::DynError,
+    scenario::{DynError, seed::derive_seed},
     topology::{deployment::Topology, generation::GeneratedTopology},
 };
 
@@ -17,9 +19,26 @@ pub struct NodeClients {
     inner: Arc<RwLock<NodeClientsInner>>,
 }
 
-#[derive(Default)]
 struct NodeClientsInner {
     nodes: Vec<ApiClient>,
+    rng: Mutex<StdRng>,
+}
+
+impl Default for NodeClientsInner {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            rng: Mutex::new(StdRng::from_entropy()),
+        }
+    }
+}
+
+impl NodeClientsInner {
+    fn rng(&self) -> std::sync::MutexGuard<'_, StdRng> {
+        self.rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 }
 
 impl NodeClients {
@@ -27,7 +46,10 @@ impl NodeClients {
     /// Build clients from preconstructed vectors.
     pub fn new(nodes: Vec<ApiClient>) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(NodeClientsInner { nodes })),
+            inner: Arc::new(RwLock::new(NodeClientsInner {
+                nodes,
+                ..NodeClientsInner::default()
+            })),
         }
     }
 
@@ -52,16 +74,54 @@ impl NodeClients {
             .clone()
     }
 
+    /// Reseed the picker used by `random_node`, `any_client` and
+    /// `shuffled_clients` from the scenario seed. Deployers call this as soon
+    /// as the clients exist, before anything picks a node.
+    ///
+    /// The picker is shared by every clone, so once workloads run, which node
+    /// a call gets depends on how concurrent tasks interleave. Consumers that
+    /// must replay their choices draw from `RunContext::seeded_rng` instead.
+    pub fn reseed(&self, scenario_seed: u64) {
+        let guard = self
+            .inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        *guard.rng() = StdRng::seed_from_u64(derive_seed(scenario_seed, "node_clients"));
+    }
+
     #[must_use]
     /// Choose a random node client if present.
     pub fn random_node(&self) -> Option<ApiClient> {
-        let nodes = self.node_clients();
-        if nodes.is_empty() {
+        self.random_indexed_node().map(|(_, client)| client)
+    }
+
+    #[must_use]
+    /// Choose a random node client and its index in the node list.
+    pub fn random_indexed_node(&self) -> Option<(usize, ApiClient)> {
+        let guard = self
+            .inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if guard.nodes.is_empty() {
             return None;
         }
-        let mut rng = thread_rng();
-        let idx = rng.gen_range(0..nodes.len());
-        nodes.get(idx).cloned()
+        let idx = guard.rng().gen_range(0..guard.nodes.len());
+        guard.nodes.get(idx).cloned().map(|client| (idx, client))
+    }
+
+    #[must_use]
+    /// All clients in a random order drawn from the seeded picker.
+    pub fn shuffled_clients(&self) -> Vec<ApiClient> {
+        let guard = self
+            .inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut clients = guard.nodes.clone();
+        clients.shuffle(&mut *guard.rng());
+        clients
     }
 
     /// Iterator over all clients.
@@ -86,8 +146,7 @@ impl NodeClients {
         if total == 0 {
             return None;
         }
-        let mut rng = thread_rng();
-        let choice = rng.gen_range(0..total);
+        let choice = guard.rng().gen_range(0..total);
         guard.nodes.get(choice).cloned()
     }
 
@@ -138,13 +197,11 @@ impl<'a> ClusterClient<'a> {
     where
         E: Into<DynError>,
     {
-        let mut clients = self.node_clients.all_clients();
+        let clients = self.node_clients.shuffled_clients();
         if clients.is_empty() {
             return Err("cluster client has no api clients".into());
         }
 
-        clients.shuffle(&mut thread_rng());
-
         let mut last_err = None;
         for client in &clients {
             match f(client).await {
@@ -155,4 +212,21 @@ impl<'a> ClusterClient<'a> {
 
         Err(last_err.unwrap_or_else(|| "cluster client exhausted all nodes".into()))
     }
+
+    /// Mantle mempool depth of every node, labelled `node-{idx}`. Nodes that
+    /// cannot be queried report `None`.
+    pub async fn mempool_depths(&self) -> Vec<(String, Option<usize>)> {
+        let clients = self.node_clients.all_clients();
+        let depths = join_all(clients.iter().map(ApiClient::mempool_pending_count)).await;
+        depths
+            .into_iter()
+            .enumerate()
+            .map(|(idx, depth)| {
+                let depth = depth
+                    .inspect_err(|err| debug!(node = idx, %err, "mempool metrics request failed"))
+                    .ok();
+                (format!("node-{idx}"), depth)
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,601 @@
+use std::{
+    error::Error as StdError,
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lb_node::HeaderId;
+use serde::{Serialize, Serializer};
+
+use super::context::RunContext;
+use crate::scenario::DynError;
+
+/// File name used when the runner writes the JSON report automatically.
+pub const REPORT_JSON_FILE: &str = "scenario-report.json";
+/// File name used when the runner writes the JUnit report automatically.
+pub const REPORT_JUNIT_FILE: &str = "scenario-report.xml";
+
+const JUNIT_SUITE_NAME: &str = "scenario";
+
+/// Machine-readable summary of a scenario run.
+///
+/// Returned alongside the run result by
+/// [`Runner::run_with_report`](super::Runner::run_with_report) and available
+/// from [`RunHandle::report`](super::RunHandle::report) after a successful run.
+#[derive(Clone, Debug, Serialize)]
+pub struct ScenarioReport {
+    pub outcome: RunOutcome,
+    /// Error that ended the run, if any.
+    pub error: Option<String>,
+    /// Scenario seed; set `LOGOS_BLOCKCHAIN_SCENARIO_SEED` to replay the run.
+    pub seed: u64,
+    /// Wall-clock start of the run, in seconds since the Unix epoch.
+    #[serde(serialize_with = "serialize_secs")]
+    pub started_at: Duration,
+    /// Requested run duration.
+    #[serde(serialize_with = "serialize_secs")]
+    pub run_duration: Duration,
+    pub phases: PhaseTimings,
+    pub workloads: Vec<WorkloadReport>,
+    pub expectations: Vec<ExpectationReport>,
+    pub blocks: BlockSummary,
+    pub nodes: Vec<NodeReport>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Passed,
+    Failed,
+}
+
+/// Time spent in each runner phase.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct PhaseTimings {
+    #[serde(serialize_with = "serialize_secs")]
+    pub capture: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub workloads: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub cooldown: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub settle: Duration,
+    #[serde(serialize_with = "serialize_secs")]
+    pub evaluate: Duration,
+}
+
+impl PhaseTimings {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.capture + self.workloads + self.cooldown + self.settle + self.evaluate
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct WorkloadReport {
+    pub name: String,
+    pub status: WorkloadStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkloadStatus {
+    /// The run ended before the workload was spawned.
+    NotStarted,
+    /// `start` returned `Ok` before the run window closed.
+    Completed,
+    /// `start` returned an error or panicked.
+    Failed,
+    /// Still running when the runner aborted it.
+    Stopped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExpectationReport {
+    pub name: String,
+    pub status: ExpectationStatus,
+    /// Phase the failure came from (`capture`, `invariant` or `evaluate`).
+    pub phase: Option<ExpectationPhase>,
+    pub error: Option<String>,
+    /// Source chain of the failure, outermost first, excluding `error`.
+    pub causes: Vec<String>,
+    /// Failed continuous checks, in the order they happened.
+    pub violations: Vec<ViolationReport>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ViolationReport {
+    /// Time since the workload phase started.
+    #[serde(serialize_with = "serialize_secs")]
+    pub elapsed: Duration,
+    pub error: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectationStatus {
+    Passed,
+    Failed,
+    /// The run failed before the expectation was evaluated.
+    Skipped,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectationPhase {
+    Capture,
+    Invariant,
+    Evaluate,
+}
+
+/// Block feed counters at the end of the run.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BlockSummary {
+    pub total_transactions: u64,
+    pub reorgs: u64,
+    pub max_reorg_depth: u64,
+    /// Number of blocks on the canonical chain tracked by the feed.
+    pub canonical_length: usize,
+    pub canonical_tip: Option<HeaderId>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct NodeReport {
+    pub name: String,
+    pub api_url: Option<String>,
+    pub api_port: u16,
+    pub testing_http_port: u16,
+    pub network_port: u16,
+    /// Last tip seen by the block feed, when this node was scanned.
+    pub tip: Option<HeaderId>,
+    pub height: Option<u64>,
+}
+
+impl ScenarioReport {
+    pub(crate) fn new(
+        seed: u64,
+        run_duration: Duration,
+        workloads: impl IntoIterator<Item = String>,
+        expectations: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            outcome: RunOutcome::Failed,
+            error: None,
+            seed,
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+            run_duration,
+            phases: PhaseTimings::default(),
+            workloads: workloads
+                .into_iter()
+                .map(|name| WorkloadReport {
+                    name,
+                    status: WorkloadStatus::NotStarted,
+                    error: None,
+                })
+                .collect(),
+            expectations: expectations
+                .into_iter()
+                .map(|name| ExpectationReport {
+                    name,
+                    status: ExpectationStatus::Skipped,
+                    phase: None,
+                    error: None,
+                    causes: Vec::new(),
+                    violations: Vec::new(),
+                })
+                .collect(),
+            blocks: BlockSummary::default(),
+            nodes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn set_workload(
+        &mut self,
+        index: usize,
+        status: WorkloadStatus,
+        error: Option<String>,
+    ) {
+        if let Some(workload) = self.workloads.get_mut(index) {
+            workload.status = status;
+            workload.error = error;
+        }
+    }
+
+    pub(crate) fn expectation_passed(&mut self, index: usize) {
+        if let Some(expectation) = self.expectations.get_mut(index) {
+            expectation.status = ExpectationStatus::Passed;
+        }
+    }
+
+    pub(crate) fn expectation_failed(
+        &mut self,
+        index: usize,
+        phase: ExpectationPhase,
+        error: &DynError,
+    ) {
+        if let Some(expectation) = self.expectations.get_mut(index) {
+            expectation.status = ExpectationStatus::Failed;
+            expectation.phase = Some(phase);
+            expectation.error = Some(error.to_string());
+            expectation.causes = error_causes(error.as_ref());
+        }
+    }
+
+    pub(crate) fn invariant_violated(&mut self, index: usize, elapsed: Duration, error: &DynError) {
+        if let Some(expectation) = self.expectations.get_mut(index) {
+            expectation.violations.push(ViolationReport {
+                elapsed,
+                error: error.to_string(),
+            });
+        }
+    }
+
+    pub(crate) fn violations(&self, index: usize) -> &[ViolationReport] {
+        self.expectations
+            .get(index)
+            .map_or(&[], |expectation| expectation.violations.as_slice())
+    }
+
+    /// Fills in the outcome and the end-of-run snapshot of blocks and nodes.
+    pub(crate) fn finish(&mut self, context: &RunContext, error: Option<&dyn StdError>) {
+        self.outcome = if error.is_none() {
+            RunOutcome::Passed
+        } else {
+            RunOutcome::Failed
+        };
+        self.error = error.map(ToString::to_string);
+
+        let feed = context.block_feed();
+        let stats = feed.stats();
+        let canonical = feed.canonical_chain();
+        self.blocks = BlockSummary {
+            total_transactions: stats.total_transactions(),
+            reorgs: stats.reorgs(),
+            max_reorg_depth: stats.max_reorg_depth(),
+            canonical_length: feed.pruned_canonical_blocks() + canonical.len(),
+            canonical_tip: canonical.last().copied(),
+        };
+
+        let tips = feed.node_tips();
+        let clients = context.node_clients().node_clients();
+        self.nodes = context
+            .descriptors()
+            .nodes()
+            .iter()
+            .map(|node| {
+                let name = format!("node-{}", node.index());
+                let api_url = clients
+                    .get(node.index())
+                    .map(|client| client.base_url.to_string());
+                let tip = tips.get(&name);
+                NodeReport {
+                    api_url,
+                    api_port: node.api_port(),
+                    testing_http_port: node.testing_http_port(),
+                    network_port: node.network_port(),
+                    tip: tip.map(|tip| tip.header),
+                    height: tip.map(|tip| tip.height),
+                    name,
+                }
+            })
+            .collect();
+    }
+
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.outcome == RunOutcome::Passed
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the report as a JUnit XML document.
+    ///
+    /// Workloads and expectations become test cases in a single suite, so CI
+    /// systems that understand JUnit can show them individually.
+    #[must_use]
+    pub fn to_junit_xml(&self) -> String {
+        let failures = self
+            .workloads
+            .iter()
+            .filter(|workload| workload.status == WorkloadStatus::Failed)
+            .count()
+            + self
+                .expectations
+                .iter()
+                .filter(|expectation| expectation.status == ExpectationStatus::Failed)
+                .count();
+        let skipped = self
+            .workloads
+            .iter()
+            .filter(|workload| workload.status == WorkloadStatus::NotStarted)
+            .count()
+            + self
+                .expectations
+                .iter()
+                .filter(|expectation| expectation.status == ExpectationStatus::Skipped)
+                .count();
+        let tests = self.workloads.len() + self.expectations.len();
+        let total = self.phases.total().as_secs_f64();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" \
+             time=\"{total:.3}\">"
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{JUNIT_SUITE_NAME}\" tests=\"{tests}\" failures=\"{failures}\" \
+             skipped=\"{skipped}\" time=\"{total:.3}\">"
+        );
+
+        xml.push_str("    <properties>\n");
+        for (name, value) in self.properties() {
+            let _ = writeln!(
+                xml,
+                "      <property name=\"{}\" value=\"{}\"/>",
+                escape_xml(&name),
+                escape_xml(&value)
+            );
+        }
+        xml.push_str("    </properties>\n");
+
+        let workload_time = (self.phases.workloads + self.phases.cooldown).as_secs_f64();
+        for workload in &self.workloads {
+            let body = match workload.status {
+                WorkloadStatus::Failed => Some(failure_element(
+                    "workload failed",
+                    workload.error.as_deref().unwrap_or_default(),
+                    &[],
+                )),
+                WorkloadStatus::NotStarted => Some("      <skipped/>\n".to_owned()),
+                WorkloadStatus::Completed | WorkloadStatus::Stopped => None,
+            };
+            push_testcase(&mut xml, "workload", &workload.name, workload_time, body);
+        }
+
+        let evaluate_time = self.phases.evaluate.as_secs_f64();
+        for expectation in &self.expectations {
+            let body = match expectation.status {
+                ExpectationStatus::Failed => {
+                    let phase = match expectation.phase {
+                        Some(ExpectationPhase::Capture) => "expectation capture failed",
+                        Some(ExpectationPhase::Invariant) => "invariant violated",
+                        _ => "expectation failed",
+                    };
+                    Some(failure_element(
+                        phase,
+                        expectation.error.as_deref().unwrap_or_default(),
+                        &expectation.causes,
+                    ))
+                }
+                ExpectationStatus::Skipped => Some("      <skipped/>\n".to_owned()),
+                ExpectationStatus::Passed => None,
+            };
+            push_testcase(
+                &mut xml,
+                "expectation",
+                &expectation.name,
+                evaluate_time,
+                body,
+            );
+        }
+
+        if let Some(error) = &self.error {
+            let _ = writeln!(xml, "    <system-err>{}</system-err>", escape_xml(error));
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    /// Writes the JSON report to `path`.
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        let json = self.to_json().map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Writes the JUnit XML report to `path`.
+    pub fn write_junit(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_junit_xml())
+    }
+
+    /// Writes both reports into `dir` using the default file names.
+    pub fn write_to_dir(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        self.write_json(&dir.join(REPORT_JSON_FILE))?;
+        self.write_junit(&dir.join(REPORT_JUNIT_FILE))
+    }
+
+    fn properties(&self) -> Vec<(String, String)> {
+        let mut properties = vec![
+            ("seed".to_owned(), self.seed.to_string()),
+            (
+                "run_duration_secs".to_owned(),
+                self.run_duration.as_secs_f64().to_string(),
+            ),
+            (
+                "total_transactions".to_owned(),
+                self.blocks.total_transactions.to_string(),
+            ),
+            ("reorgs".to_owned(), self.blocks.reorgs.to_string()),
+            (
+                "max_reorg_depth".to_owned(),
+                self.blocks.max_reorg_depth.to_string(),
+            ),
+            (
+                "canonical_length".to_owned(),
+                self.blocks.canonical_length.to_string(),
+            ),
+        ];
+        for node in &self.nodes {
+            if let Some(url) = &node.api_url {
+                properties.push((format!("{}.api_url", node.name), url.clone()));
+            }
+            if let Some(height) = node.height {
+                properties.push((format!("{}.height", node.name), height.to_string()));
+            }
+        }
+        properties
+    }
+}
+
+fn push_testcase(xml: &mut String, class: &str, name: &str, time: f64, body: Option<String>) {
+    let _ = write!(
+        xml,
+        "    <testcase classname=\"{JUNIT_SUITE_NAME}.{class}\" name=\"{}\" time=\"{time:.3}\"",
+        escape_xml(name)
+    );
+    match body {
+        Some(body) => {
+            xml.push_str(">\n");
+            xml.push_str(&body);
+            xml.push_str("    </testcase>\n");
+        }
+        None => xml.push_str("/>\n"),
+    }
+}
+
+fn failure_element(kind: &str, message: &str, causes: &[String]) -> String {
+    let mut details = message.to_owned();
+    for cause in causes {
+        let _ = write!(details, "\ncaused by: {cause}");
+    }
+    format!(
+        "      <failure type=\"{kind}\" message=\"{}\">{}</failure>\n",
+        escape_xml(message.lines().next().unwrap_or_default()),
+        escape_xml(&details)
+    )
+}
+
+fn error_causes(error: &(dyn StdError + 'static)) -> Vec<String> {
+    let mut causes = Vec::new();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        causes.push(cause.to_string());
+        source = cause.source();
+    }
+    causes
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            ch if ch.is_control() && !matches!(ch, '\n' | '\r' | '\t') => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn serialize_secs<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use serde_json::Value;
+
+    use super::{
+        ExpectationPhase, REPORT_JSON_FILE, REPORT_JUNIT_FILE, ScenarioReport, WorkloadStatus,
+    };
+
+    #[test]
+    fn junit_report_escapes_and_counts_failures() {
+        let mut report = ScenarioReport::new(
+            7,
+            Duration::from_secs(60),
+            ["tx_workload".to_owned()],
+            ["consensus_liveness".to_owned(), "tx_inclusion".to_owned()],
+        );
+        report.set_workload(0, WorkloadStatus::Stopped, None);
+        report.expectation_passed(0);
+        report.expectation_failed(
+            1,
+            ExpectationPhase::Evaluate,
+            &"included 3 < expected 10 <tx>".into(),
+        );
+
+        let xml = report.to_junit_xml();
+        assert!(xml.contains("tests=\"3\" failures=\"1\" skipped=\"0\""));
+        assert!(xml.contains("included 3 &lt; expected 10 &lt;tx&gt;"));
+        assert!(xml.contains("name=\"tx_workload\" time="));
+    }
+
+    #[test]
+    fn json_report_keeps_phases_and_expectation_details() {
+        let mut report = ScenarioReport::new(
+            11,
+            Duration::from_secs(30),
+            ["tx_workload".to_owned()],
+            ["consensus_liveness".to_owned(), "no_forks".to_owned()],
+        );
+        report.phases.capture = Duration::from_millis(500);
+        report.phases.workloads = Duration::from_secs(30);
+        report.phases.evaluate = Duration::from_millis(250);
+        report.set_workload(0, WorkloadStatus::Completed, None);
+        report.expectation_passed(0);
+        report.invariant_violated(1, Duration::from_secs(12), &"fork at height 4".into());
+        report.expectation_failed(1, ExpectationPhase::Invariant, &"fork at height 4".into());
+
+        let json: Value = serde_json::from_str(&report.to_json().expect("report serializes"))
+            .expect("report is valid JSON");
+
+        assert_eq!(json["seed"], 11);
+        assert_eq!(json["outcome"], "failed");
+        assert_eq!(json["phases"]["capture"], 0.5);
+        assert_eq!(json["phases"]["workloads"], 30.0);
+        assert_eq!(json["phases"]["cooldown"], 0.0);
+        assert_eq!(json["phases"]["evaluate"], 0.25);
+        assert_eq!(json["workloads"][0]["status"], "completed");
+
+        let passed = &json["expectations"][0];
+        assert_eq!(passed["name"], "consensus_liveness");
+        assert_eq!(passed["status"], "passed");
+        assert!(passed["phase"].is_null());
+        assert!(passed["error"].is_null());
+
+        let failed = &json["expectations"][1];
+        assert_eq!(failed["name"], "no_forks");
+        assert_eq!(failed["status"], "failed");
+        assert_eq!(failed["phase"], "invariant");
+        assert_eq!(failed["error"], "fork at height 4");
+        assert_eq!(failed["causes"], Value::Array(Vec::new()));
+        assert_eq!(failed["violations"][0]["elapsed"], 12.0);
+        assert_eq!(failed["violations"][0]["error"], "fork at height 4");
+    }
+
+    #[test]
+    fn write_to_dir_writes_both_reports() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let out = dir.path().join("reports");
+        let report = ScenarioReport::new(
+            3,
+            Duration::from_secs(10),
+            ["tx_workload".to_owned()],
+            ["consensus_liveness".to_owned()],
+        );
+
+        report.write_to_dir(&out).expect("reports are written");
+
+        let json = fs::read_to_string(out.join(REPORT_JSON_FILE)).expect("JSON report exists");
+        assert_eq!(json, report.to_json().expect("report serializes"));
+        let xml = fs::read_to_string(out.join(REPORT_JUNIT_FILE)).expect("JUnit report exists");
+        assert_eq!(xml, report.to_junit_xml());
+    }
+}
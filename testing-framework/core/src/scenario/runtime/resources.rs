@@ -0,0 +1,367 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::debug;
+
+use super::context::CleanupGuard;
+
+/// Clock ticks per second used by `/proc/<pid>/stat`. Linux reports CPU time
+/// in `USER_HZ`, which is 100 on every architecture it exposes to userspace.
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+const BYTES_PER_MIB: f64 = 1024.0 * 1024.0;
+/// Samples kept per node. A full series drops every other sample and keeps
+/// later ones at half the rate, so long runs keep an evenly spaced series.
+const MAX_SAMPLES_PER_NODE: usize = 4096;
+
+/// Resource usage of one node process at one point of the run.
+#[derive(Clone, Copy, Debug)]
+pub struct ResourceSample {
+    /// Time since sampling started.
+    pub elapsed: Duration,
+    /// Process the sample was taken from; it changes when the node restarts.
+    pub pid: u32,
+    /// Resident set size.
+    pub rss_bytes: u64,
+    /// CPU time used since the previous sample of the same process, as a
+    /// percentage of one core. `None` for the first sample of a process.
+    pub cpu_percent: Option<f64>,
+    pub threads: u64,
+    /// Open file descriptors; `None` if `/proc/<pid>/fd` was not readable.
+    pub open_fds: Option<u64>,
+    /// Bytes the process read from and wrote to storage since it started;
+    /// `None` if `/proc/<pid>/io` was not readable.
+    pub read_bytes: Option<u64>,
+    pub write_bytes: Option<u64>,
+}
+
+/// Per-node resource time series; clones share the same series.
+///
+/// Each series holds at most 4096 samples and is downsampled as it fills;
+/// the peak RSS still covers every sample ever recorded.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceUsage {
+    series: Arc<Mutex<BTreeMap<String, NodeSeries>>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct NodeSeries {
+    samples: Vec<ResourceSample>,
+    peak_rss: u64,
+    /// Times the series was halved; only every `2^halvings`-th sample is kept.
+    halvings: u32,
+    /// Samples seen since the last kept one.
+    skipped: u64,
+}
+
+impl NodeSeries {
+    fn push(&mut self, sample: ResourceSample) {
+        self.peak_rss = self.peak_rss.max(sample.rss_bytes);
+        if self.skipped + 1 < (1 << self.halvings) {
+            self.skipped += 1;
+            return;
+        }
+        self.skipped = 0;
+        if self.samples.len() >= MAX_SAMPLES_PER_NODE {
+            let mut index = 0usize;
+            self.samples.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.halvings += 1;
+        }
+        self.samples.push(sample);
+    }
+}
+
+impl ResourceUsage {
+    pub fn record(&self, node: &str, sample: ResourceSample) {
+        self.lock().entry(node.to_owned()).or_default().push(sample);
+    }
+
+    /// Names of the nodes with at least one sample.
+    #[must_use]
+    pub fn nodes(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// Samples of `node`, oldest first.
+    #[must_use]
+    pub fn series(&self, node: &str) -> Vec<ResourceSample> {
+        self.lock()
+            .get(node)
+            .map(|series| series.samples.clone())
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn snapshot(&self) -> BTreeMap<String, Vec<ResourceSample>> {
+        self.lock()
+            .iter()
+            .map(|(node, series)| (node.clone(), series.samples.clone()))
+            .collect()
+    }
+
+    /// Highest RSS sampled for `node`, including samples dropped by
+    /// downsampling.
+    #[must_use]
+    pub fn peak_rss(&self, node: &str) -> Option<u64> {
+        self.lock().get(node).map(|series| series.peak_rss)
+    }
+
+    /// Least-squares slope of the RSS of `node`'s current process, in MiB
+    /// per minute, over the samples taken at or after `since`. `None` with
+    /// fewer than two such samples.
+    #[must_use]
+    pub fn memory_growth_mib_per_min(&self, node: &str, since: Duration) -> Option<f64> {
+        let series = self.lock();
+        let samples = &series.get(node)?.samples;
+        let pid = samples.last()?.pid;
+        let points = samples
+            .iter()
+            .filter(|sample| sample.pid == pid && sample.elapsed >= since)
+            .map(|sample| {
+                (
+                    sample.elapsed.as_secs_f64() / 60.0,
+                    sample.rss_bytes as f64 / BYTES_PER_MIB,
+                )
+            })
+            .collect::<Vec<_>>();
+        slope(&points)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, NodeSeries>> {
+        self.series
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn slope(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let (covariance, variance) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                let dx = x - mean_x;
+                (covariance + dx * (y - mean_y), variance + dx * dx)
+            });
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Raw counters read from `/proc` for one process.
+#[derive(Clone, Copy, Debug)]
+struct ProcStats {
+    cpu_ticks: u64,
+    rss_bytes: u64,
+    threads: u64,
+    open_fds: Option<u64>,
+    read_bytes: Option<u64>,
+    write_bytes: Option<u64>,
+}
+
+impl ProcStats {
+    fn read(pid: u32) -> io::Result<Self> {
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat"))?;
+        let status = fs::read_to_string(format!("/proc/{pid}/status"))?;
+        let io_counters = fs::read_to_string(format!("/proc/{pid}/io")).ok();
+        let open_fds = fs::read_dir(format!("/proc/{pid}/fd"))
+            .ok()
+            .map(|entries| entries.count() as u64);
+
+        // A zombie has no memory left to report.
+        let rss_kib = status_field(&status, "VmRSS:").ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "process has no resident memory")
+        })?;
+
+        Ok(Self {
+            cpu_ticks: cpu_ticks(&stat).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "malformed /proc stat")
+            })?,
+            rss_bytes: rss_kib * 1024,
+            threads: status_field(&status, "Threads:").unwrap_or_default(),
+            open_fds,
+            read_bytes: io_counters
+                .as_deref()
+                .and_then(|counters| status_field(counters, "read_bytes:")),
+            write_bytes: io_counters
+                .as_deref()
+                .and_then(|counters| status_field(counters, "write_bytes:")),
+        })
+    }
+}
+
+/// User plus system CPU time from `/proc/<pid>/stat`. The command name is
+/// parenthesised and may contain spaces, so fields are counted from the
+/// closing parenthesis: `utime` and `stime` are fields 14 and 15 of
+/// `proc_pid_stat(5)`.
+fn cpu_ticks(stat: &str) -> Option<u64> {
+    let fields = stat
+        .rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    Some(utime + stime)
+}
+
+/// First number after `key` in a `key: value [unit]` file.
+fn status_field(contents: &str, key: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Join handle for a background resource sampler; aborting it stops sampling.
+pub struct ResourceSamplerTask {
+    handle: JoinHandle<()>,
+}
+
+impl Drop for ResourceSamplerTask {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl CleanupGuard for ResourceSamplerTask {
+    fn cleanup(self: Box<Self>) {
+        self.handle.abort();
+    }
+}
+
+/// Every `interval`, sample each `(node, pid)` that `pids` returns and
+/// record it into `usage`. Processes that cannot be read (stopped nodes, or
+/// hosts without `/proc`) are skipped for that round.
+pub fn spawn_resource_sampler<F>(
+    usage: ResourceUsage,
+    interval: Duration,
+    mut pids: F,
+) -> ResourceSamplerTask
+where
+    F: FnMut() -> Vec<(String, u32)> + Send + 'static,
+{
+    let handle = tokio::spawn(async move {
+        let started = Instant::now();
+        let mut previous = HashMap::<String, (u32, u64, Instant)>::new();
+        loop {
+            for (node, pid) in pids() {
+                let stats = match ProcStats::read(pid) {
+                    Ok(stats) => stats,
+                    Err(err) => {
+                        debug!(node = %node, pid, error = %err, "skipping resource sample");
+                        continue;
+                    }
+                };
+                let now = Instant::now();
+                let cpu_percent = previous
+                    .get(&node)
+                    .filter(|(last_pid, ..)| *last_pid == pid)
+                    .and_then(|(_, last_ticks, last_at)| {
+                        let wall = now.duration_since(*last_at).as_secs_f64();
+                        let cpu = stats.cpu_ticks.saturating_sub(*last_ticks) as f64
+                            / CLOCK_TICKS_PER_SEC;
+                        (wall > 0.0).then(|| cpu / wall * 100.0)
+                    });
+                previous.insert(node.clone(), (pid, stats.cpu_ticks, now));
+
+                usage.record(
+                    &node,
+                    ResourceSample {
+                        elapsed: now.duration_since(started),
+                        pid,
+                        rss_bytes: stats.rss_bytes,
+                        cpu_percent,
+                        threads: stats.threads,
+                        open_fds: stats.open_fds,
+                        read_bytes: stats.read_bytes,
+                        write_bytes: stats.write_bytes,
+                    },
+                );
+            }
+            sleep(interval).await;
+        }
+    });
+
+    ResourceSamplerTask { handle }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{MAX_SAMPLES_PER_NODE, ResourceSample, ResourceUsage, cpu_ticks};
+
+    fn sample(minutes: u64, pid: u32, rss_mib: u64) -> ResourceSample {
+        ResourceSample {
+            elapsed: Duration::from_secs(minutes * 60),
+            pid,
+            rss_bytes: rss_mib * 1024 * 1024,
+            cpu_percent: None,
+            threads: 1,
+            open_fds: None,
+            read_bytes: None,
+            write_bytes: None,
+        }
+    }
+
+    #[test]
+    fn memory_growth_follows_the_current_process() {
+        let usage = ResourceUsage::default();
+        usage.record("node-0", sample(0, 1, 900));
+        for minute in 1..=4 {
+            usage.record("node-0", sample(minute, 2, 100 + 10 * minute));
+        }
+
+        let growth = usage
+            .memory_growth_mib_per_min("node-0", Duration::ZERO)
+            .expect("enough samples");
+        assert!((growth - 10.0).abs() < 1e-9);
+        assert_eq!(usage.peak_rss("node-0"), Some(900 * 1024 * 1024));
+    }
+
+    #[test]
+    fn full_series_are_halved_but_keep_their_peak() {
+        let usage = ResourceUsage::default();
+        let total = 3 * MAX_SAMPLES_PER_NODE as u64;
+        for minute in 0..total {
+            let rss_mib = if minute == 1 { 4096 } else { 100 };
+            usage.record("node-0", sample(minute, 1, rss_mib));
+        }
+
+        let series = usage.series("node-0");
+        assert!(series.len() <= MAX_SAMPLES_PER_NODE);
+        assert!(series.len() >= MAX_SAMPLES_PER_NODE / 2);
+        let spacing = series[1].elapsed - series[0].elapsed;
+        assert!(
+            series
+                .windows(2)
+                .all(|pair| pair[1].elapsed - pair[0].elapsed == spacing)
+        );
+        assert!(
+            series
+                .iter()
+                .all(|sample| sample.rss_bytes < 4096 * 1024 * 1024)
+        );
+        assert_eq!(usage.peak_rss("node-0"), Some(4096 * 1024 * 1024));
+    }
+
+    #[test]
+    fn parses_cpu_ticks_after_command_name() {
+        let stat = "42 (node (x) y) S 1 42 42 0 -1 4194560 100 0 0 0 250 50 0 0 20 0 7";
+        assert_eq!(cpu_ticks(stat), Some(300));
+    }
+}
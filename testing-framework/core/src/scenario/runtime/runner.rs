@@ -1,18 +1,35 @@
-use std::{any::Any, panic::AssertUnwindSafe, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures::FutureExt as _;
+use testing_framework_env as tf_env;
 use tokio::{
+    sync::watch,
     task::JoinSet,
-    time::{sleep, timeout},
+    time::{sleep, sleep_until},
 };
+use tracing::{info, warn};
 
-use super::deployer::ScenarioError;
-use crate::scenario::{
-    DynError, Expectation, Scenario,
-    runtime::context::{CleanupGuard, RunContext, RunHandle},
+use super::{
+    deployer::ScenarioError,
+    exits::{NodeExit, NodeExits},
+    invariants::{InvariantMonitor, violation_summary},
+    report::{ExpectationPhase, ScenarioReport, WorkloadStatus},
+};
+use crate::{
+    nodes::AttachNodeLogs as _,
+    scenario::{
+        DynError, Expectation, Scenario, Workload,
+        runtime::context::{CleanupGuard, RunContext, RunHandle},
+    },
 };
 
 type WorkloadOutcome = Result<(), DynError>;
+type WorkloadResult = (usize, WorkloadOutcome);
 
 const COOLDOWN_BLOCK_INTERVAL_MULTIPLIER: f64 = 5.0;
 const MIN_NODE_CONTROL_COOLDOWN: Duration = Duration::from_secs(30);
@@ -47,51 +64,136 @@ impl Runner {
         }
     }
 
-    pub(crate) fn into_run_handle(mut self) -> RunHandle {
+    pub(crate) fn into_run_handle(mut self, report: ScenarioReport) -> RunHandle {
         RunHandle::from_shared(Arc::clone(&self.context), self.cleanup_guard.take())
+            .with_report(report)
     }
 
     /// Executes the scenario by driving workloads first and then evaluating all
     /// expectations. On any failure it cleans up resources and propagates the
     /// error to the caller.
-    pub async fn run<Caps>(
+    pub async fn run<Caps>(self, scenario: &mut Scenario<Caps>) -> Result<RunHandle, ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        self.run_with_report(scenario).await.0
+    }
+
+    /// Same as [`Runner::run`], but also returns the structured
+    /// [`ScenarioReport`], including for failed runs, so callers can publish
+    /// it.
+    ///
+    /// When `LOGOS_BLOCKCHAIN_SCENARIO_REPORT_DIR` is set the report is also
+    /// written there as JSON and JUnit XML.
+    pub async fn run_with_report<Caps>(
         mut self,
         scenario: &mut Scenario<Caps>,
-    ) -> Result<RunHandle, ScenarioError>
+    ) -> (Result<RunHandle, ScenarioError>, ScenarioReport)
     where
         Caps: Send + Sync,
     {
         let context = self.context();
-        if let Err(error) =
-            Self::prepare_expectations(scenario.expectations_mut(), context.as_ref()).await
-        {
-            self.cleanup();
-            return Err(error);
-        }
+        info!(
+            seed = context.seed(),
+            duration_secs = scenario.duration().as_secs(),
+            "starting scenario run"
+        );
+        let mut report = ScenarioReport::new(
+            context.seed(),
+            scenario.duration(),
+            scenario
+                .workloads()
+                .iter()
+                .map(|workload| workload.name().to_owned()),
+            scenario
+                .expectations()
+                .iter()
+                .map(|expectation| expectation.name().to_owned()),
+        );
+
+        let result = Self::execute(&context, scenario, &mut report).await;
+        report.finish(
+            context.as_ref(),
+            result
+                .as_ref()
+                .err()
+                .map(|error| error as &dyn std::error::Error),
+        );
+        write_report_artifacts(&report);
 
-        if let Err(error) = Self::run_workloads(&context, scenario).await {
-            self.cleanup();
-            return Err(error);
+        match result {
+            Ok(()) => (Ok(self.into_run_handle(report.clone())), report),
+            Err(error) => {
+                let error = match context.node_logs() {
+                    Some(logs) => error.with_node_logs(logs.tails()),
+                    None => error,
+                };
+                warn!(
+                    seed = report.seed,
+                    %error,
+                    "scenario failed; set LOGOS_BLOCKCHAIN_SCENARIO_SEED={} to replay it",
+                    report.seed
+                );
+                self.cleanup();
+                (Err(error), report)
+            }
         }
+    }
 
-        Self::settle_before_expectations(&context).await;
+    async fn execute<Caps>(
+        context: &Arc<RunContext>,
+        scenario: &mut Scenario<Caps>,
+        report: &mut ScenarioReport,
+    ) -> Result<(), ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        let started = Instant::now();
+        let captured =
+            Self::prepare_expectations(scenario.expectations_mut(), context.as_ref(), report).await;
+        report.phases.capture = started.elapsed();
+        captured?;
+
+        let workloads = scenario.workloads().to_vec();
+        let duration = scenario.duration();
+        let mut monitor = InvariantMonitor::new(scenario.expectations_mut());
+        Self::run_workloads(context, &workloads, duration, &mut monitor, report).await?;
+
+        let started = Instant::now();
+        Self::settle_before_expectations(context).await;
+        report.phases.settle = started.elapsed();
+
+        let started = Instant::now();
+        let evaluated =
+            Self::run_expectations(scenario.expectations_mut(), context.as_ref(), report).await;
+        report.phases.evaluate = started.elapsed();
+
+        // A node that died after the workloads stopped is the more useful
+        // failure than whatever expectation it broke.
+        Self::check_node_exits(context.as_ref())?;
+        evaluated
+    }
 
-        if let Err(error) =
-            Self::run_expectations(scenario.expectations_mut(), context.as_ref()).await
-        {
-            self.cleanup();
-            return Err(error);
+    fn check_node_exits(context: &RunContext) -> Result<(), ScenarioError> {
+        let exits = context
+            .node_exits()
+            .map(NodeExits::snapshot)
+            .unwrap_or_default();
+        if exits.is_empty() {
+            Ok(())
+        } else {
+            Err(ScenarioError::NodeExited { exits })
         }
-
-        Ok(self.into_run_handle())
     }
 
     async fn prepare_expectations(
         expectations: &mut [Box<dyn Expectation>],
         context: &RunContext,
+        report: &mut ScenarioReport,
     ) -> Result<(), ScenarioError> {
-        for expectation in expectations {
+        for (index, expectation) in expectations.iter_mut().enumerate() {
             if let Err(source) = expectation.start_capture(context).await {
+                report.expectation_failed(index, ExpectationPhase::Capture, &source);
                 return Err(ScenarioError::ExpectationCapture(source));
             }
         }
@@ -99,47 +201,32 @@ impl Runner {
     }
 
     /// Spawns every workload, waits until the configured duration elapses (or a
-    /// workload fails), and then aborts the remaining tasks.
-    async fn run_workloads<Caps>(
+    /// workload fails), and then aborts the remaining tasks. Continuous
+    /// invariants are polled throughout, including the cooldown window.
+    async fn run_workloads(
         context: &Arc<RunContext>,
-        scenario: &Scenario<Caps>,
-    ) -> Result<(), ScenarioError>
-    where
-        Caps: Send + Sync,
-    {
-        if scenario.workloads().is_empty() {
-            let duration = scenario.duration();
-            if !duration.is_zero() {
-                sleep(duration).await;
-            }
-
-            if let Some(cooldown) = Self::cooldown_duration(context.as_ref()) {
-                if !cooldown.is_zero() {
-                    sleep(cooldown).await;
-                }
-            }
-
-            return Ok(());
-        }
-
-        let mut workloads = Self::spawn_workloads(scenario, context);
-        let _ = Self::drive_until_timer(&mut workloads, scenario.duration()).await?;
+        workloads: &[Arc<dyn Workload>],
+        duration: Duration,
+        monitor: &mut InvariantMonitor<'_>,
+        report: &mut ScenarioReport,
+    ) -> Result<(), ScenarioError> {
+        let started = Instant::now();
+        let mut tasks = Self::spawn_workloads(workloads, context, report);
+        let driven = Self::drive_until_timer(context, &mut tasks, duration, monitor, report).await;
+        report.phases.workloads = started.elapsed();
+        let _ = driven?;
 
         // Keep workloads running during the cooldown window so that late
         // inclusions (especially DA parent-linked ops) still have a chance to
         // land before expectations evaluate. We still abort everything at the
         // end of cooldown to prevent leaking tasks across runs.
-        if let Some(cooldown) = Self::cooldown_duration(context.as_ref()) {
-            if !cooldown.is_zero() {
-                if workloads.is_empty() {
-                    sleep(cooldown).await;
-                } else {
-                    let _ = Self::drive_until_timer(&mut workloads, cooldown).await?;
-                }
-            }
-        }
+        let started = Instant::now();
+        let cooldown = Self::cooldown_duration(context.as_ref()).unwrap_or_default();
+        let driven = Self::drive_until_timer(context, &mut tasks, cooldown, monitor, report).await;
+        report.phases.cooldown = started.elapsed();
+        let _ = driven?;
 
-        Self::drain_workloads(&mut workloads).await
+        Self::drain_workloads(&mut tasks, report).await
     }
 
     async fn settle_before_expectations(context: &Arc<RunContext>) {
@@ -163,11 +250,23 @@ impl Runner {
     async fn run_expectations(
         expectations: &mut [Box<dyn Expectation>],
         context: &RunContext,
+        report: &mut ScenarioReport,
     ) -> Result<(), ScenarioError> {
         let mut failures: Vec<(String, DynError)> = Vec::new();
-        for expectation in expectations {
-            if let Err(source) = expectation.evaluate(context).await {
-                failures.push((expectation.name().to_owned(), source));
+        for (index, expectation) in expectations.iter_mut().enumerate() {
+            match expectation.evaluate(context).await {
+                Ok(()) => match violation_summary(report.violations(index)) {
+                    None => report.expectation_passed(index),
+                    Some(summary) => {
+                        let source: DynError = summary.into();
+                        report.expectation_failed(index, ExpectationPhase::Invariant, &source);
+                        failures.push((expectation.name().to_owned(), source));
+                    }
+                },
+                Err(source) => {
+                    report.expectation_failed(index, ExpectationPhase::Evaluate, &source);
+                    failures.push((expectation.name().to_owned(), source));
+                }
             }
         }
 
@@ -218,61 +317,76 @@ impl Runner {
 
     /// Spawns each workload inside its own task and returns the join set for
     /// cooperative management.
-    fn spawn_workloads<Caps>(
-        scenario: &Scenario<Caps>,
+    fn spawn_workloads(
+        workloads: &[Arc<dyn Workload>],
         context: &Arc<RunContext>,
-    ) -> JoinSet<WorkloadOutcome>
-    where
-        Caps: Send + Sync,
-    {
-        let mut workloads = JoinSet::new();
-        for workload in scenario.workloads() {
+        report: &mut ScenarioReport,
+    ) -> JoinSet<WorkloadResult> {
+        let mut tasks = JoinSet::new();
+        for (index, workload) in workloads.iter().enumerate() {
             let workload = Arc::clone(workload);
             let ctx = Arc::clone(context);
 
-            workloads.spawn(async move {
+            tasks.spawn(async move {
                 let outcome = AssertUnwindSafe(async { workload.start(ctx.as_ref()).await })
                     .catch_unwind()
                     .await;
 
-                outcome.unwrap_or_else(|panic| {
+                let outcome = outcome.unwrap_or_else(|panic| {
                     Err(format!("workload panicked: {}", panic_message(panic)).into())
-                })
+                });
+                (index, outcome)
             });
+            // Anything still running when the runner aborts it keeps this
+            // status; a finished task overwrites it in `map_join_result`.
+            report.set_workload(index, WorkloadStatus::Stopped, None);
         }
 
-        workloads
+        tasks
     }
 
-    /// Polls workload tasks until the timeout fires or one reports an error.
+    /// Polls workload tasks until the timer fires, every workload has finished,
+    /// one reports an error or a node exits, running due invariants in
+    /// between. Without workload tasks this simply waits out the timer.
     async fn drive_until_timer(
-        workloads: &mut JoinSet<WorkloadOutcome>,
+        context: &RunContext,
+        workloads: &mut JoinSet<WorkloadResult>,
         duration: Duration,
+        monitor: &mut InvariantMonitor<'_>,
+        report: &mut ScenarioReport,
     ) -> Result<bool, ScenarioError> {
-        let run_future = async {
-            while let Some(result) = workloads.join_next().await {
-                Self::map_join_result(result)?;
+        let deadline = Instant::now() + duration;
+        let mut node_exits = context.node_exits().map(NodeExits::subscribe);
+        loop {
+            let next_check = monitor.next_due().filter(|due| *due < deadline);
+            tokio::select! {
+                () = sleep_until(deadline.into()) => return Ok(true),
+                exits = next_node_exits(node_exits.as_mut()) => {
+                    return Err(ScenarioError::NodeExited { exits });
+                }
+                () = sleep_until(next_check.unwrap_or(deadline).into()), if next_check.is_some() => {
+                    monitor.check_due(context, report).await?;
+                }
+                Some(result) = workloads.join_next(), if !workloads.is_empty() => {
+                    Self::map_join_result(result, report)?;
+                    if workloads.is_empty() {
+                        return Ok(false);
+                    }
+                }
             }
-            Ok(())
-        };
-
-        timeout(duration, run_future)
-            .await
-            .map_or(Ok(true), |result| {
-                result?;
-                Ok(false)
-            })
+        }
     }
 
     /// Aborts and drains any remaining workload tasks so we do not leak work
     /// across scenario runs.
     async fn drain_workloads(
-        workloads: &mut JoinSet<WorkloadOutcome>,
+        workloads: &mut JoinSet<WorkloadResult>,
+        report: &mut ScenarioReport,
     ) -> Result<(), ScenarioError> {
         workloads.abort_all();
 
         while let Some(result) = workloads.join_next().await {
-            Self::map_join_result(result)?;
+            Self::map_join_result(result, report)?;
         }
 
         Ok(())
@@ -281,10 +395,18 @@ impl Runner {
     /// Converts the outcome of a workload task into the canonical scenario
     /// error, tolerating cancellation when the runner aborts unfinished tasks.
     fn map_join_result(
-        result: Result<WorkloadOutcome, tokio::task::JoinError>,
+        result: Result<WorkloadResult, tokio::task::JoinError>,
+        report: &mut ScenarioReport,
     ) -> Result<(), ScenarioError> {
         match result {
-            Ok(outcome) => outcome.map_err(ScenarioError::Workload),
+            Ok((index, Ok(()))) => {
+                report.set_workload(index, WorkloadStatus::Completed, None);
+                Ok(())
+            }
+            Ok((index, Err(source))) => {
+                report.set_workload(index, WorkloadStatus::Failed, Some(source.to_string()));
+                Err(ScenarioError::Workload(source))
+            }
             Err(join_err) if join_err.is_cancelled() => Ok(()),
             Err(join_err) => Err(ScenarioError::Workload(
                 format!("workload task failed: {join_err}").into(),
@@ -293,6 +415,36 @@ impl Runner {
     }
 }
 
+/// Resolves with the recorded exits once there is at least one; never
+/// resolves when the deployer does not watch its nodes.
+async fn next_node_exits(exits: Option<&mut watch::Receiver<Vec<NodeExit>>>) -> Vec<NodeExit> {
+    let Some(exits) = exits else {
+        return std::future::pending().await;
+    };
+    loop {
+        let current = exits.borrow_and_update().clone();
+        if !current.is_empty() {
+            return current;
+        }
+        if exits.changed().await.is_err() {
+            return std::future::pending().await;
+        }
+    }
+}
+
+/// Writes the report into `LOGOS_BLOCKCHAIN_SCENARIO_REPORT_DIR` when set. A
+/// failed write is logged rather than failing the run.
+fn write_report_artifacts(report: &ScenarioReport) {
+    let Some(dir) = tf_env::scenario_report_dir() else {
+        return;
+    };
+
+    match report.write_to_dir(&dir) {
+        Ok(()) => info!(dir = %dir.display(), "scenario report written"),
+        Err(err) => warn!(dir = %dir.display(), error = %err, "failed to write scenario report"),
+    }
+}
+
 /// Attempts to turn a panic payload into a readable string for diagnostics.
 fn panic_message(panic: Box<dyn Any + Send>) -> String {
     panic.downcast::<String>().map_or_else(
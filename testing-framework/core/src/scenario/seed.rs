@@ -0,0 +1,69 @@
+use rand::{Rng as _, SeedableRng as _, rngs::StdRng, thread_rng};
+use testing_framework_env as tf_env;
+
+use super::definition::ScenarioBuildError;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Picks the scenario seed: an explicit builder seed wins, then
+/// `LOGOS_BLOCKCHAIN_SCENARIO_SEED`, then a fresh random value.
+pub(crate) fn resolve_seed(explicit: Option<u64>) -> Result<u64, ScenarioBuildError> {
+    let seed = match explicit {
+        Some(seed) => Some(seed),
+        None => {
+            tf_env::scenario_seed().map_err(|source| ScenarioBuildError::InvalidSeed { source })?
+        }
+    };
+    Ok(seed.unwrap_or_else(|| thread_rng().r#gen()))
+}
+
+/// Derives an independent seed for a named random stream.
+///
+/// Each consumer (a workload, the client picker, ...) gets its own stream so
+/// the values it draws do not depend on what other consumers draw.
+pub(crate) fn derive_seed(seed: u64, stream: &str) -> u64 {
+    let hash = stream.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    splitmix64(seed ^ hash)
+}
+
+pub(crate) fn seeded_rng(seed: u64, stream: &str) -> StdRng {
+    StdRng::seed_from_u64(derive_seed(seed, stream))
+}
+
+const fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng as _;
+
+    use super::{derive_seed, seeded_rng};
+
+    #[test]
+    fn streams_are_stable_and_independent() {
+        assert_eq!(
+            derive_seed(7, "chaos_restart"),
+            derive_seed(7, "chaos_restart")
+        );
+        assert_ne!(
+            derive_seed(7, "chaos_restart"),
+            derive_seed(7, "node_clients")
+        );
+        assert_ne!(
+            derive_seed(7, "chaos_restart"),
+            derive_seed(8, "chaos_restart")
+        );
+
+        let first: Vec<u32> = (0..4)
+            .map(|_| seeded_rng(42, "stream").gen_range(0..1000))
+            .collect();
+        assert!(first.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+}
@@ -5,6 +5,7 @@ use lb_core::{
     sdp::{Locator, ServiceType},
 };
 use lb_node::config::RunConfig;
+use lb_utils::net::get_available_udp_port;
 use testing_framework_config::topology::{
     configs::{
         api::{ApiConfigError, create_api_configs},
@@ -14,6 +15,11 @@ use testing_framework_config::topology::{
             create_genesis_tx_with_declarations, sync_utxos_with_genesis,
         },
         network::{Libp2pNetworkLayout, NetworkParams},
+        node_spec::{
+            NodeSpec, NodeSpecError, NodeSpecs, apply_initial_peer_specs,
+            consensus_params_with_specs, retain_blend_core_providers, validate_node_specs,
+        },
+        stake::StakeDistribution,
         tracing::create_tracing_configs,
         wallet::WalletConfig,
     },
@@ -47,6 +53,8 @@ pub enum TopologyBuildError {
     Api(#[from] ApiConfigError),
     #[error(transparent)]
     Genesis(#[from] ConsensusConfigError),
+    #[error(transparent)]
+    NodeSpec(#[from] NodeSpecError),
     #[error("config generation requires at least one consensus config")]
     MissingConsensusConfig,
     #[error("internal config vector mismatch for {label} (expected {expected}, got {actual})")]
@@ -66,6 +74,10 @@ pub struct TopologyConfig {
     pub wallet_config: WalletConfig,
     pub node_config_patches: HashMap<usize, NodeConfigPatch>,
     pub persist_dirs: HashMap<usize, PathBuf>,
+    pub node_specs: NodeSpecs,
+    /// Point nodes at the framework time server instead of their own clock.
+    /// Off unless enabled through [`TopologyBuilder::with_framework_time`].
+    pub framework_time: bool,
 }
 
 impl TopologyConfig {
@@ -79,6 +91,8 @@ impl TopologyConfig {
             wallet_config: WalletConfig::default(),
             node_config_patches: HashMap::new(),
             persist_dirs: HashMap::new(),
+            node_specs: NodeSpecs::new(),
+            framework_time: false,
         }
     }
 
@@ -92,6 +106,8 @@ impl TopologyConfig {
             wallet_config: WalletConfig::default(),
             node_config_patches: HashMap::new(),
             persist_dirs: HashMap::new(),
+            node_specs: NodeSpecs::new(),
+            framework_time: false,
         }
     }
 
@@ -107,6 +123,8 @@ impl TopologyConfig {
             wallet_config: WalletConfig::default(),
             node_config_patches: HashMap::new(),
             persist_dirs: HashMap::new(),
+            node_specs: NodeSpecs::new(),
+            framework_time: false,
         }
     }
 
@@ -131,6 +149,31 @@ impl TopologyConfig {
         self.persist_dirs.insert(index, dir);
         self
     }
+
+    /// Declarative spec for a node; nodes without one use
+    /// [`NodeSpec::default`].
+    #[must_use]
+    pub fn node_spec(&self, index: usize) -> Option<&NodeSpec> {
+        self.node_specs.get(&index)
+    }
+
+    #[must_use]
+    pub fn with_node_spec(mut self, index: usize, spec: NodeSpec) -> Self {
+        self.node_specs.insert(index, spec);
+        self
+    }
+
+    #[must_use]
+    /// Spread genesis leader stake across nodes according to `distribution`.
+    pub fn with_stake_distribution(mut self, distribution: StakeDistribution) -> Self {
+        self.consensus_params.stake_distribution = distribution;
+        self
+    }
+
+    #[must_use]
+    pub const fn stake_distribution(&self) -> &StakeDistribution {
+        &self.consensus_params.stake_distribution
+    }
 }
 
 /// Builder that produces `GeneratedTopology` instances from a `TopologyConfig`.
@@ -182,6 +225,13 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Describe a specific node index declaratively.
+    pub fn with_node_spec(mut self, index: usize, spec: NodeSpec) -> Self {
+        self.config.node_specs.insert(index, spec);
+        self
+    }
+
     #[must_use]
     /// Set node counts.
     pub const fn with_node_count(mut self, nodes: usize) -> Self {
@@ -196,7 +246,7 @@ impl TopologyBuilder {
 
     #[must_use]
     /// Configure the libp2p network layout.
-    pub const fn with_network_layout(mut self, layout: Libp2pNetworkLayout) -> Self {
+    pub fn with_network_layout(mut self, layout: Libp2pNetworkLayout) -> Self {
         self.config.network_params.libp2p_network_layout = layout;
         self
     }
@@ -207,6 +257,21 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Spread genesis leader stake across nodes according to `distribution`.
+    pub fn with_stake_distribution(mut self, distribution: StakeDistribution) -> Self {
+        self.config.consensus_params.stake_distribution = distribution;
+        self
+    }
+
+    #[must_use]
+    /// Sync nodes against the framework time server so the run can skew
+    /// their clocks. Off by default; only the local deployer supports it.
+    pub const fn with_framework_time(mut self, enabled: bool) -> Self {
+        self.config.framework_time = enabled;
+        self
+    }
+
     /// Finalize and generate topology and node descriptors.
     pub fn build(self) -> Result<GeneratedTopology, TopologyBuildError> {
         let Self {
@@ -225,20 +290,29 @@ impl TopologyBuilder {
         }
 
         let (ids, blend_ports) = resolve_and_validate_vectors(ids, blend_ports, n_participants)?;
+        validate_node_specs(&config.node_specs, n_participants)?;
+        let consensus_params = consensus_params_with_specs(
+            &config.consensus_params,
+            &config.wallet_config,
+            n_participants,
+            &config.node_specs,
+        )?;
 
         let BaseConfigs {
             mut consensus_configs,
             bootstrap_configs: bootstrapping_config,
-            network_configs,
+            mut network_configs,
             blend_configs,
         } = build_base_configs(
             &ids,
-            &config.consensus_params,
+            &consensus_params,
             &config.network_params,
             &config.wallet_config,
             &blend_ports,
         )?;
 
+        apply_initial_peer_specs(&mut network_configs, &config.node_specs);
+
         let api_configs = create_api_configs(&ids)?;
         let tracing_configs = create_tracing_configs(&ids);
         let time_config = default_time_config();
@@ -246,7 +320,10 @@ impl TopologyBuilder {
         let first_consensus = consensus_configs
             .first()
             .ok_or(TopologyBuildError::MissingConsensusConfig)?;
-        let providers = collect_provider_infos(first_consensus, &blend_configs)?;
+        let providers = retain_blend_core_providers(
+            collect_provider_infos(first_consensus, &blend_configs)?,
+            &config.node_specs,
+        );
 
         let genesis_tx = create_consensus_genesis_tx(first_consensus, providers)?;
         apply_consensus_genesis_tx(&mut consensus_configs, &genesis_tx)?;
@@ -378,7 +455,7 @@ fn build_node_descriptors(
         let id = get_copied("ids", ids, i, n_participants)?;
         let blend_port = get_copied("blend_ports", blend_ports, i, n_participants)?;
 
-        let general = GeneralConfig {
+        let mut general = GeneralConfig {
             consensus_config,
             bootstrapping_config,
             network_config,
@@ -388,6 +465,17 @@ fn build_node_descriptors(
             time_config: time_config.clone(),
             kms_config,
         };
+        let spec = config.node_specs.get(&i).cloned().unwrap_or_default();
+        spec.apply_to(i, &mut general)?;
+        // An NTP server set in the node spec wins over the framework clock.
+        let own_ntp_server = spec.time.as_ref().is_some_and(|t| t.ntp_server.is_some());
+        if config.framework_time && !own_ntp_server {
+            let port =
+                get_available_udp_port().ok_or(TopologyResolveError::PortAllocationFailed {
+                    label: "time server",
+                })?;
+            general.time_config.use_framework_clock(port);
+        }
 
         let descriptor = GeneratedNodeConfig {
             index: i,
@@ -396,6 +484,7 @@ fn build_node_descriptors(
             blend_port,
             config_patch: node_config_patches.get(&i).cloned(),
             persist_dir: config.persist_dirs.get(&i).cloned(),
+            spec,
         };
 
         nodes.push(descriptor);
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    nodes::node::Node,
+    nodes::{NodeLogs, node::Node},
     topology::{
         generation::find_expected_peer_counts,
         readiness::{NetworkReadiness, ReadinessCheck, ReadinessError},
@@ -31,6 +31,16 @@ impl Topology {
         self.nodes
     }
 
+    /// Output handles of every node in the topology.
+    #[must_use]
+    pub fn node_logs(&self) -> NodeLogs {
+        let logs = NodeLogs::default();
+        for node in &self.nodes {
+            logs.register(node.output().clone());
+        }
+        logs
+    }
+
     pub async fn wait_network_ready(&self) -> Result<(), ReadinessError> {
         let listen_ports = self.node_listen_ports();
         if listen_ports.len() <= 1 {
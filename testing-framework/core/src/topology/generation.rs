@@ -4,7 +4,7 @@ use reqwest::{Client, Url};
 
 use crate::topology::{
     config::{NodeConfigPatch, TopologyConfig},
-    configs::{GeneralConfig, wallet::WalletAccount},
+    configs::{GeneralConfig, node_spec::NodeSpec, wallet::WalletAccount},
     readiness::{HttpNetworkReadiness, ReadinessCheck, ReadinessError},
 };
 
@@ -17,6 +17,7 @@ pub struct GeneratedNodeConfig {
     pub blend_port: u16,
     pub config_patch: Option<NodeConfigPatch>,
     pub persist_dir: Option<PathBuf>,
+    pub spec: NodeSpec,
 }
 
 impl GeneratedNodeConfig {
@@ -26,6 +27,12 @@ impl GeneratedNodeConfig {
         self.index
     }
 
+    #[must_use]
+    /// Declarative spec this node was generated from.
+    pub const fn spec(&self) -> &NodeSpec {
+        &self.spec
+    }
+
     #[must_use]
     pub const fn network_port(&self) -> u16 {
         self.general.network_config.backend.swarm.port
@@ -200,3 +207,40 @@ pub fn find_expected_peer_counts(
 
     expected.into_iter().map(|set| set.len()).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use testing_framework_config::topology::configs::network::{Libp2pNetworkLayout, layout_links};
+
+    use super::find_expected_peer_counts;
+
+    fn expected_counts(layout: &Libp2pNetworkLayout, n: usize) -> Vec<usize> {
+        let listen_ports = (0..n).map(|idx| 7000 + idx as u16).collect::<Vec<_>>();
+        let mut initial_peer_ports = vec![HashSet::new(); n];
+        for (dialer, target) in layout_links(layout, n).expect("layout should resolve") {
+            initial_peer_ports[dialer].insert(listen_ports[target]);
+        }
+        find_expected_peer_counts(&listen_ports, &initial_peer_ports)
+    }
+
+    #[test]
+    fn expected_peer_counts_follow_layout_degree() {
+        assert_eq!(expected_counts(&Libp2pNetworkLayout::Ring, 5), vec![2; 5]);
+        assert_eq!(
+            expected_counts(
+                &Libp2pNetworkLayout::RandomRegular { degree: 3, seed: 1 },
+                8
+            ),
+            vec![3; 8]
+        );
+        assert_eq!(
+            expected_counts(
+                &Libp2pNetworkLayout::Explicit(vec![vec![1, 2], vec![0], vec![]]),
+                3
+            ),
+            vec![2, 1, 1]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Gherkin step library for writing scenarios as feature files.
+//!
+//! ```gherkin
+//! Scenario: restart under load
+//!   Given a star topology of 4 nodes
+//!   When transactions are submitted at 5 per block for 60 seconds
+//!   And node-1 is restarted
+//!   Then consensus is live
+//! ```
+//!
+//! `Given`/`When` steps shape a [`ScenarioWorld`]; the first `Then` step
+//! builds, deploys and runs it, and later `Then` steps check the finished run.
+
+pub mod steps;
+pub mod world;
+
+pub use world::{DeployerChoice, ScenarioWorld, StepError};
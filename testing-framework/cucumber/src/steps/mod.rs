@@ -0,0 +1,9 @@
+//! Step definitions, registered with `cucumber` through their attributes.
+//!
+//! - [`run`]: deployer, topology, wallets, running the scenario and post-run
+//!   checks.
+//! - [`workloads`]: transaction load, node restarts and expectations that must
+//!   be attached before the run.
+
+pub mod run;
+pub mod workloads;
@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use cucumber::{given, then, when};
+use testing_framework_config::topology::configs::network::Libp2pNetworkLayout;
+use testing_framework_core::scenario::{Expectation as _, ScenarioBuilder};
+use testing_framework_workflows::{ChainReconvergence, ConsensusLiveness};
+
+use crate::world::{DeployerChoice, ScenarioWorld, StepError, StepScenarioBuilder};
+
+/// Wallets seeded by a topology step; override with `Given N funded wallets`.
+const DEFAULT_WALLETS: usize = 100;
+
+fn parse_layout(raw: &str) -> Result<Libp2pNetworkLayout, StepError> {
+    match raw.to_ascii_lowercase().as_str() {
+        "star" => Ok(Libp2pNetworkLayout::Star),
+        "chain" => Ok(Libp2pNetworkLayout::Chain),
+        "full" => Ok(Libp2pNetworkLayout::Full),
+        "ring" => Ok(Libp2pNetworkLayout::Ring),
+        other => Err(StepError::UnknownLayout(other.to_owned())),
+    }
+}
+
+#[given(expr = "the {word} deployer")]
+async fn deployer(world: &mut ScenarioWorld, deployer: String) -> Result<(), StepError> {
+    world.set_deployer(deployer.parse::<DeployerChoice>()?)
+}
+
+fn start_topology(
+    world: &mut ScenarioWorld,
+    layout: Libp2pNetworkLayout,
+    nodes: usize,
+) -> Result<(), StepError> {
+    let builder: StepScenarioBuilder =
+        ScenarioBuilder::topology_with(|t| t.network_layout(layout).nodes(nodes))
+            .enable_node_control()
+            .wallets(DEFAULT_WALLETS);
+    world.start_scenario(builder)
+}
+
+#[given(expr = "a {word} topology of {int} nodes")]
+async fn topology(
+    world: &mut ScenarioWorld,
+    layout: String,
+    nodes: usize,
+) -> Result<(), StepError> {
+    start_topology(world, parse_layout(&layout)?, nodes)
+}
+
+#[given(expr = "a random-regular topology of {int} nodes with degree {int} and seed {int}")]
+async fn random_regular_topology(
+    world: &mut ScenarioWorld,
+    nodes: usize,
+    degree: usize,
+    seed: u64,
+) -> Result<(), StepError> {
+    start_topology(
+        world,
+        Libp2pNetworkLayout::RandomRegular { degree, seed },
+        nodes,
+    )
+}
+
+#[given(
+    expr = "a small-world topology of {int} nodes with {int} neighbours, rewire probability {float} and seed {int}"
+)]
+async fn small_world_topology(
+    world: &mut ScenarioWorld,
+    nodes: usize,
+    neighbours: usize,
+    rewire_probability: f64,
+    seed: u64,
+) -> Result<(), StepError> {
+    start_topology(
+        world,
+        Libp2pNetworkLayout::SmallWorld {
+            neighbours,
+            rewire_probability,
+            seed,
+        },
+        nodes,
+    )
+}
+
+#[given(
+    expr = "a clustered topology of {int} nodes in {int} clusters with {int} links between clusters and seed {int}"
+)]
+async fn clustered_topology(
+    world: &mut ScenarioWorld,
+    nodes: usize,
+    clusters: usize,
+    inter_cluster_links: usize,
+    seed: u64,
+) -> Result<(), StepError> {
+    start_topology(
+        world,
+        Libp2pNetworkLayout::Clustered {
+            clusters,
+            inter_cluster_links,
+            seed,
+        },
+        nodes,
+    )
+}
+
+#[given(expr = "{int} funded wallets")]
+async fn wallets(world: &mut ScenarioWorld, users: usize) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.wallets(users))
+}
+
+#[given(expr = "the scenario seed is {int}")]
+async fn seed(world: &mut ScenarioWorld, seed: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.with_seed(seed))
+}
+
+#[when(expr = "the scenario runs for {int} seconds")]
+async fn run_for(world: &mut ScenarioWorld, secs: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.with_run_duration(Duration::from_secs(secs)))
+}
+
+#[then("the scenario succeeds")]
+async fn scenario_succeeds(world: &mut ScenarioWorld) -> Result<(), StepError> {
+    world.run().await.map(|_| ())
+}
+
+#[then("consensus is live")]
+async fn consensus_is_live(world: &mut ScenarioWorld) -> Result<(), StepError> {
+    let handle = world.run().await?;
+    ConsensusLiveness::default()
+        .evaluate(handle.context())
+        .await
+        .map_err(|err| StepError::Check {
+            check: "consensus liveness",
+            message: err.to_string(),
+        })
+}
+
+#[then("all nodes converge on the same tip")]
+async fn nodes_converge(world: &mut ScenarioWorld) -> Result<(), StepError> {
+    let handle = world.run().await?;
+    ChainReconvergence::default()
+        .evaluate(handle.context())
+        .await
+        .map_err(|err| StepError::Check {
+            check: "chain reconvergence",
+            message: err.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_config::topology::configs::network::Libp2pNetworkLayout;
+
+    use super::parse_layout;
+    use crate::world::{DeployerChoice, StepError};
+
+    #[test]
+    fn parses_named_layouts_case_insensitively() {
+        assert_eq!(parse_layout("star").unwrap(), Libp2pNetworkLayout::Star);
+        assert_eq!(parse_layout("Chain").unwrap(), Libp2pNetworkLayout::Chain);
+        assert_eq!(parse_layout("FULL").unwrap(), Libp2pNetworkLayout::Full);
+        assert_eq!(parse_layout("ring").unwrap(), Libp2pNetworkLayout::Ring);
+        assert!(matches!(
+            parse_layout("random-regular"),
+            Err(StepError::UnknownLayout(layout)) if layout == "random-regular"
+        ));
+    }
+
+    #[test]
+    fn parses_deployer_choices() {
+        assert_eq!(
+            "local".parse::<DeployerChoice>().unwrap(),
+            DeployerChoice::Local
+        );
+        assert_eq!(
+            " Host ".parse::<DeployerChoice>().unwrap(),
+            DeployerChoice::Local
+        );
+        assert_eq!(
+            "compose".parse::<DeployerChoice>().unwrap(),
+            DeployerChoice::Compose
+        );
+        assert!(matches!(
+            "k8s".parse::<DeployerChoice>(),
+            Err(StepError::UnknownDeployer(deployer)) if deployer == "k8s"
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use async_trait::async_trait;
+use cucumber::{given, when};
+use testing_framework_core::scenario::{DynError, RunContext, Workload};
+use testing_framework_workflows::{ChaosBuilderExt as _, MaxRss, ScenarioBuilderExt as _};
+use tokio::time::sleep;
+use tracing::info;
+
+use crate::world::{ScenarioWorld, StepError};
+
+#[when(expr = "transactions are submitted at {int} per block for {int} seconds")]
+async fn transactions(world: &mut ScenarioWorld, rate: u64, secs: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| {
+        builder
+            .transactions_with(|txs| txs.rate(rate))
+            .with_run_duration(Duration::from_secs(secs))
+    })
+}
+
+#[when(expr = "load is generated at {float} transactions per second for {int} seconds")]
+async fn load(world: &mut ScenarioWorld, tps: f64, secs: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| {
+        builder
+            .load_with(|load| load.tps(tps))
+            .with_run_duration(Duration::from_secs(secs))
+    })
+}
+
+#[when(expr = "{word} is restarted")]
+async fn restart_midway(world: &mut ScenarioWorld, node: String) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.with_workload(RestartNode::new(node, None)))
+}
+
+#[when(expr = "{word} is restarted after {int} seconds")]
+async fn restart_after(
+    world: &mut ScenarioWorld,
+    node: String,
+    secs: u64,
+) -> Result<(), StepError> {
+    let delay = Some(Duration::from_secs(secs));
+    world.update_scenario(|builder| builder.with_workload(RestartNode::new(node, delay)))
+}
+
+#[when(expr = "random nodes are restarted every {int} to {int} seconds")]
+async fn random_restarts(
+    world: &mut ScenarioWorld,
+    min_secs: u64,
+    max_secs: u64,
+) -> Result<(), StepError> {
+    world.update_scenario(|builder| {
+        builder.chaos_with(|chaos| {
+            chaos
+                .restart()
+                .min_delay(Duration::from_secs(min_secs))
+                .max_delay(Duration::from_secs(max_secs))
+                .target_cooldown(Duration::from_secs(max_secs))
+                .apply()
+        })
+    })
+}
+
+#[when(expr = "invalid transactions are submitted every {int} seconds")]
+async fn adversarial(world: &mut ScenarioWorld, secs: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| {
+        builder.adversarial_transactions_with(|attacks| {
+            attacks.with_interval(Duration::from_secs(secs))
+        })
+    })
+}
+
+#[when(expr = "{int} channels are inscribed every {int} seconds")]
+async fn channels(world: &mut ScenarioWorld, channels: usize, secs: u64) -> Result<(), StepError> {
+    let channels = NonZeroUsize::new(channels)
+        .ok_or_else(|| StepError::Build("channel count must be non-zero".to_owned()))?;
+    world.update_scenario(|builder| {
+        builder.channels_with(|workload| {
+            workload
+                .with_channels(channels)
+                .with_interval(Duration::from_secs(secs))
+        })
+    })
+}
+
+#[given("chain safety is checked")]
+async fn chain_safety(world: &mut ScenarioWorld) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.expect_chain_safety())
+}
+
+#[given(expr = "no node may fall more than {int} blocks behind")]
+async fn max_block_lag(world: &mut ScenarioWorld, max_lag: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.expect_max_block_lag(max_lag))
+}
+
+#[given(expr = "no node may use more than {int} MiB of memory")]
+async fn max_rss(world: &mut ScenarioWorld, max_mib: u64) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.with_expectation(MaxRss::mib(max_mib)))
+}
+
+#[given(expr = "node memory grows by less than {float} MiB per minute after {int} seconds")]
+async fn memory_growth(
+    world: &mut ScenarioWorld,
+    max_mib_per_min: f64,
+    warmup_secs: u64,
+) -> Result<(), StepError> {
+    world.update_scenario(|builder| {
+        builder.expect_memory_growth_below(max_mib_per_min, Duration::from_secs(warmup_secs))
+    })
+}
+
+#[given("ledger balances are checked")]
+async fn ledger_conservation(world: &mut ScenarioWorld) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.expect_ledger_conservation())
+}
+
+#[given(expr = "mempools drain to {int} pending transactions")]
+async fn mempool_drain(world: &mut ScenarioWorld, max_pending: usize) -> Result<(), StepError> {
+    world.update_scenario(|builder| builder.expect_mempool_drain(max_pending))
+}
+
+/// Restarts a single node once, by default halfway through the run.
+struct RestartNode {
+    node: String,
+    delay: Option<Duration>,
+}
+
+impl RestartNode {
+    const fn new(node: String, delay: Option<Duration>) -> Self {
+        Self { node, delay }
+    }
+}
+
+#[async_trait]
+impl Workload for RestartNode {
+    fn name(&self) -> &str {
+        "restart_node"
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or("restart step requires a deployer with node control")?;
+        let delay = self.delay.unwrap_or_else(|| ctx.run_duration() / 2);
+
+        sleep(delay).await;
+        info!(node = %self.node, "restarting node");
+        handle.restart_node(&self.node).await?;
+
+        // Finishing early would end the run once every workload is done.
+        sleep(ctx.run_duration().saturating_sub(delay)).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::{fmt, str::FromStr};
+
+use cucumber::World;
+use testing_framework_core::scenario::{
+    Builder as CoreScenarioBuilder, Deployer as _, NodeControlCapability, RunHandle,
+};
+use testing_framework_runner_compose::ComposeDeployer;
+use testing_framework_runner_local::LocalDeployer;
+use thiserror::Error;
+use tracing::info;
+
+/// Scenario builder used by every step. Node control is always enabled so
+/// restart steps work on both supported deployers.
+pub type StepScenarioBuilder = CoreScenarioBuilder<NodeControlCapability>;
+
+#[derive(Debug, Error)]
+pub enum StepError {
+    #[error("no topology configured; start with a `Given a ... topology of N nodes` step")]
+    MissingTopology,
+    #[error("the scenario already ran; configuration steps must come before `Then` steps")]
+    AlreadyRan,
+    #[error("unknown deployer '{0}' (expected `local` or `compose`)")]
+    UnknownDeployer(String),
+    #[error(
+        "unknown network layout '{0}' (expected `star`, `chain`, `full` or `ring`; random-regular, small-world and clustered layouts have their own steps)"
+    )]
+    UnknownLayout(String),
+    #[error("scenario build failed: {0}")]
+    Build(String),
+    #[error("deployment failed: {0}")]
+    Deploy(String),
+    #[error("scenario run failed: {0}")]
+    Run(String),
+    /// Replays the failure of an earlier build, deploy or run.
+    #[error("{0}")]
+    Failed(String),
+    #[error("{check} failed: {message}")]
+    Check {
+        check: &'static str,
+        message: String,
+    },
+}
+
+/// Deployer the feature runs against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeployerChoice {
+    #[default]
+    Local,
+    Compose,
+}
+
+impl DeployerChoice {
+    /// Reads `LOGOS_BLOCKCHAIN_CUCUMBER_DEPLOYER`, falling back to local.
+    #[must_use]
+    pub fn from_env() -> Self {
+        testing_framework_env::cucumber_deployer()
+            .and_then(|raw| match raw.parse() {
+                Ok(choice) => Some(choice),
+                Err(err) => {
+                    tracing::warn!(%err, "ignoring LOGOS_BLOCKCHAIN_CUCUMBER_DEPLOYER");
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for DeployerChoice {
+    type Err = StepError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "local" | "host" => Ok(Self::Local),
+            "compose" => Ok(Self::Compose),
+            other => Err(StepError::UnknownDeployer(other.to_owned())),
+        }
+    }
+}
+
+/// Per-scenario state: the builder being assembled by `Given`/`When` steps
+/// and, once a `Then` step needs it, the finished run.
+///
+/// The run handle is kept until the world is dropped so post-run checks can
+/// still query the nodes; dropping it tears the deployment down.
+#[derive(World)]
+#[world(init = Self::new)]
+pub struct ScenarioWorld {
+    deployer: DeployerChoice,
+    builder: Option<StepScenarioBuilder>,
+    outcome: Option<Result<RunHandle, String>>,
+}
+
+impl fmt::Debug for ScenarioWorld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScenarioWorld")
+            .field("deployer", &self.deployer)
+            .field("configured", &self.builder.is_some())
+            .field(
+                "outcome",
+                &self.outcome.as_ref().map(|outcome| outcome.is_ok()),
+            )
+            .finish()
+    }
+}
+
+impl ScenarioWorld {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            deployer: DeployerChoice::from_env(),
+            builder: None,
+            outcome: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn deployer(&self) -> DeployerChoice {
+        self.deployer
+    }
+
+    pub fn set_deployer(&mut self, deployer: DeployerChoice) -> Result<(), StepError> {
+        self.ensure_not_run()?;
+        self.deployer = deployer;
+        Ok(())
+    }
+
+    /// Starts a fresh scenario, replacing anything configured so far.
+    pub fn start_scenario(&mut self, builder: StepScenarioBuilder) -> Result<(), StepError> {
+        self.ensure_not_run()?;
+        self.builder = Some(builder);
+        Ok(())
+    }
+
+    /// Applies `f` to the scenario builder.
+    pub fn update_scenario(
+        &mut self,
+        f: impl FnOnce(StepScenarioBuilder) -> StepScenarioBuilder,
+    ) -> Result<(), StepError> {
+        self.ensure_not_run()?;
+        let builder = self.builder.take().ok_or(StepError::MissingTopology)?;
+        self.builder = Some(f(builder));
+        Ok(())
+    }
+
+    /// Builds, deploys and runs the scenario on first use and returns the
+    /// finished run. Later calls return the same outcome.
+    pub async fn run(&mut self) -> Result<&RunHandle, StepError> {
+        if self.outcome.is_none() {
+            let builder = self.builder.take().ok_or(StepError::MissingTopology)?;
+            let outcome = Self::execute(self.deployer, builder).await;
+            self.outcome = Some(outcome.map_err(|err| err.to_string()));
+        }
+
+        match &self.outcome {
+            Some(Ok(handle)) => Ok(handle),
+            Some(Err(message)) => Err(StepError::Failed(message.clone())),
+            None => Err(StepError::MissingTopology),
+        }
+    }
+
+    async fn execute(
+        deployer: DeployerChoice,
+        builder: StepScenarioBuilder,
+    ) -> Result<RunHandle, StepError> {
+        let mut plan = builder
+            .build()
+            .map_err(|err| StepError::Build(err.to_string()))?;
+        info!(?deployer, "deploying cucumber scenario");
+
+        let runner = match deployer {
+            DeployerChoice::Local => LocalDeployer::default()
+                .deploy(&plan)
+                .await
+                .map_err(|err| StepError::Deploy(err.to_string()))?,
+            DeployerChoice::Compose => ComposeDeployer::new()
+                .deploy(&plan)
+                .await
+                .map_err(|err| StepError::Deploy(err.to_string()))?,
+        };
+
+        runner
+            .run(&mut plan)
+            .await
+            .map_err(|err| StepError::Run(err.to_string()))
+    }
+
+    fn ensure_not_run(&self) -> Result<(), StepError> {
+        if self.outcome.is_some() {
+            return Err(StepError::AlreadyRan);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+//! Runs the feature files in `examples/cucumber/features`.
+//!
+//! Features start real nodes, so the harness only runs when
+//! `LOGOS_BLOCKCHAIN_CUCUMBER_RUN=true`:
+//!
+//! ```bash
+//! LOGOS_BLOCKCHAIN_CUCUMBER_RUN=true cargo test -p testing-framework-cucumber --test features
+//! ```
+//!
+//! Scenarios tagged `@compose` only run when
+//! `LOGOS_BLOCKCHAIN_CUCUMBER_DEPLOYER=compose`.
+
+use std::path::Path;
+
+use cucumber::World as _;
+use testing_framework_cucumber::{DeployerChoice, ScenarioWorld};
+
+const COMPOSE_TAG: &str = "compose";
+
+#[tokio::main]
+async fn main() {
+    if !testing_framework_env::cucumber_run_features() {
+        println!("skipping feature files; set LOGOS_BLOCKCHAIN_CUCUMBER_RUN=true to run them");
+        return;
+    }
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .try_init();
+
+    let features = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/cucumber/features");
+    let compose = DeployerChoice::from_env() == DeployerChoice::Compose;
+
+    ScenarioWorld::cucumber()
+        .max_concurrent_scenarios(1)
+        .fail_on_skipped()
+        .filter_run_and_exit(features, move |feature, _, scenario| {
+            let tagged_compose = feature
+                .tags
+                .iter()
+                .chain(&scenario.tags)
+                .any(|tag| tag == COMPOSE_TAG);
+            compose || !tagged_compose
+        })
+        .await;
+}
@@ -1,5 +1,5 @@
 use testing_framework_core::{
-    scenario::{BlockFeed, BlockFeedTask, NodeClients},
+    scenario::{BlockFeed, BlockFeedMode, BlockFeedTask, NodeClients},
     topology::generation::GeneratedTopology,
 };
 use tracing::info;
@@ -45,9 +45,10 @@ impl ClientBuilder {
     pub async fn start_block_feed(
         &self,
         node_clients: &NodeClients,
+        mode: BlockFeedMode,
         environment: &mut StackEnvironment,
     ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
-        let pair = match spawn_block_feed_with_retry(node_clients).await {
+        let pair = match spawn_block_feed_with_retry(node_clients, mode).await {
             Ok(pair) => pair,
             Err(err) => {
                 return Err(fail_deploy_step(
@@ -6,8 +6,8 @@ pub mod setup;
 
 use async_trait::async_trait;
 use testing_framework_core::scenario::{
-    BlockFeedTask, CleanupGuard, Deployer, ObservabilityCapabilityProvider, RequiresNodeControl,
-    Runner, Scenario,
+    BlockFeedTask, CleanupGuard, Deployer, ExitWatchTask, ObservabilityCapabilityProvider,
+    RequiresNodeControl, Runner, Scenario,
 };
 
 use crate::{errors::ComposeRunnerError, lifecycle::cleanup::RunnerCleanup};
@@ -56,19 +56,29 @@ where
 pub(super) struct ComposeCleanupGuard {
     environment: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
+    supervisor: Option<ExitWatchTask>,
 }
 
 impl ComposeCleanupGuard {
-    const fn new(environment: RunnerCleanup, block_feed: BlockFeedTask) -> Self {
+    const fn new(
+        environment: RunnerCleanup,
+        block_feed: BlockFeedTask,
+        supervisor: ExitWatchTask,
+    ) -> Self {
         Self {
             environment,
             block_feed: Some(block_feed),
+            supervisor: Some(supervisor),
         }
     }
 }
 
 impl CleanupGuard for ComposeCleanupGuard {
     fn cleanup(mut self: Box<Self>) {
+        // Stop watching first; tearing the stack down exits every container.
+        if let Some(supervisor) = self.supervisor.take() {
+            CleanupGuard::cleanup(Box::new(supervisor));
+        }
         if let Some(block_feed) = self.block_feed.take() {
             CleanupGuard::cleanup(Box::new(block_feed));
         }
@@ -79,8 +89,13 @@ impl CleanupGuard for ComposeCleanupGuard {
 pub(super) fn make_cleanup_guard(
     environment: RunnerCleanup,
     block_feed: BlockFeedTask,
+    supervisor: ExitWatchTask,
 ) -> Box<dyn CleanupGuard> {
-    Box::new(ComposeCleanupGuard::new(environment, block_feed))
+    Box::new(ComposeCleanupGuard::new(
+        environment,
+        block_feed,
+        supervisor,
+    ))
 }
 
 #[cfg(test)]
@@ -118,6 +133,7 @@ mod tests {
 
         let configs = create_node_configs(
             &topology.config().consensus_params,
+            &topology.config().network_params,
             &tracing_settings,
             &topology.config().wallet_config,
             Some(topology.nodes().iter().map(|node| node.id).collect()),
@@ -128,6 +144,7 @@ mod tests {
                     .map(|node| node.blend_port)
                     .collect(),
             ),
+            &topology.config().node_specs,
             hosts,
         )
         .expect("cfgsync config generation should succeed");
@@ -177,6 +194,7 @@ mod tests {
 
         let configs = create_node_configs(
             &topology.config().consensus_params,
+            &topology.config().network_params,
             &tracing_settings,
             &topology.config().wallet_config,
             Some(topology.nodes().iter().map(|node| node.id).collect()),
@@ -187,6 +205,7 @@ mod tests {
                     .map(|node| node.blend_port)
                     .collect(),
             ),
+            &topology.config().node_specs,
             hosts,
         )
         .expect("cfgsync config generation should succeed");
@@ -220,6 +239,7 @@ mod tests {
 
         let configs = create_node_configs(
             &topology.config().consensus_params,
+            &topology.config().network_params,
             &tracing_settings,
             &topology.config().wallet_config,
             Some(topology.nodes().iter().map(|node| node.id).collect()),
@@ -230,6 +250,7 @@ mod tests {
                     .map(|node| node.blend_port)
                     .collect(),
             ),
+            &topology.config().node_specs,
             hosts,
         )
         .expect("cfgsync config generation should succeed");
@@ -258,6 +279,7 @@ mod tests {
 
         let configs = create_node_configs(
             &topology.config().consensus_params,
+            &topology.config().network_params,
             &tracing_settings,
             &topology.config().wallet_config,
             Some(topology.nodes().iter().map(|node| node.id).collect()),
@@ -268,6 +290,7 @@ mod tests {
                     .map(|node| node.blend_port)
                     .collect(),
             ),
+            &topology.config().node_specs,
             hosts,
         )
         .expect("cfgsync config generation should succeed");
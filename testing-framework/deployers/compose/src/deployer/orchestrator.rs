@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use testing_framework_core::scenario::{
-    NodeControlHandle, ObservabilityCapabilityProvider, ObservabilityInputs, RequiresNodeControl,
-    RunContext, Runner, Scenario,
+    ExitWatchTask, NodeControlHandle, NodeExits, ObservabilityCapabilityProvider,
+    ObservabilityInputs, RequiresNodeControl, RunContext, Runner, Scenario,
 };
 use tracing::info;
 
@@ -15,7 +15,11 @@ use super::{
     setup::{DeploymentContext, DeploymentSetup},
 };
 use crate::{
-    docker::control::ComposeNodeControl,
+    descriptor::node_instance_name,
+    docker::{
+        control::ComposeNodeControl,
+        supervisor::{ExpectedDown, supervise_services},
+    },
     errors::ComposeRunnerError,
     infrastructure::{
         environment::StackEnvironment,
@@ -78,8 +82,10 @@ impl DeploymentOrchestrator {
         let node_clients = client_builder
             .build_node_clients(&descriptors, &host_ports, &host, &mut environment)
             .await?;
+        node_clients.reseed(scenario.seed());
         let telemetry = observability.telemetry_handle()?;
-        let node_control = self.maybe_node_control::<Caps>(&environment);
+        let expected_down = ExpectedDown::default();
+        let node_control = self.maybe_node_control::<Caps>(&environment, &expected_down);
 
         log_observability_endpoints(&observability);
         log_profiling_urls(&host, &host_ports);
@@ -87,19 +93,25 @@ impl DeploymentOrchestrator {
         maybe_print_endpoints(&observability, &host, &host_ports);
 
         let (block_feed, block_feed_guard) = client_builder
-            .start_block_feed(&node_clients, &mut environment)
+            .start_block_feed(&node_clients, scenario.block_feed_mode(), &mut environment)
             .await?;
-        let cleanup_guard = make_cleanup_guard(environment.into_cleanup()?, block_feed_guard);
+        let node_exits = NodeExits::default();
+        let supervisor =
+            supervise_environment(&environment, node_count, expected_down, node_exits.clone());
+        let cleanup_guard =
+            make_cleanup_guard(environment.into_cleanup()?, block_feed_guard, supervisor);
 
         let context = RunContext::new(
             descriptors,
             None,
             node_clients,
             scenario.duration(),
+            scenario.seed(),
             telemetry,
             block_feed,
             node_control,
-        );
+        )
+        .with_node_exits(node_exits);
 
         info!(
             nodes = node_count,
@@ -115,6 +127,7 @@ impl DeploymentOrchestrator {
     fn maybe_node_control<Caps>(
         &self,
         environment: &StackEnvironment,
+        expected_down: &ExpectedDown,
     ) -> Option<Arc<dyn NodeControlHandle>>
     where
         Caps: RequiresNodeControl + Send + Sync,
@@ -123,11 +136,28 @@ impl DeploymentOrchestrator {
             Arc::new(ComposeNodeControl {
                 compose_file: environment.compose_path().to_path_buf(),
                 project_name: environment.project_name().to_owned(),
+                expected_down: expected_down.clone(),
+                partitions: Mutex::default(),
             }) as Arc<dyn NodeControlHandle>
         })
     }
 }
 
+fn supervise_environment(
+    environment: &StackEnvironment,
+    node_count: usize,
+    expected_down: ExpectedDown,
+    exits: NodeExits,
+) -> ExitWatchTask {
+    supervise_services(
+        environment.compose_path().to_path_buf(),
+        environment.project_name().to_owned(),
+        (0..node_count).map(node_instance_name).collect(),
+        expected_down,
+        exits,
+    )
+}
+
 fn resolve_observability_inputs<Caps>(
     scenario: &Scenario<Caps>,
 ) -> Result<ObservabilityInputs, ComposeRunnerError>
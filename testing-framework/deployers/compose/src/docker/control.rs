@@ -1,15 +1,31 @@
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
     time::Duration,
 };
 
-use testing_framework_core::scenario::{DynError, NodeControlHandle};
-use tokio::process::Command;
-use tracing::info;
+use anyhow::anyhow;
+use testing_framework_core::{
+    adjust_timeout,
+    scenario::{DynError, NodeControlHandle},
+};
+use tokio::{process::Command, time::timeout};
+use tracing::{info, warn};
 
-use crate::{docker::commands::run_docker_command, errors::ComposeRunnerError};
+use crate::{
+    docker::{
+        commands::{ComposeCommandError, run_docker_command},
+        supervisor::ExpectedDown,
+    },
+    errors::ComposeRunnerError,
+};
 
 const COMPOSE_RESTART_TIMEOUT: Duration = Duration::from_secs(120);
+const DOCKER_NETWORK_TIMEOUT: Duration = Duration::from_secs(30);
+/// Label carrying the compose project on networks created for partitions, so
+/// teardown can find them.
+const PARTITION_LABEL: &str = "io.logos.testing-framework.partition";
 
 pub async fn restart_compose_service(
     compose_file: &Path,
@@ -30,24 +46,464 @@ pub async fn restart_compose_service(
     info!(service, project = project_name, compose_file = %compose_file.display(), "restarting compose service");
     run_docker_command(
         command,
-        testing_framework_core::adjust_timeout(COMPOSE_RESTART_TIMEOUT),
+        adjust_timeout(COMPOSE_RESTART_TIMEOUT),
         description,
     )
     .await
     .map_err(ComposeRunnerError::Compose)
 }
 
-/// Compose-specific node control handle for restarting nodes.
+/// Stop a compose service with SIGTERM, letting docker kill it once `grace`
+/// has passed.
+pub async fn terminate_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    grace: Duration,
+) -> Result<(), ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("stop")
+        .arg("-t")
+        .arg(grace.as_secs().max(1).to_string())
+        .arg(service);
+
+    info!(
+        service,
+        project = project_name,
+        ?grace,
+        "terminating compose service"
+    );
+    run_docker_command(
+        command,
+        adjust_timeout(COMPOSE_RESTART_TIMEOUT) + grace,
+        "docker compose stop",
+    )
+    .await
+    .map_err(ComposeRunnerError::Compose)
+}
+
+/// Send `signal` (e.g. `KILL`) to the container backing a compose service.
+pub async fn signal_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    signal: &str,
+) -> Result<(), ComposeRunnerError> {
+    let container = resolve_service_container(compose_file, project_name, service).await?;
+
+    let mut command = Command::new("docker");
+    command.arg("kill").arg("-s").arg(signal).arg(&container);
+
+    info!(service, %container, signal, "signalling compose service");
+    run_docker_command(
+        command,
+        adjust_timeout(DOCKER_NETWORK_TIMEOUT),
+        "docker kill",
+    )
+    .await
+    .map_err(ComposeRunnerError::Compose)
+}
+
+/// Pause (or unpause) the container backing a compose service.
+pub async fn set_compose_service_paused(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    paused: bool,
+) -> Result<(), ComposeRunnerError> {
+    let container = resolve_service_container(compose_file, project_name, service).await?;
+    let action = if paused { "pause" } else { "unpause" };
+
+    let mut command = Command::new("docker");
+    command.arg(action).arg(&container);
+
+    let description = if paused {
+        "docker pause"
+    } else {
+        "docker unpause"
+    };
+    info!(service, %container, action, "changing compose service run state");
+    run_docker_command(command, adjust_timeout(DOCKER_NETWORK_TIMEOUT), description)
+        .await
+        .map_err(ComposeRunnerError::Compose)
+}
+
+/// Name of the default network docker compose creates for a project.
+fn default_network_name(project_name: &str) -> String {
+    format!("{project_name}_default")
+}
+
+/// Resolve the container id backing a running compose service.
+async fn resolve_service_container(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<String, ComposeRunnerError> {
+    lookup_service_container(compose_file, project_name, service, false).await
+}
+
+/// Resolve the container id backing a compose service, including stopped
+/// containers when `include_stopped` is set.
+pub(crate) async fn lookup_service_container(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    include_stopped: bool,
+) -> Result<String, ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("ps")
+        .arg("-q");
+    if include_stopped {
+        command.arg("-a");
+    }
+    command.arg(service);
+
+    let output = timeout(adjust_timeout(DOCKER_NETWORK_TIMEOUT), command.output())
+        .await
+        .map_err(|_| ComposeRunnerError::ContainerLookup {
+            service: service.to_owned(),
+            source: anyhow!("docker compose ps timed out"),
+        })?
+        .map_err(|source| ComposeRunnerError::ContainerLookup {
+            service: service.to_owned(),
+            source: source.into(),
+        })?;
+
+    if !output.status.success() {
+        return Err(ComposeRunnerError::ContainerLookup {
+            service: service.to_owned(),
+            source: anyhow!("docker compose ps exited with {}", output.status),
+        });
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_owned)
+        .ok_or_else(|| ComposeRunnerError::ContainerLookup {
+            service: service.to_owned(),
+            source: if include_stopped {
+                anyhow!("no container for service")
+            } else {
+                anyhow!("no running container for service")
+            },
+        })
+}
+
+/// Disconnect (or reconnect) a compose service from `network`.
+pub async fn set_compose_service_connected(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    network: &str,
+    connected: bool,
+) -> Result<(), ComposeRunnerError> {
+    let container = resolve_service_container(compose_file, project_name, service).await?;
+    let action = if connected { "connect" } else { "disconnect" };
+
+    let mut command = Command::new("docker");
+    command.arg("network").arg(action);
+    if connected {
+        // Keep the service reachable by name for peers that resolve it via DNS.
+        command.arg("--alias").arg(service);
+    }
+    command.arg(network).arg(&container);
+
+    let description = if connected {
+        "docker network connect"
+    } else {
+        "docker network disconnect"
+    };
+    info!(service, network, %container, action, "changing compose service network");
+    run_docker_command(command, adjust_timeout(DOCKER_NETWORK_TIMEOUT), description)
+        .await
+        .map_err(ComposeRunnerError::Compose)
+}
+
+/// Create a network for one partition of a compose project.
+async fn create_partition_network(
+    project_name: &str,
+    network: &str,
+) -> Result<(), ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("network")
+        .arg("create")
+        .arg("--label")
+        .arg(format!("{PARTITION_LABEL}={project_name}"))
+        .arg(network);
+
+    info!(
+        project = project_name,
+        network, "creating partition network"
+    );
+    run_docker_command(
+        command,
+        adjust_timeout(DOCKER_NETWORK_TIMEOUT),
+        "docker network create",
+    )
+    .await
+    .map_err(ComposeRunnerError::Compose)
+}
+
+async fn remove_partition_network(network: &str) -> Result<(), ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command.arg("network").arg("rm").arg(network);
+
+    info!(network, "removing partition network");
+    run_docker_command(
+        command,
+        adjust_timeout(DOCKER_NETWORK_TIMEOUT),
+        "docker network rm",
+    )
+    .await
+    .map_err(ComposeRunnerError::Compose)
+}
+
+/// Remove the partition networks left behind by a compose project.
+pub(crate) async fn prune_partition_networks(
+    project_name: &str,
+) -> Result<(), ComposeCommandError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("network")
+        .arg("prune")
+        .arg("--force")
+        .arg("--filter")
+        .arg(format!("label={PARTITION_LABEL}={project_name}"));
+
+    run_docker_command(
+        command,
+        adjust_timeout(DOCKER_NETWORK_TIMEOUT),
+        "docker network prune",
+    )
+    .await
+}
+
+/// Which partition network each partitioned service is attached to.
+#[derive(Debug, Default)]
+pub(crate) struct PartitionNetworks {
+    next: usize,
+    members: HashMap<String, String>,
+}
+
+impl PartitionNetworks {
+    /// Name for the next partition network of the project.
+    fn allocate(&mut self, project_name: &str) -> String {
+        let network = format!("{project_name}_partition-{}", self.next);
+        self.next += 1;
+        network
+    }
+
+    /// Moves `service` into `network`. Returns the network it has to leave:
+    /// a previous partition or the project network.
+    fn join(&mut self, project_name: &str, service: &str, network: &str) -> String {
+        self.members
+            .insert(service.to_owned(), network.to_owned())
+            .unwrap_or_else(|| default_network_name(project_name))
+    }
+
+    /// Removes `service` from its partition. Returns the partition network
+    /// and whether it has no members left.
+    fn leave(&mut self, service: &str) -> Option<(String, bool)> {
+        let network = self.members.remove(service)?;
+        let empty = !self.members.values().any(|member| *member == network);
+        Some((network, empty))
+    }
+}
+
+/// Compose-specific node control handle for restarting, stopping, pausing
+/// and isolating nodes.
+///
+/// Freezing uses `docker pause`, which suspends every process in the
+/// container. Wiped restarts are not supported. Isolation disconnects the
+/// container from the project network. Published ports go through that network
+/// too, so the node API is unreachable from the host until the node is
+/// reconnected. Partitioning moves each group onto a network of its own, so
+/// members of a group still reach each other.
+///
+/// Services it stops are recorded in `expected_down`, so the exit watcher
+/// does not report them.
 pub struct ComposeNodeControl {
     pub(crate) compose_file: PathBuf,
     pub(crate) project_name: String,
+    pub(crate) expected_down: ExpectedDown,
+    pub(crate) partitions: Mutex<PartitionNetworks>,
+}
+
+impl ComposeNodeControl {
+    fn partitions(&self) -> MutexGuard<'_, PartitionNetworks> {
+        self.partitions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Takes `service` out of its partition, removing the partition network
+    /// once it is empty. Returns the network the service was attached to.
+    async fn leave_partition(&self, service: &str) -> Result<String, ComposeRunnerError> {
+        let Some((network, empty)) = self.partitions().leave(service) else {
+            return Ok(default_network_name(&self.project_name));
+        };
+        set_compose_service_connected(
+            &self.compose_file,
+            &self.project_name,
+            service,
+            &network,
+            false,
+        )
+        .await?;
+        if empty && let Err(err) = remove_partition_network(&network).await {
+            warn!(network, error = %err, "failed to remove partition network");
+        }
+        Ok(network)
+    }
 }
 
 #[async_trait::async_trait]
 impl NodeControlHandle for ComposeNodeControl {
     async fn restart_node(&self, name: &str) -> Result<(), DynError> {
-        restart_compose_service(&self.compose_file, &self.project_name, name)
+        let newly_marked = self.expected_down.insert(name);
+        let result = restart_compose_service(&self.compose_file, &self.project_name, name).await;
+        if result.is_ok() || newly_marked {
+            self.expected_down.remove(name);
+        }
+        result.map_err(|err| format!("node restart failed: {err}").into())
+    }
+
+    async fn terminate_node(&self, name: &str, timeout: Duration) -> Result<(), DynError> {
+        self.expected_down.insert(name);
+        terminate_compose_service(&self.compose_file, &self.project_name, name, timeout)
+            .await
+            .map_err(|err| format!("node terminate failed: {err}").into())
+    }
+
+    async fn crash_node(&self, name: &str) -> Result<(), DynError> {
+        self.expected_down.insert(name);
+        signal_compose_service(&self.compose_file, &self.project_name, name, "KILL")
+            .await
+            .map_err(|err| format!("node crash failed: {err}").into())
+    }
+
+    async fn freeze_node(&self, name: &str) -> Result<(), DynError> {
+        set_compose_service_paused(&self.compose_file, &self.project_name, name, true)
             .await
-            .map_err(|err| format!("node restart failed: {err}").into())
+            .map_err(|err| format!("node freeze failed: {err}").into())
+    }
+
+    async fn unfreeze_node(&self, name: &str) -> Result<(), DynError> {
+        set_compose_service_paused(&self.compose_file, &self.project_name, name, false)
+            .await
+            .map_err(|err| format!("node unfreeze failed: {err}").into())
+    }
+
+    async fn isolate_node(&self, name: &str) -> Result<(), DynError> {
+        let default = default_network_name(&self.project_name);
+        let left = self
+            .leave_partition(name)
+            .await
+            .map_err(|err| format!("node isolation failed: {err}"))?;
+        // A partitioned node is already cut off once it leaves its partition.
+        if left != default {
+            return Ok(());
+        }
+        set_compose_service_connected(
+            &self.compose_file,
+            &self.project_name,
+            name,
+            &default,
+            false,
+        )
+        .await
+        .map_err(|err| format!("node isolation failed: {err}").into())
+    }
+
+    async fn partition_nodes(&self, names: &[String]) -> Result<(), DynError> {
+        let network = self.partitions().allocate(&self.project_name);
+        create_partition_network(&self.project_name, &network)
+            .await
+            .map_err(|err| format!("node partition failed: {err}"))?;
+
+        for name in names {
+            let previous = self.partitions().join(&self.project_name, name, &network);
+            set_compose_service_connected(
+                &self.compose_file,
+                &self.project_name,
+                name,
+                &previous,
+                false,
+            )
+            .await
+            .map_err(|err| format!("node partition failed: {err}"))?;
+            set_compose_service_connected(
+                &self.compose_file,
+                &self.project_name,
+                name,
+                &network,
+                true,
+            )
+            .await
+            .map_err(|err| format!("node partition failed: {err}"))?;
+        }
+        Ok(())
+    }
+
+    async fn reconnect_node(&self, name: &str) -> Result<(), DynError> {
+        let network = default_network_name(&self.project_name);
+        self.leave_partition(name)
+            .await
+            .map_err(|err| format!("node reconnect failed: {err}"))?;
+        set_compose_service_connected(&self.compose_file, &self.project_name, name, &network, true)
+            .await
+            .map_err(|err| format!("node reconnect failed: {err}").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartitionNetworks;
+
+    #[test]
+    fn partitions_move_groups_onto_their_own_networks() {
+        let mut partitions = PartitionNetworks::default();
+
+        let mut split = |group: &[&str]| {
+            let network = partitions.allocate("proj");
+            let previous = group
+                .iter()
+                .map(|service| partitions.join("proj", service, &network))
+                .collect::<Vec<_>>();
+            (network, previous)
+        };
+
+        let (left, previous) = split(&["node-0", "node-1"]);
+        assert_eq!(left, "proj_partition-0");
+        assert_eq!(previous, ["proj_default", "proj_default"]);
+
+        let (right, previous) = split(&["node-2", "node-1"]);
+        assert_eq!(right, "proj_partition-1");
+        assert_eq!(previous, ["proj_default", "proj_partition-0"]);
+
+        assert_eq!(
+            partitions.leave("node-0"),
+            Some(("proj_partition-0".to_owned(), true))
+        );
+        assert_eq!(
+            partitions.leave("node-2"),
+            Some(("proj_partition-1".to_owned(), false))
+        );
+        assert_eq!(partitions.leave("node-3"), None);
     }
 }
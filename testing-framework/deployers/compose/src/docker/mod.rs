@@ -1,6 +1,7 @@
 pub mod commands;
 pub mod control;
 pub mod platform;
+pub mod supervisor;
 pub mod workspace;
 
 use std::{env, process::Stdio, time::Duration};
@@ -0,0 +1,196 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime},
+};
+
+use testing_framework_core::{
+    adjust_timeout,
+    scenario::{ExitWatchTask, NodeExit, NodeExits, spawn_exit_watcher},
+};
+use tokio::{process::Command, time::timeout};
+use tracing::debug;
+
+use crate::docker::control::lookup_service_container;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DOCKER_QUERY_TIMEOUT: Duration = Duration::from_secs(15);
+const LOG_TAIL_LINES: usize = 50;
+
+/// Services the node control handle stopped on purpose; clones share the same
+/// set.
+#[derive(Clone, Debug, Default)]
+pub struct ExpectedDown {
+    services: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ExpectedDown {
+    /// Mark `service` as expected to be down. Returns `false` if it already
+    /// was.
+    pub fn insert(&self, service: &str) -> bool {
+        self.lock().insert(service.to_owned())
+    }
+
+    pub fn remove(&self, service: &str) {
+        self.lock().remove(service);
+    }
+
+    #[must_use]
+    pub fn contains(&self, service: &str) -> bool {
+        self.lock().contains(service)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<String>> {
+        self.services
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Container state as reported by `docker inspect`.
+#[derive(Debug, PartialEq, Eq)]
+struct ContainerState {
+    status: String,
+    exit_code: i32,
+    restart_count: u32,
+    finished_at: String,
+}
+
+impl ContainerState {
+    fn parse(raw: &str) -> Option<Self> {
+        let mut fields = raw.split_whitespace();
+        Some(Self {
+            status: fields.next()?.to_owned(),
+            exit_code: fields.next()?.parse().ok()?,
+            restart_count: fields.next()?.parse().ok()?,
+            finished_at: fields.next()?.to_owned(),
+        })
+    }
+
+    /// What the exit is reported as, or `None` while the container runs and
+    /// was never restarted by docker.
+    fn exit_instance(&self, container: &str) -> Option<String> {
+        let short = &container[..container.len().min(12)];
+        if matches!(self.status.as_str(), "exited" | "dead") {
+            Some(format!("container {short} exited at {}", self.finished_at))
+        } else if self.restart_count > 0 {
+            Some(format!("container {short} restart #{}", self.restart_count))
+        } else {
+            None
+        }
+    }
+}
+
+/// Watch the node containers of a compose project. A container that exited,
+/// or that docker restarted under its restart policy, is reported unless the
+/// service is in `expected_down`.
+pub fn supervise_services(
+    compose_file: PathBuf,
+    project_name: String,
+    services: Vec<String>,
+    expected_down: ExpectedDown,
+    exits: NodeExits,
+) -> ExitWatchTask {
+    let target = Arc::new((compose_file, project_name, services));
+
+    spawn_exit_watcher(exits, POLL_INTERVAL, move || {
+        let target = Arc::clone(&target);
+        let expected_down = expected_down.clone();
+        async move {
+            let (compose_file, project_name, services) = &*target;
+            let mut exited = Vec::new();
+            for service in services {
+                if expected_down.contains(service) {
+                    continue;
+                }
+                let Some(exit) = check_service(compose_file, project_name, service).await else {
+                    continue;
+                };
+                // The control handle may have stopped the service while it was
+                // being inspected.
+                if !expected_down.contains(service) {
+                    exited.push(exit);
+                }
+            }
+            exited
+        }
+    })
+}
+
+async fn check_service(compose_file: &Path, project_name: &str, service: &str) -> Option<NodeExit> {
+    let container = lookup_service_container(compose_file, project_name, service, true)
+        .await
+        .inspect_err(|err| debug!(service, error = %err, "node container lookup failed"))
+        .ok()?;
+
+    let raw = docker_output(&[
+        "inspect",
+        "-f",
+        "{{.State.Status}} {{.State.ExitCode}} {{.RestartCount}} {{.State.FinishedAt}}",
+        &container,
+    ])
+    .await?;
+    let state = ContainerState::parse(&raw)?;
+    let instance = state.exit_instance(&container)?;
+
+    let tail = LOG_TAIL_LINES.to_string();
+    let log_tail = docker_output(&["logs", "--tail", &tail, &container])
+        .await
+        .map(|logs| logs.lines().map(str::to_owned).collect())
+        .unwrap_or_default();
+
+    Some(NodeExit {
+        node: service.to_owned(),
+        instance,
+        observed_at: SystemTime::now(),
+        code: Some(state.exit_code),
+        signal: None,
+        log_tail,
+    })
+}
+
+/// Stdout and stderr of a docker command, or `None` if it failed.
+async fn docker_output(args: &[&str]) -> Option<String> {
+    let output = timeout(
+        adjust_timeout(DOCKER_QUERY_TIMEOUT),
+        Command::new("docker").args(args).output(),
+    )
+    .await
+    .ok()?
+    .inspect_err(|err| debug!(error = %err, ?args, "docker query failed to spawn"))
+    .ok()?;
+
+    if !output.status.success() {
+        debug!(status = %output.status, ?args, "docker query failed");
+        return None;
+    }
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContainerState;
+
+    #[test]
+    fn reports_exited_and_restarted_containers() {
+        let running = ContainerState::parse("running 0 0 0001-01-01T00:00:00Z").unwrap();
+        assert_eq!(running.exit_instance("abc"), None);
+
+        let exited = ContainerState::parse("exited 137 0 2025-01-01T00:00:00Z").unwrap();
+        assert_eq!(exited.exit_code, 137);
+        assert_eq!(
+            exited.exit_instance("0123456789abcdef").as_deref(),
+            Some("container 0123456789ab exited at 2025-01-01T00:00:00Z")
+        );
+
+        let restarted = ContainerState::parse("running 0 2 2025-01-01T00:00:00Z").unwrap();
+        assert_eq!(
+            restarted.exit_instance("abc").as_deref(),
+            Some("container abc restart #2")
+        );
+    }
+}
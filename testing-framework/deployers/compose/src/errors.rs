@@ -13,6 +13,8 @@ use crate::{docker::commands::ComposeCommandError, infrastructure::template::Tem
 pub enum ComposeRunnerError {
     #[error("compose runner requires at least one node (nodes={nodes})")]
     MissingNode { nodes: usize },
+    #[error("framework time is only supported by the local deployer")]
+    FrameworkTimeUnsupported,
     #[error("docker does not appear to be available on this host")]
     DockerUnavailable,
     #[error("failed to resolve host port for {service} container port {container_port}: {source}")]
@@ -22,6 +24,12 @@ pub enum ComposeRunnerError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("failed to resolve container for service {service}: {source}")]
+    ContainerLookup {
+        service: String,
+        #[source]
+        source: anyhow::Error,
+    },
     #[error(transparent)]
     Workspace(#[from] WorkspaceError),
     #[error(transparent)]
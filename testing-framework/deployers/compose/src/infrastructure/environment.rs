@@ -131,7 +131,8 @@ impl StackEnvironment {
     }
 }
 
-/// Verifies the topology has at least one node so compose can start.
+/// Verifies the topology has at least one node so compose can start, and
+/// does not ask for the local-only framework clock.
 pub fn ensure_supported_topology(
     descriptors: &GeneratedTopology,
 ) -> Result<(), ComposeRunnerError> {
@@ -139,6 +140,9 @@ pub fn ensure_supported_topology(
     if nodes == 0 {
         return Err(ComposeRunnerError::MissingNode { nodes });
     }
+    if descriptors.config().framework_time {
+        return Err(ComposeRunnerError::FrameworkTimeUnsupported);
+    }
     Ok(())
 }
 
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use testing_framework_core::scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed};
+use testing_framework_core::scenario::{
+    BlockFeed, BlockFeedMode, BlockFeedTask, NodeClients, spawn_block_feed,
+    spawn_multi_node_block_feed,
+};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -11,28 +14,36 @@ const BLOCK_FEED_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    mode: BlockFeedMode,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
+    if mode == BlockFeedMode::AllNodes {
+        return spawn_multi_node_block_feed(node_clients.clone())
+            .await
+            .map_err(|source| ComposeRunnerError::BlockFeed { source });
+    }
+
     debug!(
         nodes = node_clients.node_clients().len(),
         "selecting node client for block feed"
     );
 
-    let block_source_client = node_clients
-        .random_node()
+    let (index, block_source_client) = node_clients
+        .random_indexed_node()
         .ok_or(ComposeRunnerError::BlockFeedMissing)?;
 
-    spawn_block_feed(block_source_client)
+    spawn_block_feed(block_source_client, index)
         .await
         .map_err(|source| ComposeRunnerError::BlockFeed { source })
 }
 
 pub async fn spawn_block_feed_with_retry(
     node_clients: &NodeClients,
+    mode: BlockFeedMode,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
     let mut last_err = None;
     for attempt in 1..=BLOCK_FEED_MAX_ATTEMPTS {
-        info!(attempt, "starting block feed");
-        match spawn_block_feed_with(node_clients).await {
+        info!(attempt, ?mode, "starting block feed");
+        match spawn_block_feed_with(node_clients, mode).await {
             Ok(result) => {
                 info!(attempt, "block feed established");
                 return Ok(result);
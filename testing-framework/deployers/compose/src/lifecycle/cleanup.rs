@@ -6,6 +6,7 @@ use tracing::{debug, info, warn};
 use crate::{
     docker::{
         commands::{ComposeCommandError, compose_down},
+        control::prune_partition_networks,
         workspace::ComposeWorkspace,
     },
     infrastructure::cfgsync::CfgsyncServerHandle,
@@ -68,7 +69,11 @@ fn run_compose_down_blocking(
                 command: "docker compose down".into(),
                 source: std::io::Error::new(std::io::ErrorKind::Other, err),
             })?
-            .block_on(compose_down(&compose_file, &project_name, &root))
+            .block_on(async {
+                compose_down(&compose_file, &project_name, &root).await?;
+                // Partition networks are not part of the compose file.
+                prune_partition_networks(&project_name).await
+            })
     });
 
     handle.join().map_err(|_| ComposeCommandError::Spawn {
@@ -3,8 +3,8 @@ use async_trait::async_trait;
 use kube::Client;
 use testing_framework_core::{
     scenario::{
-        BlockFeedTask, CleanupGuard, Deployer, MetricsError, ObservabilityCapability,
-        ObservabilityInputs, RunContext, Runner, Scenario,
+        BlockFeedTask, CleanupGuard, Deployer, ExitWatchTask, MetricsError, NodeExits,
+        ObservabilityCapability, ObservabilityInputs, RunContext, Runner, Scenario,
     },
     topology::generation::GeneratedTopology,
 };
@@ -20,7 +20,9 @@ use crate::{
         },
         helm::HelmError,
     },
-    lifecycle::{block_feed::spawn_block_feed_with, cleanup::RunnerCleanup},
+    lifecycle::{
+        block_feed::spawn_block_feed_with, cleanup::RunnerCleanup, supervisor::supervise_pods,
+    },
     wait::{ClusterWaitError, PortForwardHandle},
 };
 
@@ -58,6 +60,8 @@ impl K8sDeployer {
 pub enum K8sRunnerError {
     #[error("kubernetes runner requires at least one node (nodes={nodes})")]
     UnsupportedTopology { nodes: usize },
+    #[error("framework time is only supported by the local deployer")]
+    FrameworkTimeUnsupported,
     #[error("failed to initialise kubernetes client: {source}")]
     ClientInit {
         #[source]
@@ -126,6 +130,9 @@ fn ensure_supported_topology(descriptors: &GeneratedTopology) -> Result<(), K8sR
     if nodes == 0 {
         return Err(K8sRunnerError::UnsupportedTopology { nodes });
     }
+    if descriptors.config().framework_time {
+        return Err(K8sRunnerError::FrameworkTimeUnsupported);
+    }
     Ok(())
 }
 
@@ -169,11 +176,12 @@ async fn deploy_with_observability<Caps>(
 
     info!("building node clients");
     let node_clients = build_node_clients_or_fail(&mut cluster).await?;
+    node_clients.reseed(scenario.seed());
 
     let telemetry = build_telemetry_or_fail(&mut cluster, &observability).await?;
 
     let (block_feed, block_feed_guard) =
-        spawn_block_feed_or_fail(&mut cluster, &node_clients).await?;
+        spawn_block_feed_or_fail(&mut cluster, &node_clients, scenario.block_feed_mode()).await?;
 
     if let Some(url) = observability.metrics_query_url.as_ref() {
         info!(
@@ -192,6 +200,7 @@ async fn deploy_with_observability<Caps>(
         descriptors,
         node_clients,
         scenario.duration(),
+        scenario.seed(),
         telemetry,
         block_feed,
         block_feed_guard,
@@ -293,8 +302,9 @@ async fn build_telemetry_or_fail(
 async fn spawn_block_feed_or_fail(
     cluster: &mut Option<ClusterEnvironment>,
     node_clients: &testing_framework_core::scenario::NodeClients,
+    mode: testing_framework_core::scenario::BlockFeedMode,
 ) -> Result<(testing_framework_core::scenario::BlockFeed, BlockFeedTask), K8sRunnerError> {
-    match spawn_block_feed_with(node_clients).await {
+    match spawn_block_feed_with(node_clients, mode).await {
         Ok(pair) => Ok(pair),
         Err(err) => {
             fail_cluster(cluster, "failed to initialize block feed").await;
@@ -344,6 +354,7 @@ fn finalize_runner(
     descriptors: GeneratedTopology,
     node_clients: testing_framework_core::scenario::NodeClients,
     duration: std::time::Duration,
+    seed: u64,
     telemetry: testing_framework_core::scenario::Metrics,
     block_feed: testing_framework_core::scenario::BlockFeed,
     block_feed_guard: BlockFeedTask,
@@ -354,12 +365,19 @@ fn finalize_runner(
         .ok_or_else(|| K8sRunnerError::InternalInvariant {
             message: "cluster should still be available".to_owned(),
         })?;
+    let node_exits = NodeExits::default();
+    let supervisor = supervise_pods(
+        environment.client().clone(),
+        environment.namespace().to_owned(),
+        node_exits.clone(),
+    );
     let (cleanup, port_forwards) = environment.into_cleanup()?;
 
     let cleanup_guard: Box<dyn CleanupGuard> = Box::new(K8sCleanupGuard::new(
         cleanup,
         block_feed_guard,
         port_forwards,
+        supervisor,
     ));
 
     let context = RunContext::new(
@@ -367,10 +385,12 @@ fn finalize_runner(
         None,
         node_clients,
         duration,
+        seed,
         telemetry,
         block_feed,
         None,
-    );
+    )
+    .with_node_exits(node_exits);
 
     info!(
         nodes = node_count,
@@ -385,6 +405,7 @@ struct K8sCleanupGuard {
     cleanup: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
     port_forwards: Vec<PortForwardHandle>,
+    supervisor: Option<ExitWatchTask>,
 }
 
 impl K8sCleanupGuard {
@@ -392,17 +413,23 @@ impl K8sCleanupGuard {
         cleanup: RunnerCleanup,
         block_feed: BlockFeedTask,
         port_forwards: Vec<PortForwardHandle>,
+        supervisor: ExitWatchTask,
     ) -> Self {
         Self {
             cleanup,
             block_feed: Some(block_feed),
             port_forwards,
+            supervisor: Some(supervisor),
         }
     }
 }
 
 impl CleanupGuard for K8sCleanupGuard {
     fn cleanup(mut self: Box<Self>) {
+        // Stop watching first; uninstalling the release terminates every pod.
+        if let Some(supervisor) = self.supervisor.take() {
+            CleanupGuard::cleanup(Box::new(supervisor));
+        }
         if let Some(block_feed) = self.block_feed.take() {
             CleanupGuard::cleanup(Box::new(block_feed));
         }
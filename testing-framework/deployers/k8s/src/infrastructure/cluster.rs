@@ -89,7 +89,10 @@ impl ClusterEnvironment {
         Ok((cleanup, self.port_forwards))
     }
 
-    #[allow(dead_code)]
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -1,11 +1,22 @@
-use testing_framework_core::scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed};
+use testing_framework_core::scenario::{
+    BlockFeed, BlockFeedMode, BlockFeedTask, NodeClients, spawn_block_feed,
+    spawn_multi_node_block_feed,
+};
 use tracing::{debug, info};
 
 use crate::deployer::K8sRunnerError;
 
 pub async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    mode: BlockFeedMode,
 ) -> Result<(BlockFeed, BlockFeedTask), K8sRunnerError> {
+    if mode == BlockFeedMode::AllNodes {
+        info!("starting multi-node block feed");
+        return spawn_multi_node_block_feed(node_clients.clone())
+            .await
+            .map_err(|source| K8sRunnerError::BlockFeed { source });
+    }
+
     debug!(
         nodes = node_clients.node_clients().len(),
         "selecting node client for block feed"
@@ -15,11 +26,10 @@ pub async fn spawn_block_feed_with(
         .node_clients()
         .into_iter()
         .next()
-        .or_else(|| node_clients.any_client())
         .ok_or(K8sRunnerError::BlockFeedMissing)?;
 
     info!("starting block feed");
-    spawn_block_feed(block_source_client)
+    spawn_block_feed(block_source_client, 0)
         .await
         .map_err(|source| K8sRunnerError::BlockFeed { source })
 }
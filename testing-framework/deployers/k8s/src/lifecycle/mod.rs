@@ -1,4 +1,5 @@
 pub mod block_feed;
 pub mod cleanup;
 pub mod logs;
+pub mod supervisor;
 pub mod wait;
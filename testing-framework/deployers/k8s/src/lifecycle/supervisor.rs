@@ -0,0 +1,263 @@
+use std::{
+    fmt::Write as _,
+    time::{Duration, SystemTime},
+};
+
+use k8s_openapi::api::core::v1::{ContainerStateTerminated, Pod};
+use kube::{
+    Api, Client,
+    api::{ListParams, LogParams},
+};
+use testing_framework_core::scenario::{ExitWatchTask, NodeExit, NodeExits, spawn_exit_watcher};
+use tracing::debug;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const LOG_TAIL_LINES: i64 = 50;
+const NODE_CONTAINER: &str = "node";
+const NODE_SELECTOR: &str = "nomos/logical-role=node";
+const NODE_INDEX_LABEL: &str = "nomos/node-index";
+
+/// Watch the node pods of a namespace. A node container that terminated, or
+/// that the kubelet restarted, is reported as an unexpected exit; the k8s
+/// runner does not stop nodes on purpose. Pods that are being deleted are
+/// skipped.
+pub fn supervise_pods(client: Client, namespace: String, exits: NodeExits) -> ExitWatchTask {
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+
+    spawn_exit_watcher(exits, POLL_INTERVAL, move || {
+        let pods = pods.clone();
+        async move {
+            let list = match pods
+                .list(&ListParams::default().labels(NODE_SELECTOR))
+                .await
+            {
+                Ok(list) => list,
+                Err(err) => {
+                    debug!(error = ?err, "failed to list node pods");
+                    return Vec::new();
+                }
+            };
+
+            let mut exited = Vec::new();
+            for pod in list {
+                if let Some(exit) = pod_exit(&pods, &pod).await {
+                    exited.push(exit);
+                }
+            }
+            exited
+        }
+    })
+}
+
+async fn pod_exit(pods: &Api<Pod>, pod: &Pod) -> Option<NodeExit> {
+    let exit = terminated_run(pod)?;
+
+    Some(NodeExit {
+        node: exit.node,
+        instance: exit.instance,
+        observed_at: SystemTime::now(),
+        code: Some(exit.terminated.exit_code),
+        signal: exit.terminated.signal,
+        log_tail: log_tail(pods, exit.pod, exit.previous, exit.terminated).await,
+    })
+}
+
+/// A finished run of a pod's node container.
+#[derive(Debug)]
+struct PodExit<'a> {
+    pod: &'a str,
+    node: String,
+    instance: String,
+    /// Whether the run is the container's previous one, i.e. its logs are
+    /// only available with `previous = true`.
+    previous: bool,
+    terminated: &'a ContainerStateTerminated,
+}
+
+/// The node container's finished run, or `None` while it runs and was never
+/// restarted. Pods that are being deleted are skipped: their containers are
+/// stopped on purpose.
+fn terminated_run(pod: &Pod) -> Option<PodExit<'_>> {
+    if pod.metadata.deletion_timestamp.is_some() {
+        return None;
+    }
+    let name = pod.metadata.name.as_deref()?;
+    let index = pod.metadata.labels.as_ref()?.get(NODE_INDEX_LABEL)?;
+    let status = pod
+        .status
+        .as_ref()?
+        .container_statuses
+        .as_ref()?
+        .iter()
+        .find(|status| status.name == NODE_CONTAINER)?;
+
+    // A running container that was restarted ended its previous run. Runs
+    // are numbered by restart count, so a crash seen both while terminated
+    // and after the restart is reported once.
+    let current = status
+        .state
+        .as_ref()
+        .and_then(|state| state.terminated.as_ref());
+    let (terminated, previous, run) = match current {
+        Some(terminated) => (terminated, false, status.restart_count),
+        None if status.restart_count > 0 => (
+            status.last_state.as_ref()?.terminated.as_ref()?,
+            true,
+            status.restart_count - 1,
+        ),
+        None => return None,
+    };
+
+    let mut instance = format!("pod {name} run #{run}");
+    if let Some(reason) = &terminated.reason {
+        let _ = write!(instance, " ({reason})");
+    }
+
+    Some(PodExit {
+        pod: name,
+        node: format!("node-{index}"),
+        instance,
+        previous,
+        terminated,
+    })
+}
+
+async fn log_tail(
+    pods: &Api<Pod>,
+    pod: &str,
+    previous: bool,
+    terminated: &ContainerStateTerminated,
+) -> Vec<String> {
+    let params = LogParams {
+        container: Some(NODE_CONTAINER.to_owned()),
+        previous,
+        tail_lines: Some(LOG_TAIL_LINES),
+        ..Default::default()
+    };
+
+    match pods.logs(pod, &params).await {
+        Ok(logs) => logs.lines().map(str::to_owned).collect(),
+        Err(err) => {
+            debug!(pod, error = ?err, "failed to fetch node logs");
+            terminated
+                .message
+                .iter()
+                .flat_map(|message| message.lines())
+                .map(str::to_owned)
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::{
+        api::core::v1::{
+            ContainerState, ContainerStateRunning, ContainerStateTerminated, ContainerStatus, Pod,
+            PodStatus,
+        },
+        apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time},
+        chrono::Utc,
+    };
+
+    use super::{NODE_CONTAINER, NODE_INDEX_LABEL, terminated_run};
+
+    fn terminated(exit_code: i32, reason: &str) -> ContainerState {
+        ContainerState {
+            terminated: Some(ContainerStateTerminated {
+                exit_code,
+                reason: Some(reason.to_owned()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn running() -> ContainerState {
+        ContainerState {
+            running: Some(ContainerStateRunning::default()),
+            ..Default::default()
+        }
+    }
+
+    fn node_pod(
+        state: ContainerState,
+        last_state: Option<ContainerState>,
+        restart_count: i32,
+    ) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("node-1-abc".to_owned()),
+                labels: Some(BTreeMap::from([(
+                    NODE_INDEX_LABEL.to_owned(),
+                    "1".to_owned(),
+                )])),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                container_statuses: Some(vec![ContainerStatus {
+                    name: NODE_CONTAINER.to_owned(),
+                    state: Some(state),
+                    last_state,
+                    restart_count,
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ignores_a_running_container_that_never_restarted() {
+        assert!(terminated_run(&node_pod(running(), None, 0)).is_none());
+    }
+
+    #[test]
+    fn reports_a_terminated_container_with_its_exit_code() {
+        let pod = node_pod(terminated(3, "Error"), None, 0);
+        let exit = terminated_run(&pod).expect("terminated container is an exit");
+
+        assert_eq!(exit.pod, "node-1-abc");
+        assert_eq!(exit.node, "node-1");
+        assert_eq!(exit.instance, "pod node-1-abc run #0 (Error)");
+        assert_eq!(exit.terminated.exit_code, 3);
+        assert!(!exit.previous);
+    }
+
+    #[test]
+    fn reports_an_oom_killed_container() {
+        let pod = node_pod(terminated(137, "OOMKilled"), None, 0);
+        let exit = terminated_run(&pod).expect("OOM kill is an exit");
+
+        assert_eq!(exit.instance, "pod node-1-abc run #0 (OOMKilled)");
+        assert_eq!(exit.terminated.exit_code, 137);
+    }
+
+    #[test]
+    fn reports_the_previous_run_of_a_restarted_container() {
+        let crashed = node_pod(terminated(1, "Error"), None, 0);
+        let restarted = node_pod(running(), Some(terminated(1, "Error")), 1);
+
+        let exit = terminated_run(&restarted).expect("restart is an exit");
+        assert_eq!(exit.instance, "pod node-1-abc run #0 (Error)");
+        assert_eq!(exit.terminated.exit_code, 1);
+        assert!(exit.previous);
+
+        // The same crash seen before the restart has the same identity.
+        assert_eq!(
+            terminated_run(&crashed).expect("crash is an exit").instance,
+            exit.instance
+        );
+    }
+
+    #[test]
+    fn ignores_a_pod_that_is_being_deleted() {
+        let mut pod = node_pod(terminated(143, "Error"), None, 0);
+        pod.metadata.deletion_timestamp = Some(Time(Utc::now()));
+
+        assert!(terminated_run(&pod).is_none());
+    }
+}
@@ -1,7 +1,12 @@
 mod manual;
 mod node_control;
+mod resources;
 mod runner;
+mod supervisor;
 
 pub use manual::{LocalManualCluster, ManualClusterError};
-pub use node_control::{LocalNodeManager, LocalNodeManagerError, LocalNodeManagerSeed};
+pub use node_control::{
+    DEFAULT_SPAWN_CONCURRENCY, LocalNodeManager, LocalNodeManagerError, LocalNodeManagerSeed,
+    NodeSpawnFailure, SpawnNodesError, default_spawn_concurrency,
+};
 pub use runner::{LocalDeployer, LocalDeployerError};
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use testing_framework_core::{
     manual::ManualClusterHandle,
     nodes::ApiClient,
@@ -33,7 +35,9 @@ pub struct LocalManualCluster {
 
 impl LocalManualCluster {
     pub(crate) fn from_builder(builder: TopologyBuilder) -> Result<Self, ManualClusterError> {
+        // Manual clusters have no run context to hand a framework clock to.
         let descriptors = builder
+            .with_framework_time(false)
             .build()
             .map_err(|source| ManualClusterError::Build { source })?;
 
@@ -87,6 +91,42 @@ impl LocalManualCluster {
         Ok(self.nodes.stop_node(name).await?)
     }
 
+    pub async fn terminate_node(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.terminate_node(name, timeout).await?)
+    }
+
+    pub async fn crash_node(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.crash_node(name).await?)
+    }
+
+    pub fn freeze_node(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.freeze_node(name)?)
+    }
+
+    pub fn unfreeze_node(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.unfreeze_node(name)?)
+    }
+
+    pub async fn restart_node_wiped(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.restart_node_wiped(name).await?)
+    }
+
+    pub async fn isolate_node(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.isolate_node(name).await?)
+    }
+
+    pub async fn partition_nodes(&self, names: &[String]) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.partition_nodes(names).await?)
+    }
+
+    pub async fn reconnect_node(&self, name: &str) -> Result<(), ManualClusterError> {
+        Ok(self.nodes.reconnect_node(name).await?)
+    }
+
     pub async fn wait_network_ready(&self) -> Result<(), ReadinessError> {
         let nodes = self.nodes.readiness_nodes();
         if self.is_singleton(&nodes) {
@@ -124,6 +164,53 @@ impl NodeControlHandle for LocalManualCluster {
         self.nodes.stop_node(name).await.map_err(|err| err.into())
     }
 
+    async fn terminate_node(&self, name: &str, timeout: Duration) -> Result<(), DynError> {
+        self.nodes
+            .terminate_node(name, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn crash_node(&self, name: &str) -> Result<(), DynError> {
+        self.nodes.crash_node(name).await.map_err(|err| err.into())
+    }
+
+    async fn freeze_node(&self, name: &str) -> Result<(), DynError> {
+        self.nodes.freeze_node(name).map_err(|err| err.into())
+    }
+
+    async fn unfreeze_node(&self, name: &str) -> Result<(), DynError> {
+        self.nodes.unfreeze_node(name).map_err(|err| err.into())
+    }
+
+    async fn restart_node_wiped(&self, name: &str) -> Result<(), DynError> {
+        self.nodes
+            .restart_node_wiped(name)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn isolate_node(&self, name: &str) -> Result<(), DynError> {
+        self.nodes
+            .isolate_node(name)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn partition_nodes(&self, names: &[String]) -> Result<(), DynError> {
+        self.nodes
+            .partition_nodes(names)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn reconnect_node(&self, name: &str) -> Result<(), DynError> {
+        self.nodes
+            .reconnect_node(name)
+            .await
+            .map_err(|err| err.into())
+    }
+
     async fn start_node(&self, name: &str) -> Result<StartedNode, DynError> {
         self.start_node_with(name, StartNodeOptions::default())
             .await
@@ -112,7 +112,7 @@ fn random_node_id() -> [u8; 32] {
     id
 }
 
-fn allocate_udp_port(label: &'static str) -> Result<u16, LocalNodeManagerError> {
+pub(super) fn allocate_udp_port(label: &'static str) -> Result<u16, LocalNodeManagerError> {
     get_available_udp_port().ok_or_else(|| LocalNodeManagerError::PortAllocation {
         message: format!("failed to allocate free UDP port for {label}"),
     })
@@ -1,31 +1,41 @@
 use std::{
     collections::{HashMap, HashSet},
+    io,
     sync::Mutex,
+    time::Duration,
 };
 
+use lb_libp2p::{Multiaddr, NatSettings};
 use lb_node::config::RunConfig;
 use testing_framework_config::topology::configs::{consensus, time};
 use testing_framework_core::{
     nodes::{
-        ApiClient,
+        ApiClient, NodeLogTails, NodeLogs,
+        common::node::SpawnNodeError,
         node::{Node, apply_node_config_patch, create_node_config},
     },
-    scenario::{DynError, NodeControlHandle, StartNodeOptions, StartedNode},
+    scenario::{DynError, NodeControlHandle, NodeExit, StartNodeOptions, StartedNode},
     topology::{
         deployment::Topology,
         generation::{GeneratedTopology, find_expected_peer_counts},
         utils::multiaddr_port,
     },
 };
+use testing_framework_env as tf_env;
 use thiserror::Error;
+use tokio::time::Instant;
 
 mod config;
+mod spawn;
 mod state;
 
-use config::build_general_config_for;
-use state::LocalNodeManagerState;
+use config::{allocate_udp_port, build_general_config_for};
+use spawn::{SpawnJob, spawn_concurrently};
+use state::{IsolatedNetwork, LocalNodeManagerState};
 use testing_framework_core::scenario::NodeClients;
 
+use crate::supervisor::node_exit;
+
 #[derive(Debug, Error)]
 pub enum LocalNodeManagerError {
     #[error("failed to generate node config: {source}")]
@@ -36,7 +46,7 @@ pub enum LocalNodeManagerError {
     #[error("failed to spawn node: {source}")]
     Spawn {
         #[source]
-        source: testing_framework_core::nodes::common::node::SpawnNodeError,
+        source: SpawnNodeError,
     },
     #[error("{message}")]
     InvalidArgument { message: String },
@@ -49,15 +59,72 @@ pub enum LocalNodeManagerError {
     #[error("failed to restart node: {source}")]
     Restart {
         #[source]
-        source: testing_framework_core::nodes::common::node::SpawnNodeError,
+        source: SpawnNodeError,
+    },
+    #[error("failed to signal node '{name}': {source}")]
+    Signal {
+        name: String,
+        #[source]
+        source: io::Error,
     },
 }
 
+/// How many nodes are started at once unless configured otherwise.
+pub const DEFAULT_SPAWN_CONCURRENCY: usize = 64;
+
+/// Spawn concurrency from `LOGOS_BLOCKCHAIN_LOCAL_SPAWN_CONCURRENCY`, falling
+/// back to [`DEFAULT_SPAWN_CONCURRENCY`].
+#[must_use]
+pub fn default_spawn_concurrency() -> usize {
+    tf_env::local_spawn_concurrency().unwrap_or(DEFAULT_SPAWN_CONCURRENCY)
+}
+
+/// One node that failed to start.
+#[derive(Debug, Error)]
+#[error("{node}: {source}")]
+pub struct NodeSpawnFailure {
+    pub node: String,
+    #[source]
+    pub source: SpawnNodeError,
+}
+
+/// Nodes of a topology that failed to start. The nodes that did start are
+/// dropped, which kills their processes.
+#[derive(Debug, Error)]
+#[error("{} of {total} node(s) failed to start:\n{}", .failures.len(), describe_failures(.failures))]
+pub struct SpawnNodesError {
+    pub total: usize,
+    pub failures: Vec<NodeSpawnFailure>,
+}
+
+impl SpawnNodesError {
+    /// Output tails of the failed nodes that got far enough to produce any.
+    #[must_use]
+    pub fn log_tails(&self) -> NodeLogTails {
+        NodeLogTails(
+            self.failures
+                .iter()
+                .filter_map(|failure| failure.source.output())
+                .map(|output| output.log_tail())
+                .collect(),
+        )
+    }
+}
+
+fn describe_failures(failures: &[NodeSpawnFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("  {failure}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub struct LocalNodeManager {
     descriptors: GeneratedTopology,
     base_consensus: consensus::GeneralConsensusConfig,
     base_time: time::GeneralTimeConfig,
     node_clients: NodeClients,
+    node_logs: NodeLogs,
     seed: LocalNodeManagerSeed,
     state: Mutex<LocalNodeManagerState>,
 }
@@ -103,24 +170,65 @@ impl LocalNodeManager {
         format!("node-{index}")
     }
 
+    /// Spawn every node of the topology, at most `concurrency` at a time,
+    /// and wait until each is ready. Start delays still count from the
+    /// moment spawning begins; a delayed node does not hold a slot while it
+    /// waits.
+    ///
+    /// Every node is given the chance to start, so the error lists all
+    /// failures rather than the first one.
     pub async fn spawn_initial_nodes(
         descriptors: &GeneratedTopology,
-    ) -> Result<Vec<Node>, testing_framework_core::nodes::common::node::SpawnNodeError> {
-        let mut nodes = Vec::with_capacity(descriptors.nodes().len());
-        for node in descriptors.nodes() {
-            let label = Self::default_label(node.index());
-            let config = create_node_config(node.general.clone());
-            let spawned = Node::spawn(config, &label, node.persist_dir.clone()).await?;
-            nodes.push(spawned);
-        }
+        concurrency: usize,
+    ) -> Result<Vec<Node>, SpawnNodesError> {
+        let started = Instant::now();
+        let total = descriptors.nodes().len();
+        let jobs = descriptors
+            .nodes()
+            .iter()
+            .map(|node| {
+                let label = Self::default_label(node.index());
+                let binary = node.spec().binary.clone();
+                let env = node.general.time_config.node_env();
+                let config = create_node_config(node.general.clone());
+                let persist_dir = node.persist_dir.clone();
+                SpawnJob {
+                    label: label.clone(),
+                    start_delay: node.spec().start_delay,
+                    spawn: async move {
+                        Node::spawn_with_env(config, &label, persist_dir, binary, env).await
+                    },
+                }
+            })
+            .collect();
 
+        let nodes = spawn_concurrently(jobs, concurrency, started)
+            .await
+            .map_err(|failures| SpawnNodesError {
+                total,
+                failures: failures
+                    .into_iter()
+                    .map(|(node, source)| {
+                        tracing::warn!(node = %node, error = %source, "node failed to start");
+                        NodeSpawnFailure { node, source }
+                    })
+                    .collect(),
+            })?;
+
+        tracing::info!(
+            nodes = total,
+            concurrency,
+            elapsed_ms = started.elapsed().as_millis(),
+            "local nodes spawned"
+        );
         Ok(nodes)
     }
 
     pub async fn spawn_initial_topology(
         descriptors: &GeneratedTopology,
-    ) -> Result<Topology, testing_framework_core::nodes::common::node::SpawnNodeError> {
-        let nodes = Self::spawn_initial_nodes(descriptors).await?;
+        concurrency: usize,
+    ) -> Result<Topology, SpawnNodesError> {
+        let nodes = Self::spawn_initial_nodes(descriptors, concurrency).await?;
         Ok(Topology::from_nodes(nodes))
     }
     pub fn new(descriptors: GeneratedTopology, node_clients: NodeClients) -> Self {
@@ -147,6 +255,8 @@ impl LocalNodeManager {
             clients_by_name: HashMap::new(),
             indices_by_name: HashMap::new(),
             nodes: Vec::new(),
+            isolated: HashMap::new(),
+            stopped: HashSet::new(),
         };
 
         Self {
@@ -154,6 +264,7 @@ impl LocalNodeManager {
             base_consensus,
             base_time,
             node_clients,
+            node_logs: NodeLogs::default(),
             seed,
             state: Mutex::new(state),
         }
@@ -169,6 +280,19 @@ impl LocalNodeManager {
         state.clients_by_name.get(name).cloned()
     }
 
+    #[must_use]
+    /// Names of every node the manager started, running or not.
+    pub fn node_names(&self) -> Vec<String> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut names = state.indices_by_name.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
     #[must_use]
     pub fn node_pid(&self, name: &str) -> Option<u32> {
         let mut state = self
@@ -198,12 +322,15 @@ impl LocalNodeManager {
             .clone_from(&self.seed.peer_ports_by_name);
         state.clients_by_name.clear();
         state.indices_by_name.clear();
+        state.isolated.clear();
+        state.stopped.clear();
         state.node_count = self.seed.node_count;
         self.node_clients.clear();
     }
 
     pub fn initialize_with_nodes(&self, nodes: Vec<Node>) {
         self.node_clients.clear();
+        self.node_logs.clear();
 
         let mut state = self
             .state
@@ -215,6 +342,8 @@ impl LocalNodeManager {
         state.peer_ports_by_name.clear();
         state.clients_by_name.clear();
         state.indices_by_name.clear();
+        state.isolated.clear();
+        state.stopped.clear();
         state.node_count = 0;
 
         for (idx, node) in nodes.into_iter().enumerate() {
@@ -223,6 +352,7 @@ impl LocalNodeManager {
             let client = node.api().clone();
 
             self.node_clients.add_node(client.clone());
+            self.node_logs.register(node.output().clone());
             state.register_node(&name, port, client, node);
         }
     }
@@ -232,6 +362,13 @@ impl LocalNodeManager {
         self.node_clients.clone()
     }
 
+    /// Captured output of every node this manager started, including nodes
+    /// that have since been stopped.
+    #[must_use]
+    pub fn node_logs(&self) -> NodeLogs {
+        self.node_logs.clone()
+    }
+
     pub async fn start_node_with(
         &self,
         name: &str,
@@ -328,6 +465,7 @@ impl LocalNodeManager {
             &peer_ports,
         )?;
 
+        let env = general_config.time_config.node_env();
         let config = build_node_config(
             general_config,
             descriptor_patch.as_ref(),
@@ -335,7 +473,7 @@ impl LocalNodeManager {
         )?;
 
         let api_client = self
-            .spawn_and_register_node(&node_name, network_port, config, options.persist_dir)
+            .spawn_and_register_node(&node_name, network_port, config, env, options.persist_dir)
             .await?;
 
         Ok(StartedNode {
@@ -345,71 +483,290 @@ impl LocalNodeManager {
     }
 
     pub async fn restart_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
-        let (index, mut node) = {
+        let (index, mut node) = self.take_node(name)?;
+
+        let result = node
+            .restart()
+            .await
+            .map_err(|source| LocalNodeManagerError::Restart { source });
+
+        self.return_node(index, node);
+        self.mark_running_if_ok(name, &result);
+        result
+    }
+
+    pub async fn stop_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        self.mark_stopped(name);
+        let (index, mut node) = self.take_node(name)?;
+
+        node.stop().await;
+
+        self.return_node(index, node);
+        Ok(())
+    }
+
+    /// Send SIGTERM and wait up to `timeout` for the node to exit, killing it
+    /// if it does not.
+    pub async fn terminate_node(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<(), LocalNodeManagerError> {
+        self.mark_stopped(name);
+        let (index, mut node) = self.take_node(name)?;
+
+        let result =
+            node.terminate(timeout)
+                .await
+                .map_err(|source| LocalNodeManagerError::Signal {
+                    name: name.to_owned(),
+                    source,
+                });
+
+        self.return_node(index, node);
+        result.map(|_graceful| ())
+    }
+
+    /// Kill the node with SIGKILL, leaving its data as a crash would.
+    pub async fn crash_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        tracing::info!(node = name, "crashing node");
+        self.stop_node(name).await
+    }
+
+    /// Suspend the node process with SIGSTOP.
+    pub fn freeze_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        let (index, node) = self.take_node(name)?;
+        let result = node.freeze();
+        self.return_node(index, node);
+
+        result.map_err(|source| LocalNodeManagerError::Signal {
+            name: name.to_owned(),
+            source,
+        })
+    }
+
+    /// Resume a node suspended by `freeze_node`.
+    pub fn unfreeze_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        let (index, node) = self.take_node(name)?;
+        let result = node.unfreeze();
+        self.return_node(index, node);
+
+        result.map_err(|source| LocalNodeManagerError::Signal {
+            name: name.to_owned(),
+            source,
+        })
+    }
+
+    /// Restart the node with its database and recovery files removed.
+    pub async fn restart_node_wiped(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        let (index, mut node) = self.take_node(name)?;
+
+        let result = node
+            .restart_wiped()
+            .await
+            .map_err(|source| LocalNodeManagerError::Restart { source });
+
+        self.return_node(index, node);
+        self.mark_running_if_ok(name, &result);
+        result
+    }
+
+    /// Cut a single node off from the rest of the topology.
+    pub async fn isolate_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        self.partition_nodes(&[name.to_owned()]).await
+    }
+
+    /// Move the named nodes into their own partition.
+    ///
+    /// Each member is restarted on a fresh network port with only the other
+    /// members as initial peers. Nodes outside the group only know the old
+    /// ports, so they cannot reach the partition until `reconnect_node`
+    /// restores the original network settings.
+    pub async fn partition_nodes(&self, names: &[String]) -> Result<(), LocalNodeManagerError> {
+        self.validate_partition(names)?;
+
+        let mut ports = Vec::with_capacity(names.len());
+        for _ in names {
+            ports.push(allocate_udp_port("partition network port")?);
+        }
+
+        for (name, port) in names.iter().zip(&ports) {
+            let initial_peers = ports
+                .iter()
+                .filter(|peer_port| *peer_port != port)
+                .map(|peer_port| testing_framework_config::node_address_from_port(*peer_port))
+                .collect::<Vec<_>>();
+
+            tracing::info!(
+                node = name,
+                port,
+                group_size = names.len(),
+                "partitioning node"
+            );
+
+            let (index, mut node) = self.take_node(name)?;
+            let mut original = None;
+            let result = node
+                .restart_with_config(|config| {
+                    let backend = &mut config.user.network.backend;
+                    original = Some(IsolatedNetwork {
+                        port: backend.swarm.port,
+                        initial_peers: std::mem::replace(&mut backend.initial_peers, initial_peers),
+                        nat_config: backend.swarm.nat_config.clone(),
+                    });
+                    backend.swarm.port = *port;
+                    backend.swarm.nat_config = rebind_nat(&backend.swarm.nat_config, *port);
+                })
+                .await
+                .map_err(|source| LocalNodeManagerError::Restart { source });
+
+            self.return_node(index, node);
+            self.mark_running_if_ok(name, &result);
+
             let mut state = self
                 .state
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(original) = original {
+                state.isolated.insert(name.clone(), original);
+            }
+            drop(state);
 
-            let Some(index) = state.indices_by_name.get(name).copied() else {
-                return Err(LocalNodeManagerError::NodeName {
-                    name: name.to_string(),
-                });
-            };
+            result?;
+        }
 
-            if index >= state.nodes.len() {
-                return Err(LocalNodeManagerError::NodeName {
-                    name: name.to_string(),
-                });
-            }
+        Ok(())
+    }
 
-            let node = state.nodes.remove(index);
-            (index, node)
+    /// Restore the original network settings of a partitioned node.
+    pub async fn reconnect_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
+        let original = {
+            let mut state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            state
+                .isolated
+                .remove(name)
+                .ok_or_else(|| LocalNodeManagerError::InvalidArgument {
+                    message: format!("node '{name}' is not partitioned"),
+                })?
         };
 
-        node.restart()
+        tracing::info!(node = name, port = original.port, "reconnecting node");
+
+        let (index, mut node) = self.take_node(name)?;
+        let result = node
+            .restart_with_config(|config| {
+                let backend = &mut config.user.network.backend;
+                backend.swarm.port = original.port;
+                backend.swarm.nat_config = original.nat_config;
+                backend.initial_peers = original.initial_peers;
+            })
             .await
-            .map_err(|source| LocalNodeManagerError::Restart { source })?;
+            .map_err(|source| LocalNodeManagerError::Restart { source });
 
+        self.return_node(index, node);
+        self.mark_running_if_ok(name, &result);
+        result
+    }
+
+    /// Nodes whose process has exited although nobody stopped them through
+    /// this manager. Nodes taken out for a restart are not checked.
+    pub(crate) fn unexpected_exits(&self) -> Vec<NodeExit> {
         let mut state = self
             .state
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-        if index <= state.nodes.len() {
-            state.nodes.insert(index, node);
-        } else {
-            state.nodes.push(node);
+        let LocalNodeManagerState { nodes, stopped, .. } = &mut *state;
+        nodes
+            .iter_mut()
+            .filter(|node| !stopped.contains(node.output().label()))
+            .filter_map(|node| {
+                let status = node.exit_status()?;
+                Some(node_exit(node.output(), node.pid(), status.into()))
+            })
+            .collect()
+    }
+
+    fn mark_stopped(&self, name: &str) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        state.stopped.insert(name.to_owned());
+    }
+
+    fn mark_running_if_ok<T>(&self, name: &str, result: &Result<T, LocalNodeManagerError>) {
+        if result.is_err() {
+            return;
         }
 
-        Ok(())
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        state.stopped.remove(name);
     }
 
-    pub async fn stop_node(&self, name: &str) -> Result<(), LocalNodeManagerError> {
-        let (index, mut node) = {
-            let mut state = self
-                .state
-                .lock()
-                .unwrap_or_else(|poisoned| poisoned.into_inner());
+    fn validate_partition(&self, names: &[String]) -> Result<(), LocalNodeManagerError> {
+        if names.is_empty() {
+            return Err(LocalNodeManagerError::InvalidArgument {
+                message: "partition requires at least one node".to_owned(),
+            });
+        }
 
-            let Some(index) = state.indices_by_name.get(name).copied() else {
-                return Err(LocalNodeManagerError::NodeName {
-                    name: name.to_string(),
-                });
-            };
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-            if index >= state.nodes.len() {
-                return Err(LocalNodeManagerError::NodeName {
-                    name: name.to_string(),
+        let mut seen = HashSet::new();
+        for name in names {
+            if !state.indices_by_name.contains_key(name) {
+                return Err(LocalNodeManagerError::NodeName { name: name.clone() });
+            }
+            if !seen.insert(name) {
+                return Err(LocalNodeManagerError::InvalidArgument {
+                    message: format!("node '{name}' listed twice in partition"),
                 });
             }
+            if state.isolated.contains_key(name) {
+                return Err(LocalNodeManagerError::InvalidArgument {
+                    message: format!("node '{name}' is already partitioned"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn take_node(&self, name: &str) -> Result<(usize, Node), LocalNodeManagerError> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-            let node = state.nodes.remove(index);
-            (index, node)
+        let Some(index) = state.indices_by_name.get(name).copied() else {
+            return Err(LocalNodeManagerError::NodeName {
+                name: name.to_string(),
+            });
         };
 
-        node.stop().await;
+        if index >= state.nodes.len() {
+            return Err(LocalNodeManagerError::NodeName {
+                name: name.to_string(),
+            });
+        }
 
+        Ok((index, state.nodes.remove(index)))
+    }
+
+    fn return_node(&self, index: usize, node: Node) {
         let mut state = self
             .state
             .lock()
@@ -420,7 +777,6 @@ impl LocalNodeManager {
         } else {
             state.nodes.push(node);
         }
-        Ok(())
     }
 
     async fn spawn_and_register_node(
@@ -428,14 +784,16 @@ impl LocalNodeManager {
         node_name: &str,
         network_port: u16,
         config: RunConfig,
+        env: Vec<(String, String)>,
         persist_dir: Option<std::path::PathBuf>,
     ) -> Result<ApiClient, LocalNodeManagerError> {
-        let node = Node::spawn(config, node_name, persist_dir)
+        let node = Node::spawn_with_env(config, node_name, persist_dir, None, env)
             .await
             .map_err(|source| LocalNodeManagerError::Spawn { source })?;
         let client = node.api().clone();
 
         self.node_clients.add_node(client.clone());
+        self.node_logs.register(node.output().clone());
 
         let mut state = self
             .state
@@ -448,6 +806,21 @@ impl LocalNodeManager {
     }
 }
 
+fn rebind_nat(nat_config: &NatSettings, port: u16) -> NatSettings {
+    match nat_config {
+        NatSettings::Static { .. } => NatSettings::Static {
+            external_address: loopback_quic_address(port),
+        },
+        other => other.clone(),
+    }
+}
+
+fn loopback_quic_address(port: u16) -> Multiaddr {
+    format!("/ip4/127.0.0.1/udp/{port}/quic-v1")
+        .parse()
+        .expect("loopback quic multiaddr is valid")
+}
+
 fn build_node_config(
     general_config: testing_framework_config::topology::configs::GeneralConfig,
     descriptor_patch: Option<&config::NodeConfigPatch>,
@@ -483,6 +856,30 @@ impl NodeControlHandle for LocalNodeManager {
         self.stop_node(name).await.map_err(|err| err.into())
     }
 
+    async fn terminate_node(&self, name: &str, timeout: Duration) -> Result<(), DynError> {
+        self.terminate_node(name, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    async fn crash_node(&self, name: &str) -> Result<(), DynError> {
+        self.crash_node(name).await.map_err(|err| err.into())
+    }
+
+    async fn freeze_node(&self, name: &str) -> Result<(), DynError> {
+        self.freeze_node(name).map_err(|err| err.into())
+    }
+
+    async fn unfreeze_node(&self, name: &str) -> Result<(), DynError> {
+        self.unfreeze_node(name).map_err(|err| err.into())
+    }
+
+    async fn restart_node_wiped(&self, name: &str) -> Result<(), DynError> {
+        self.restart_node_wiped(name)
+            .await
+            .map_err(|err| err.into())
+    }
+
     async fn start_node(&self, name: &str) -> Result<StartedNode, DynError> {
         self.start_node_with(name, StartNodeOptions::default())
             .await
@@ -499,6 +896,18 @@ impl NodeControlHandle for LocalNodeManager {
             .map_err(|err| err.into())
     }
 
+    async fn isolate_node(&self, name: &str) -> Result<(), DynError> {
+        self.isolate_node(name).await.map_err(|err| err.into())
+    }
+
+    async fn partition_nodes(&self, names: &[String]) -> Result<(), DynError> {
+        self.partition_nodes(names).await.map_err(|err| err.into())
+    }
+
+    async fn reconnect_node(&self, name: &str) -> Result<(), DynError> {
+        self.reconnect_node(name).await.map_err(|err| err.into())
+    }
+
     fn node_client(&self, name: &str) -> Option<ApiClient> {
         self.node_client(name)
     }
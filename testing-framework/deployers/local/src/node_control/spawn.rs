@@ -0,0 +1,191 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::Semaphore,
+    task::JoinSet,
+    time::{Instant, sleep_until},
+};
+
+/// One node start scheduled by [`spawn_concurrently`].
+pub(super) struct SpawnJob<F> {
+    pub(super) label: String,
+    /// Delay counted from the moment spawning begins.
+    pub(super) start_delay: Option<Duration>,
+    pub(super) spawn: F,
+}
+
+/// Runs `jobs` at most `concurrency` at a time. A delayed job does not hold a
+/// slot while it waits. Every job runs to completion, so all failures are
+/// reported rather than the first one; a panicking job panics the caller.
+///
+/// Returns the results in job order, or the labelled failures in job order.
+/// On failure the successful results are dropped.
+pub(super) async fn spawn_concurrently<T, E, F>(
+    jobs: Vec<SpawnJob<F>>,
+    concurrency: usize,
+    started: Instant,
+) -> Result<Vec<T>, Vec<(String, E)>>
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    let total = jobs.len();
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (position, job) in jobs.into_iter().enumerate() {
+        let permits = Arc::clone(&permits);
+        tasks.spawn(async move {
+            if let Some(delay) = job.start_delay {
+                tracing::debug!(
+                    node = %job.label,
+                    delay_ms = delay.as_millis(),
+                    "delaying node start"
+                );
+                sleep_until(started + delay).await;
+            }
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .expect("spawn semaphore is never closed");
+
+            (position, job.label, job.spawn.await)
+        });
+    }
+
+    let mut spawned = Vec::with_capacity(total);
+    let mut failures = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (position, label, result) =
+            joined.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+        match result {
+            Ok(value) => spawned.push((position, value)),
+            Err(source) => failures.push((position, label, source)),
+        }
+    }
+
+    if !failures.is_empty() {
+        failures.sort_by_key(|(position, ..)| *position);
+        return Err(failures
+            .into_iter()
+            .map(|(_, label, source)| (label, source))
+            .collect());
+    }
+
+    spawned.sort_by_key(|(position, _)| *position);
+    Ok(spawned.into_iter().map(|(_, value)| value).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use tokio::time::{Instant, sleep};
+
+    use super::{SpawnJob, spawn_concurrently};
+
+    type BoxedSpawn<T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send>>;
+
+    fn boxed<T>(spawn: impl Future<Output = Result<T, String>> + Send + 'static) -> BoxedSpawn<T> {
+        Box::pin(spawn)
+    }
+
+    fn job<F>(index: usize, spawn: F) -> SpawnJob<F> {
+        SpawnJob {
+            label: format!("node-{index}"),
+            start_delay: None,
+            spawn,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn never_runs_more_than_the_concurrency_limit() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let jobs = (0..8)
+            .map(|index| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                job(index, async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, String>(index)
+                })
+            })
+            .collect();
+
+        let spawned = spawn_concurrently(jobs, 3, Instant::now())
+            .await
+            .expect("all jobs succeed");
+
+        assert_eq!(spawned, (0..8).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn collects_every_failure_in_node_order() {
+        // Later nodes finish first, so completion order is the reverse of
+        // node order.
+        let jobs = (0..6)
+            .map(|index| {
+                job(index, async move {
+                    sleep(Duration::from_millis(60 - 10 * index as u64)).await;
+                    if index % 2 == 1 {
+                        Err(format!("failure {index}"))
+                    } else {
+                        Ok(index)
+                    }
+                })
+            })
+            .collect();
+
+        let failures = spawn_concurrently(jobs, 6, Instant::now())
+            .await
+            .expect_err("odd nodes fail");
+
+        assert_eq!(
+            failures,
+            vec![
+                ("node-1".to_owned(), "failure 1".to_owned()),
+                ("node-3".to_owned(), "failure 3".to_owned()),
+                ("node-5".to_owned(), "failure 5".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delayed_jobs_do_not_hold_a_slot() {
+        let started = Instant::now();
+        let mut delayed = job(0, boxed(async { Ok(Instant::now()) }));
+        delayed.start_delay = Some(Duration::from_secs(5));
+        let jobs = vec![delayed, job(1, boxed(async { Ok(Instant::now()) }))];
+
+        let spawned = spawn_concurrently(jobs, 1, started)
+            .await
+            .expect("both jobs succeed");
+
+        assert_eq!(spawned[0] - started, Duration::from_secs(5));
+        assert_eq!(spawned[1], started);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "spawn task panicked")]
+    async fn propagates_a_panicking_job() {
+        let jobs = vec![
+            job(0, boxed::<usize>(async { Ok(0) })),
+            job(1, boxed::<usize>(async { panic!("spawn task panicked") })),
+        ];
+
+        let _ = spawn_concurrently(jobs, 2, Instant::now()).await;
+    }
+}
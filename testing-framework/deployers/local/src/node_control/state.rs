@@ -1,7 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use lb_libp2p::{Multiaddr, NatSettings};
 use testing_framework_core::nodes::{ApiClient, node::Node};
 
+/// Network settings a node had before it was partitioned away, restored on
+/// reconnect.
+pub(crate) struct IsolatedNetwork {
+    pub(crate) port: u16,
+    pub(crate) initial_peers: Vec<Multiaddr>,
+    pub(crate) nat_config: NatSettings,
+}
+
 pub(crate) struct LocalNodeManagerState {
     pub(crate) node_count: usize,
     pub(crate) peer_ports: Vec<u16>,
@@ -9,6 +18,9 @@ pub(crate) struct LocalNodeManagerState {
     pub(crate) clients_by_name: HashMap<String, ApiClient>,
     pub(crate) indices_by_name: HashMap<String, usize>,
     pub(crate) nodes: Vec<Node>,
+    pub(crate) isolated: HashMap<String, IsolatedNetwork>,
+    /// Nodes stopped through the control handle, whose exit is expected.
+    pub(crate) stopped: HashSet<String>,
 }
 
 impl LocalNodeManagerState {
@@ -0,0 +1,52 @@
+use std::{sync::Arc, time::Duration};
+
+use testing_framework_core::{
+    scenario::{ResourceSamplerTask, ResourceUsage, spawn_resource_sampler},
+    topology::deployment::Topology,
+};
+use testing_framework_env as tf_env;
+
+use crate::node_control::LocalNodeManager;
+
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+fn sample_interval() -> Duration {
+    tf_env::resource_sample_interval_ms()
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_SAMPLE_INTERVAL)
+        .max(Duration::from_millis(10))
+}
+
+/// Sample the node processes of a fixed topology.
+pub(crate) fn sample_topology(topology: &Topology, usage: ResourceUsage) -> ResourceSamplerTask {
+    let pids = topology
+        .nodes()
+        .iter()
+        .map(|node| (node.output().label().to_owned(), node.pid()))
+        .collect::<Vec<_>>();
+
+    spawn_resource_sampler(usage, sample_interval(), move || pids.clone())
+}
+
+/// Sample the nodes of a manager, following their pids across restarts.
+/// Stopped nodes are not sampled.
+pub(crate) fn sample_manager(
+    manager: &Arc<LocalNodeManager>,
+    usage: ResourceUsage,
+) -> ResourceSamplerTask {
+    let manager = Arc::downgrade(manager);
+
+    spawn_resource_sampler(usage, sample_interval(), move || {
+        let Some(manager) = manager.upgrade() else {
+            return Vec::new();
+        };
+        manager
+            .node_names()
+            .into_iter()
+            .filter_map(|name| {
+                let pid = manager.node_pid(&name)?;
+                Some((name, pid))
+            })
+            .collect()
+    })
+}
@@ -1,15 +1,19 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use testing_framework_config::topology::configs::time::TimeSource;
 use testing_framework_core::{
-    nodes::common::node::SpawnNodeError,
+    nodes::{AttachNodeLogs, NodeLogTails},
     scenario::{
-        BlockFeed, BlockFeedTask, Deployer, DynError, Metrics, NodeClients, NodeControlCapability,
-        RunContext, Runner, Scenario, ScenarioError, spawn_block_feed,
+        BlockFeed, BlockFeedMode, BlockFeedTask, CleanupGuard, ClockControl, ClockError, Deployer,
+        DynError, ExitWatchTask, Metrics, NodeClients, NodeControlCapability, NodeExit, NodeExits,
+        ResourceSamplerTask, ResourceUsage, RunContext, Runner, Scenario, ScenarioError,
+        describe_exits, spawn_block_feed, spawn_multi_node_block_feed,
     },
     topology::{
         config::{TopologyBuilder, TopologyConfig},
         deployment::Topology,
+        generation::GeneratedTopology,
         readiness::ReadinessError,
     },
 };
@@ -18,13 +22,18 @@ use tracing::{debug, info};
 
 use crate::{
     manual::{LocalManualCluster, ManualClusterError},
-    node_control::{LocalNodeManager, LocalNodeManagerSeed},
+    node_control::{
+        LocalNodeManager, LocalNodeManagerSeed, SpawnNodesError, default_spawn_concurrency,
+    },
+    resources::{sample_manager, sample_topology},
+    supervisor::{supervise_manager, supervise_topology},
 };
 /// Spawns nodes as local processes, reusing the existing
 /// integration harness.
 #[derive(Clone)]
 pub struct LocalDeployer {
     membership_check: bool,
+    spawn_concurrency: Option<usize>,
 }
 
 /// Errors surfaced by the local deployer while driving a scenario.
@@ -33,13 +42,18 @@ pub enum LocalDeployerError {
     #[error("failed to spawn local topology: {source}")]
     Spawn {
         #[source]
-        source: SpawnNodeError,
+        source: SpawnNodesError,
     },
     #[error("readiness probe failed: {source}")]
     ReadinessFailed {
         #[source]
         source: ReadinessError,
     },
+    #[error("failed to start framework time servers: {source}")]
+    Clock {
+        #[source]
+        source: ClockError,
+    },
     #[error("workload failed: {source}")]
     WorkloadFailed {
         #[source]
@@ -50,19 +64,64 @@ pub enum LocalDeployerError {
         #[source]
         source: DynError,
     },
+    #[error("{} node(s) exited unexpectedly:\n{}", .exits.len(), describe_exits(.exits))]
+    NodeExited { exits: Vec<NodeExit> },
+    #[error("{source}\n{logs}")]
+    WithNodeLogs {
+        #[source]
+        source: Box<LocalDeployerError>,
+        logs: NodeLogTails,
+    },
+}
+
+impl AttachNodeLogs for LocalDeployerError {
+    fn wrap_node_logs(source: Self, logs: NodeLogTails) -> Self {
+        Self::WithNodeLogs {
+            source: Box::new(source),
+            logs,
+        }
+    }
+
+    fn into_parts(self) -> (Self, Option<NodeLogTails>) {
+        match self {
+            Self::WithNodeLogs { source, logs } => (*source, Some(logs)),
+            error => (error, None),
+        }
+    }
+
+    fn node_logs(&self) -> Option<&NodeLogTails> {
+        match self {
+            Self::WithNodeLogs { logs, .. } => Some(logs),
+            _ => None,
+        }
+    }
 }
 
 impl From<ScenarioError> for LocalDeployerError {
     fn from(value: ScenarioError) -> Self {
-        match value {
+        let (value, logs) = value.into_parts();
+        let error = match value {
             ScenarioError::Workload(source) => Self::WorkloadFailed { source },
             ScenarioError::ExpectationCapture(source) | ScenarioError::Expectations(source) => {
                 Self::ExpectationsFailed { source }
             }
-        }
+            ScenarioError::NodeExited { exits } => Self::NodeExited { exits },
+            error @ (ScenarioError::Invariant { .. } | ScenarioError::WithNodeLogs { .. }) => {
+                Self::ExpectationsFailed {
+                    source: error.into(),
+                }
+            }
+        };
+        error.with_node_logs(logs.unwrap_or_default())
     }
 }
 
+/// Spawn failures carry the output of the nodes that failed to come up.
+fn spawn_error(source: SpawnNodesError) -> LocalDeployerError {
+    let logs = source.log_tails();
+    LocalDeployerError::Spawn { source }.with_node_logs(logs)
+}
+
 #[async_trait]
 impl Deployer<()> for LocalDeployer {
     type Error = LocalDeployerError;
@@ -72,22 +131,44 @@ impl Deployer<()> for LocalDeployer {
             nodes = scenario.topology().nodes().len(),
             "starting local deployment"
         );
-        let topology = Self::prepare_topology(scenario, self.membership_check).await?;
+        let clock = start_framework_clock(scenario.topology()).await?;
+        let topology =
+            Self::prepare_topology(scenario, self.membership_check, self.spawn_concurrency())
+                .await?;
         let node_clients = NodeClients::from_topology(scenario.topology(), &topology);
+        node_clients.reseed(scenario.seed());
+        let node_logs = topology.node_logs();
+        let node_exits = NodeExits::default();
+        let supervisor = supervise_topology(&topology, node_exits.clone());
+        let resource_usage = ResourceUsage::default();
+        let sampler = sample_topology(&topology, resource_usage.clone());
 
-        let (block_feed, block_feed_guard) = spawn_block_feed_with(&node_clients).await?;
+        let (block_feed, block_feed_guard) =
+            spawn_block_feed_with(&node_clients, scenario.block_feed_mode()).await?;
 
-        let context = RunContext::new(
+        let mut context = RunContext::new(
             scenario.topology().clone(),
             Some(topology),
             node_clients,
             scenario.duration(),
+            scenario.seed(),
             Metrics::empty(),
             block_feed,
             None,
-        );
+        )
+        .with_node_logs(node_logs)
+        .with_node_exits(node_exits)
+        .with_resource_usage(resource_usage);
+        if let Some(clock) = clock {
+            context = context.with_clock(clock);
+        }
 
-        Ok(Runner::new(context, Some(Box::new(block_feed_guard))))
+        let cleanup = LocalCleanupGuard {
+            block_feed: block_feed_guard,
+            supervisor,
+            sampler,
+        };
+        Ok(Runner::new(context, Some(Box::new(cleanup))))
     }
 }
 
@@ -104,9 +185,11 @@ impl Deployer<NodeControlCapability> for LocalDeployer {
             "starting local deployment with node control"
         );
 
-        let mut nodes = LocalNodeManager::spawn_initial_nodes(scenario.topology())
-            .await
-            .map_err(|source| LocalDeployerError::Spawn { source })?;
+        let clock = start_framework_clock(scenario.topology()).await?;
+        let mut nodes =
+            LocalNodeManager::spawn_initial_nodes(scenario.topology(), self.spawn_concurrency())
+                .await
+                .map_err(spawn_error)?;
 
         if self.membership_check {
             let topology = Topology::from_nodes(nodes);
@@ -114,6 +197,7 @@ impl Deployer<NodeControlCapability> for LocalDeployer {
             wait_for_readiness(&topology).await.map_err(|source| {
                 debug!(error = ?source, "local readiness failed");
                 LocalDeployerError::ReadinessFailed { source }
+                    .with_node_logs(topology.node_logs().tails())
             })?;
 
             nodes = topology.into_nodes();
@@ -131,20 +215,39 @@ impl Deployer<NodeControlCapability> for LocalDeployer {
 
         node_control.initialize_with_nodes(nodes);
         let node_clients = node_control.node_clients();
+        node_clients.reseed(scenario.seed());
+        let node_logs = node_control.node_logs();
+        let node_exits = NodeExits::default();
+        let supervisor = supervise_manager(&node_control, node_exits.clone());
+        let resource_usage = ResourceUsage::default();
+        let sampler = sample_manager(&node_control, resource_usage.clone());
 
-        let (block_feed, block_feed_guard) = spawn_block_feed_with(&node_clients).await?;
+        let (block_feed, block_feed_guard) =
+            spawn_block_feed_with(&node_clients, scenario.block_feed_mode()).await?;
 
-        let context = RunContext::new(
+        let mut context = RunContext::new(
             scenario.topology().clone(),
             None,
             node_clients,
             scenario.duration(),
+            scenario.seed(),
             Metrics::empty(),
             block_feed,
             Some(node_control),
-        );
+        )
+        .with_node_logs(node_logs)
+        .with_node_exits(node_exits)
+        .with_resource_usage(resource_usage);
+        if let Some(clock) = clock {
+            context = context.with_clock(clock);
+        }
 
-        Ok(Runner::new(context, Some(Box::new(block_feed_guard))))
+        let cleanup = LocalCleanupGuard {
+            block_feed: block_feed_guard,
+            supervisor,
+            sampler,
+        };
+        Ok(Runner::new(context, Some(Box::new(cleanup))))
     }
 }
 
@@ -163,6 +266,21 @@ impl LocalDeployer {
         self
     }
 
+    #[must_use]
+    /// Start at most `limit` nodes at once. Defaults to
+    /// `LOGOS_BLOCKCHAIN_LOCAL_SPAWN_CONCURRENCY`, or
+    /// [`DEFAULT_SPAWN_CONCURRENCY`](crate::DEFAULT_SPAWN_CONCURRENCY) when
+    /// that is unset; `1` starts nodes one after another.
+    pub fn with_spawn_concurrency(mut self, limit: usize) -> Self {
+        self.spawn_concurrency = Some(limit);
+        self
+    }
+
+    fn spawn_concurrency(&self) -> usize {
+        self.spawn_concurrency
+            .unwrap_or_else(default_spawn_concurrency)
+    }
+
     /// Build a manual cluster using this deployer's local implementation.
     pub fn manual_cluster(
         &self,
@@ -182,19 +300,24 @@ impl LocalDeployer {
     async fn prepare_topology<Caps>(
         scenario: &Scenario<Caps>,
         membership_check: bool,
+        spawn_concurrency: usize,
     ) -> Result<Topology, LocalDeployerError> {
         let descriptors = scenario.topology();
 
-        info!(nodes = descriptors.nodes().len(), "spawning local nodes");
+        info!(
+            nodes = descriptors.nodes().len(),
+            spawn_concurrency, "spawning local nodes"
+        );
 
-        let topology = LocalNodeManager::spawn_initial_topology(descriptors)
+        let topology = LocalNodeManager::spawn_initial_topology(descriptors, spawn_concurrency)
             .await
-            .map_err(|source| LocalDeployerError::Spawn { source })?;
+            .map_err(spawn_error)?;
 
         if membership_check {
             wait_for_readiness(&topology).await.map_err(|source| {
                 debug!(error = ?source, "local readiness failed");
                 LocalDeployerError::ReadinessFailed { source }
+                    .with_node_logs(topology.node_logs().tails())
             })?;
 
             info!("local nodes are ready");
@@ -206,14 +329,53 @@ impl LocalDeployer {
     }
 }
 
+/// Stops the background tasks of a local run before its nodes are dropped.
+struct LocalCleanupGuard {
+    block_feed: BlockFeedTask,
+    supervisor: ExitWatchTask,
+    sampler: ResourceSamplerTask,
+}
+
+impl CleanupGuard for LocalCleanupGuard {
+    fn cleanup(self: Box<Self>) {
+        CleanupGuard::cleanup(Box::new(self.supervisor));
+        CleanupGuard::cleanup(Box::new(self.sampler));
+        CleanupGuard::cleanup(Box::new(self.block_feed));
+    }
+}
+
 impl Default for LocalDeployer {
     fn default() -> Self {
         Self {
             membership_check: true,
+            spawn_concurrency: None,
         }
     }
 }
 
+/// Starts the time servers for nodes configured with framework time. They
+/// have to be up before the nodes' first clock sync.
+async fn start_framework_clock(
+    descriptors: &GeneratedTopology,
+) -> Result<Option<ClockControl>, LocalDeployerError> {
+    let ports = descriptors
+        .nodes()
+        .iter()
+        .filter_map(|node| match node.general.time_config.source {
+            TimeSource::Framework { port } => Some((node.index(), port)),
+            TimeSource::Node => None,
+        })
+        .collect::<Vec<_>>();
+    if ports.is_empty() {
+        return Ok(None);
+    }
+
+    ClockControl::start(ports)
+        .await
+        .map(Some)
+        .map_err(|source| LocalDeployerError::Clock { source })
+}
+
 async fn wait_for_readiness(topology: &Topology) -> Result<(), ReadinessError> {
     info!("waiting for local network readiness");
 
@@ -223,13 +385,24 @@ async fn wait_for_readiness(topology: &Topology) -> Result<(), ReadinessError> {
 
 async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    mode: BlockFeedMode,
 ) -> Result<(BlockFeed, BlockFeedTask), LocalDeployerError> {
+    if mode == BlockFeedMode::AllNodes {
+        info!(
+            nodes = node_clients.node_clients().len(),
+            "starting multi-node block feed"
+        );
+        return spawn_multi_node_block_feed(node_clients.clone())
+            .await
+            .map_err(workload_error);
+    }
+
     debug!(
         nodes = node_clients.node_clients().len(),
         "selecting node client for local block feed"
     );
 
-    let Some(block_source_client) = node_clients.random_node() else {
+    let Some((index, block_source_client)) = node_clients.random_indexed_node() else {
         return Err(LocalDeployerError::WorkloadFailed {
             source: "block feed requires at least one node".into(),
         });
@@ -237,7 +410,7 @@ async fn spawn_block_feed_with(
 
     info!("starting block feed");
 
-    spawn_block_feed(block_source_client)
+    spawn_block_feed(block_source_client, index)
         .await
         .map_err(workload_error)
 }
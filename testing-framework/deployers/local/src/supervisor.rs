@@ -0,0 +1,156 @@
+use std::{
+    fs,
+    path::Path,
+    process::ExitStatus,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use testing_framework_core::{
+    nodes::NodeOutput,
+    scenario::{ExitWatchTask, NodeExit, NodeExits, spawn_exit_watcher},
+    topology::deployment::Topology,
+};
+use tracing::debug;
+
+use crate::node_control::LocalNodeManager;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How a node process ended.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ExitInfo {
+    code: Option<i32>,
+    signal: Option<i32>,
+}
+
+impl ExitInfo {
+    /// Decode a raw status as reported by `waitpid(2)`.
+    const fn from_wait_status(status: i32) -> Self {
+        let signal = status & 0x7f;
+        if signal == 0 {
+            Self {
+                code: Some((status >> 8) & 0xff),
+                signal: None,
+            }
+        } else {
+            Self {
+                code: None,
+                signal: Some(signal),
+            }
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitInfo {
+    fn from(status: ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal = None;
+
+        Self {
+            code: status.code(),
+            signal,
+        }
+    }
+}
+
+pub(crate) fn node_exit(output: &NodeOutput, pid: u32, info: ExitInfo) -> NodeExit {
+    NodeExit {
+        node: output.label().to_owned(),
+        instance: format!("pid {pid}"),
+        observed_at: SystemTime::now(),
+        code: info.code,
+        signal: info.signal,
+        log_tail: output.tail(),
+    }
+}
+
+/// Watch the nodes of a fixed topology. Nothing stops these nodes on purpose,
+/// so every exit is unexpected.
+///
+/// The topology is owned by the run context, so the processes cannot be
+/// polled through their handles; their state is read from `/proc` instead,
+/// which only works on Linux.
+pub(crate) fn supervise_topology(topology: &Topology, exits: NodeExits) -> ExitWatchTask {
+    let nodes = if Path::new("/proc/self/stat").exists() {
+        topology
+            .nodes()
+            .iter()
+            .map(|node| (node.pid(), node.output().clone()))
+            .collect::<Vec<_>>()
+    } else {
+        debug!("no /proc filesystem; local node exits are not watched");
+        Vec::new()
+    };
+
+    spawn_exit_watcher(exits, POLL_INTERVAL, move || {
+        let exited = nodes
+            .iter()
+            .filter_map(|(pid, output)| {
+                proc_exit_status(*pid).map(|info| node_exit(output, *pid, info))
+            })
+            .collect::<Vec<_>>();
+        async move { exited }
+    })
+}
+
+/// Watch the nodes of a manager. Nodes stopped through its control handle
+/// are not reported.
+pub(crate) fn supervise_manager(
+    manager: &Arc<LocalNodeManager>,
+    exits: NodeExits,
+) -> ExitWatchTask {
+    let manager = Arc::downgrade(manager);
+
+    spawn_exit_watcher(exits, POLL_INTERVAL, move || {
+        let exited = manager
+            .upgrade()
+            .map(|manager| manager.unexpected_exits())
+            .unwrap_or_default();
+        async move { exited }
+    })
+}
+
+/// Exit status of a child that has exited but was not reaped yet, from
+/// `/proc/<pid>/stat`. `None` while the process is still running.
+fn proc_exit_status(pid: u32) -> Option<ExitInfo> {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{pid}/stat")) else {
+        // Already reaped; the status is gone.
+        return Some(ExitInfo::default());
+    };
+
+    // The command name is parenthesised and may contain spaces, so fields
+    // are counted from the closing parenthesis: the state is field 3 and the
+    // exit code field 52 of `proc_pid_stat(5)`.
+    let fields = stat
+        .rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    if !matches!(fields.first(), Some(&("Z" | "X"))) {
+        return None;
+    }
+
+    Some(
+        fields
+            .get(49)
+            .and_then(|raw| raw.parse::<i32>().ok())
+            .map_or_else(ExitInfo::default, ExitInfo::from_wait_status),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExitInfo;
+
+    #[test]
+    fn decodes_wait_status() {
+        let exited = ExitInfo::from_wait_status(3 << 8);
+        assert_eq!((exited.code, exited.signal), (Some(3), None));
+
+        let killed = ExitInfo::from_wait_status(9);
+        assert_eq!((killed.code, killed.signal), (None, Some(9)));
+    }
+}
@@ -15,6 +15,43 @@ pub fn nomos_log_dir() -> Option<PathBuf> {
     env::var("LOGOS_BLOCKCHAIN_LOG_DIR").ok().map(PathBuf::from)
 }
 
+#[must_use]
+pub fn node_log_tail_lines() -> Option<usize> {
+    env::var("LOGOS_BLOCKCHAIN_NODE_LOG_TAIL_LINES")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+}
+
+#[must_use]
+pub fn local_spawn_concurrency() -> Option<usize> {
+    env::var("LOGOS_BLOCKCHAIN_LOCAL_SPAWN_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+}
+
+#[must_use]
+pub fn resource_sample_interval_ms() -> Option<u64> {
+    env::var("LOGOS_BLOCKCHAIN_RESOURCE_SAMPLE_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
+#[must_use]
+pub fn scenario_report_dir() -> Option<PathBuf> {
+    env::var("LOGOS_BLOCKCHAIN_SCENARIO_REPORT_DIR")
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// `LOGOS_BLOCKCHAIN_SCENARIO_SEED`, if set. A value that is not a `u64` is
+/// an error so a mistyped seed is never replaced by a random one.
+pub fn scenario_seed() -> Result<Option<u64>, std::num::ParseIntError> {
+    env::var("LOGOS_BLOCKCHAIN_SCENARIO_SEED")
+        .ok()
+        .map(|v| v.parse::<u64>())
+        .transpose()
+}
+
 #[must_use]
 pub fn nomos_log_level() -> Option<String> {
     env::var("LOGOS_BLOCKCHAIN_LOG_LEVEL").ok()
@@ -71,3 +108,13 @@ pub fn nomos_otlp_endpoint() -> Option<String> {
 pub fn nomos_otlp_metrics_endpoint() -> Option<String> {
     env::var("LOGOS_BLOCKCHAIN_OTLP_METRICS_ENDPOINT").ok()
 }
+
+#[must_use]
+pub fn cucumber_deployer() -> Option<String> {
+    env::var("LOGOS_BLOCKCHAIN_CUCUMBER_DEPLOYER").ok()
+}
+
+#[must_use]
+pub fn cucumber_run_features() -> bool {
+    env::var("LOGOS_BLOCKCHAIN_CUCUMBER_RUN").is_ok_and(|val| val.eq_ignore_ascii_case("true"))
+}
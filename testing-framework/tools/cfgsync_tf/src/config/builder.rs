@@ -13,6 +13,10 @@ use testing_framework_config::topology::configs::{
         sync_utxos_with_genesis,
     },
     network::NetworkParams,
+    node_spec::{
+        NodeSpecError, NodeSpecs, apply_initial_peer_specs, consensus_params_with_specs,
+        retain_blend_core_providers, validate_node_specs,
+    },
     time::default_time_config,
     wallet::WalletConfig,
 };
@@ -31,18 +35,22 @@ use crate::{
 
 pub fn create_node_configs(
     consensus_params: &ConsensusParams,
+    network_params: &NetworkParams,
     tracing_settings: &TracingSettings,
     wallet_config: &WalletConfig,
     ids: Option<Vec<[u8; 32]>>,
     blend_ports: Option<Vec<u16>>,
+    node_specs: &NodeSpecs,
     hosts: Vec<Host>,
 ) -> Result<HashMap<Host, GeneralConfig>, NodeConfigBuildError> {
     try_create_node_configs(
         consensus_params,
+        network_params,
         tracing_settings,
         wallet_config,
         ids,
         blend_ports,
+        node_specs,
         hosts,
     )
 }
@@ -57,6 +65,8 @@ pub enum NodeConfigBuildError {
     Base(#[from] BaseConfigError),
     #[error(transparent)]
     Genesis(#[from] ConsensusConfigError),
+    #[error(transparent)]
+    NodeSpec(#[from] NodeSpecError),
     #[error("failed to allocate an available UDP port")]
     PortAllocFailed,
     #[error("failed to parse multiaddr '{value}': {message}")]
@@ -78,32 +88,40 @@ pub enum NodeConfigBuildError {
 
 pub fn try_create_node_configs(
     consensus_params: &ConsensusParams,
+    network_params: &NetworkParams,
     tracing_settings: &TracingSettings,
     wallet_config: &WalletConfig,
     ids: Option<Vec<[u8; 32]>>,
     blend_ports: Option<Vec<u16>>,
+    node_specs: &NodeSpecs,
     hosts: Vec<Host>,
 ) -> Result<HashMap<Host, GeneralConfig>, NodeConfigBuildError> {
     let hosts = sort_hosts(hosts);
 
     validate_inputs(&hosts, consensus_params, ids.as_ref(), blend_ports.as_ref())?;
 
+    validate_node_specs(node_specs, hosts.len())?;
+
     let ids = generate_ids(consensus_params.n_participants, ids);
     let blend_ports = resolve_blend_ports(&hosts, blend_ports);
+    let consensus_params =
+        consensus_params_with_specs(consensus_params, wallet_config, ids.len(), node_specs)?;
 
     let BaseConfigs {
         mut consensus_configs,
         bootstrap_configs,
-        network_configs,
+        mut network_configs,
         blend_configs,
     } = build_base_configs(
         &ids,
-        consensus_params,
-        &NetworkParams::default(),
+        &consensus_params,
+        network_params,
         wallet_config,
         &blend_ports,
     )?;
 
+    apply_initial_peer_specs(&mut network_configs, node_specs);
+
     let api_configs = build_api_configs(&hosts)?;
     let mut configured_hosts = HashMap::new();
 
@@ -124,7 +142,10 @@ pub fn try_create_node_configs(
         &peer_ids,
     )?;
 
-    let providers = try_create_providers(&hosts, &consensus_configs, &blend_configs)?;
+    let providers = retain_blend_core_providers(
+        try_create_providers(&hosts, &consensus_configs, &blend_configs)?,
+        node_specs,
+    );
 
     let first_consensus = consensus_configs
         .get(0)
@@ -183,19 +204,21 @@ pub fn try_create_node_configs(
             update_tracing_identifier(tracing_settings.clone(), host.identifier.clone());
         let time_config = default_time_config();
 
-        configured_hosts.insert(
-            host.clone(),
-            GeneralConfig {
-                consensus_config,
-                bootstrapping_config: bootstrap_configs[i].clone(),
-                network_config,
-                blend_config,
-                api_config,
-                tracing_config,
-                time_config,
-                kms_config: kms_configs[i].clone(),
-            },
-        );
+        let mut general = GeneralConfig {
+            consensus_config,
+            bootstrapping_config: bootstrap_configs[i].clone(),
+            network_config,
+            blend_config,
+            api_config,
+            tracing_config,
+            time_config,
+            kms_config: kms_configs[i].clone(),
+        };
+        if let Some(spec) = node_specs.get(&i) {
+            spec.apply_to(i, &mut general)?;
+        }
+
+        configured_hosts.insert(host.clone(), general);
     }
 
     Ok(configured_hosts)
@@ -2,7 +2,8 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use lb_tracing_service::TracingSettings;
 use testing_framework_config::topology::configs::{
-    GeneralConfig, consensus::ConsensusParams, wallet::WalletConfig,
+    GeneralConfig, consensus::ConsensusParams, network::NetworkParams, node_spec::NodeSpecs,
+    wallet::WalletConfig,
 };
 use tokio::{
     sync::{Mutex, oneshot::Sender},
@@ -24,28 +25,34 @@ pub struct ConfigRepo {
     waiting_hosts: Mutex<HashMap<Host, Sender<RepoResponse>>>,
     n_hosts: usize,
     consensus_params: ConsensusParams,
+    network_params: NetworkParams,
     tracing_settings: TracingSettings,
     wallet_config: WalletConfig,
     timeout_duration: Duration,
     ids: Option<Vec<[u8; 32]>>,
     blend_ports: Option<Vec<u16>>,
+    node_specs: NodeSpecs,
 }
 
 impl From<CfgSyncConfig> for Arc<ConfigRepo> {
     fn from(config: CfgSyncConfig) -> Self {
         let consensus_params = config.to_consensus_params();
+        let network_params = config.to_network_params();
         let tracing_settings = config.to_tracing_settings();
         let wallet_config = config.wallet_config();
         let ids = config.ids;
         let blend_ports = config.blend_ports;
+        let node_specs = config.node_specs;
 
         ConfigRepo::new(
             config.n_hosts,
             consensus_params,
+            network_params,
             tracing_settings,
             wallet_config,
             ids,
             blend_ports,
+            node_specs,
             Duration::from_secs(config.timeout),
         )
     }
@@ -56,20 +63,24 @@ impl ConfigRepo {
     pub fn new(
         n_hosts: usize,
         consensus_params: ConsensusParams,
+        network_params: NetworkParams,
         tracing_settings: TracingSettings,
         wallet_config: WalletConfig,
         ids: Option<Vec<[u8; 32]>>,
         blend_ports: Option<Vec<u16>>,
+        node_specs: NodeSpecs,
         timeout_duration: Duration,
     ) -> Arc<Self> {
         let repo = Arc::new(Self {
             waiting_hosts: Mutex::new(HashMap::new()),
             n_hosts,
             consensus_params,
+            network_params,
             tracing_settings,
             wallet_config,
             ids,
             blend_ports,
+            node_specs,
             timeout_duration,
         });
 
@@ -140,10 +151,12 @@ fn generate_node_configs(
 ) -> Result<HashMap<Host, GeneralConfig>, String> {
     try_create_node_configs(
         &repo.consensus_params,
+        &repo.network_params,
         &repo.tracing_settings,
         &repo.wallet_config,
         repo.ids.clone(),
         repo.blend_ports.clone(),
+        &repo.node_specs,
         hosts,
     )
     .map_err(|err| {
@@ -12,7 +12,13 @@ use serde_json::{Value, json, to_value};
 use serde_with::serde_as;
 use testing_framework_config::{
     nodes::node::create_node_config,
-    topology::configs::{consensus::ConsensusParams, wallet::WalletConfig},
+    topology::configs::{
+        consensus::ConsensusParams,
+        network::{Libp2pNetworkLayout, NetworkParams},
+        node_spec::NodeSpecs,
+        stake::StakeDistribution,
+        wallet::WalletConfig,
+    },
 };
 use tokio::sync::oneshot::channel;
 
@@ -33,6 +39,12 @@ pub struct CfgSyncConfig {
     pub active_slot_coeff: f64,
     pub wallet: WalletConfig,
     #[serde(default)]
+    pub stake_distribution: StakeDistribution,
+    #[serde(default)]
+    pub node_specs: NodeSpecs,
+    #[serde(default)]
+    pub network_layout: Libp2pNetworkLayout,
+    #[serde(default)]
     pub ids: Option<Vec<[u8; 32]>>,
     #[serde(default)]
     pub blend_ports: Option<Vec<u16>>,
@@ -68,11 +80,19 @@ impl CfgSyncConfig {
     }
 
     #[must_use]
-    pub const fn to_consensus_params(&self) -> ConsensusParams {
+    pub fn to_consensus_params(&self) -> ConsensusParams {
         ConsensusParams {
             n_participants: self.n_hosts,
             security_param: self.security_param,
             active_slot_coeff: self.active_slot_coeff,
+            stake_distribution: self.stake_distribution.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_network_params(&self) -> NetworkParams {
+        NetworkParams {
+            libp2p_network_layout: self.network_layout.clone(),
         }
     }
 
@@ -9,8 +9,14 @@ use testing_framework_core::{
 };
 
 use crate::{
-    expectations::ConsensusLiveness,
-    workloads::{chaos::RandomRestartWorkload, transaction},
+    expectations::{
+        ChainSafety, ConsensusLiveness, LedgerConservation, MaxBlockLag, MaxRss, MemoryGrowth,
+        MempoolExpectation, MempoolThresholds,
+    },
+    workloads::{
+        channel::ChannelWorkload, chaos::RandomRestartWorkload,
+        partition::NetworkPartitionWorkload, transaction,
+    },
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -35,10 +41,64 @@ pub trait ScenarioBuilderExt<Caps>: Sized {
         self,
         f: impl FnOnce(TransactionFlowBuilder<Caps>) -> TransactionFlowBuilder<Caps>,
     ) -> CoreScenarioBuilder<Caps>;
+
+    /// Configure an open-loop load generator targeting transactions per
+    /// second.
+    fn load(self) -> LoadGeneratorBuilder<Caps>;
+
+    /// Configure an open-loop load generator via closure.
+    fn load_with(
+        self,
+        f: impl FnOnce(LoadGeneratorBuilder<Caps>) -> LoadGeneratorBuilder<Caps>,
+    ) -> CoreScenarioBuilder<Caps>;
+
+    #[must_use]
+    /// Submit invalid transactions for the whole run and check that nodes
+    /// reject them. Configure the workload via closure.
+    fn adversarial_transactions_with(
+        self,
+        f: impl FnOnce(transaction::AdversarialWorkload) -> transaction::AdversarialWorkload,
+    ) -> Self;
+
+    #[must_use]
+    /// Keep appending parent-linked inscriptions to a set of channels and
+    /// check their on-chain ordering. Configure the workload via closure.
+    fn channels_with(self, f: impl FnOnce(ChannelWorkload) -> ChannelWorkload) -> Self;
+
     #[must_use]
     /// Attach a consensus liveness expectation.
     fn expect_consensus_liveness(self) -> Self;
 
+    #[must_use]
+    /// Attach a chain safety expectation: nodes must agree on every
+    /// finalized block.
+    fn expect_chain_safety(self) -> Self;
+
+    #[must_use]
+    /// Fail as soon as any node falls more than `max_lag` blocks behind the
+    /// highest node while workloads run.
+    fn expect_max_block_lag(self, max_lag: u64) -> Self;
+
+    #[must_use]
+    /// Replay the observed chain from genesis and check that no note is spent
+    /// twice, no value is created and seeded wallets keep their funds.
+    fn expect_ledger_conservation(self) -> Self;
+
+    #[must_use]
+    /// Sample mempool depth during the run and require every node's mempool
+    /// to drain to at most `max_pending` transactions afterwards.
+    fn expect_mempool_drain(self, max_pending: usize) -> Self;
+
+    #[must_use]
+    /// Fail as soon as any node's resident memory exceeds `limit_bytes`.
+    /// Needs a deployer that samples resource usage.
+    fn expect_max_rss(self, limit_bytes: u64) -> Self;
+
+    #[must_use]
+    /// Require every node's memory to grow by less than `max_mib_per_min`
+    /// over the run, ignoring the first `warmup`.
+    fn expect_memory_growth_below(self, max_mib_per_min: f64, warmup: Duration) -> Self;
+
     #[must_use]
     /// Seed deterministic wallets with total funds split across `users`.
     fn initialize_wallet(self, total_funds: u64, users: usize) -> Self;
@@ -56,10 +116,58 @@ impl<Caps> ScenarioBuilderExt<Caps> for CoreScenarioBuilder<Caps> {
         f(self.transactions()).apply()
     }
 
+    fn load(self) -> LoadGeneratorBuilder<Caps> {
+        LoadGeneratorBuilder::new(self)
+    }
+
+    fn load_with(
+        self,
+        f: impl FnOnce(LoadGeneratorBuilder<Caps>) -> LoadGeneratorBuilder<Caps>,
+    ) -> CoreScenarioBuilder<Caps> {
+        f(self.load()).apply()
+    }
+
+    fn adversarial_transactions_with(
+        self,
+        f: impl FnOnce(transaction::AdversarialWorkload) -> transaction::AdversarialWorkload,
+    ) -> Self {
+        self.with_workload(f(transaction::AdversarialWorkload::new()))
+    }
+
+    fn channels_with(self, f: impl FnOnce(ChannelWorkload) -> ChannelWorkload) -> Self {
+        self.with_workload(f(ChannelWorkload::new()))
+    }
+
     fn expect_consensus_liveness(self) -> Self {
         self.with_expectation(ConsensusLiveness::default())
     }
 
+    fn expect_chain_safety(self) -> Self {
+        self.with_expectation(ChainSafety::new())
+    }
+
+    fn expect_max_block_lag(self, max_lag: u64) -> Self {
+        self.with_expectation(MaxBlockLag::new(max_lag))
+    }
+
+    fn expect_ledger_conservation(self) -> Self {
+        self.with_expectation(LedgerConservation::new())
+    }
+
+    fn expect_mempool_drain(self, max_pending: usize) -> Self {
+        self.with_expectation(MempoolExpectation::new(
+            MempoolThresholds::default().with_max_pending(max_pending),
+        ))
+    }
+
+    fn expect_max_rss(self, limit_bytes: u64) -> Self {
+        self.with_expectation(MaxRss::new(limit_bytes))
+    }
+
+    fn expect_memory_growth_below(self, max_mib_per_min: f64, warmup: Duration) -> Self {
+        self.with_expectation(MemoryGrowth::new(max_mib_per_min).with_warmup(warmup))
+    }
+
     fn initialize_wallet(self, total_funds: u64, users: usize) -> Self {
         let Some(user_count) = NonZeroUsize::new(users) else {
             tracing::warn!(
@@ -393,6 +501,9 @@ pub struct TransactionFlowBuilder<Caps> {
     builder: CoreScenarioBuilder<Caps>,
     rate: NonZeroU64,
     users: Option<NonZeroUsize>,
+    chained: Option<transaction::ChainedSpends>,
+    latency: Option<transaction::LatencyThresholds>,
+    mempool: Option<MempoolThresholds>,
 }
 
 impl<Caps> TransactionFlowBuilder<Caps> {
@@ -405,6 +516,9 @@ impl<Caps> TransactionFlowBuilder<Caps> {
             builder,
             rate: Self::default_rate(),
             users: None,
+            chained: None,
+            latency: None,
+            mempool: None,
         }
     }
 
@@ -462,14 +576,57 @@ impl<Caps> TransactionFlowBuilder<Caps> {
         Ok(self)
     }
 
+    #[must_use]
+    /// Keep re-spending included outputs for the whole run, consuming up to
+    /// `fan_in` notes and creating up to `fan_out` notes per transaction
+    /// (zero falls back to 1).
+    pub fn chained_spends(mut self, fan_in: usize, fan_out: usize) -> Self {
+        let width = |value: usize, field: &str| {
+            NonZeroUsize::new(value).unwrap_or_else(|| {
+                tracing::warn!(field, "chained spend width must be non-zero; using 1");
+                NonZeroUsize::MIN
+            })
+        };
+        self.chained = Some(transaction::ChainedSpends::new(
+            width(fan_in, "fan_in"),
+            width(fan_out, "fan_out"),
+        ));
+        self
+    }
+
+    #[must_use]
+    /// Track per-transaction inclusion latency and enforce the given limits.
+    pub const fn inclusion_latency(mut self, thresholds: transaction::LatencyThresholds) -> Self {
+        self.latency = Some(thresholds);
+        self
+    }
+
+    #[must_use]
+    /// Sample mempools during the run, require them to drain afterwards and,
+    /// if set, bound how long each transaction takes to reach every node.
+    pub const fn mempool(mut self, thresholds: MempoolThresholds) -> Self {
+        self.mempool = Some(thresholds);
+        self
+    }
+
     #[must_use]
     /// Attach the transaction workload to the scenario.
     pub fn apply(mut self) -> CoreScenarioBuilder<Caps> {
-        let workload = transaction::Workload::new(self.rate).with_user_limit(self.users);
+        let mut workload = transaction::Workload::new(self.rate).with_user_limit(self.users);
+        if let Some(spends) = self.chained {
+            workload = workload.with_chained_spends(spends);
+        }
+        if let Some(thresholds) = self.latency {
+            workload = workload.with_latency_thresholds(thresholds);
+        }
+        if let Some(thresholds) = self.mempool {
+            workload = workload.with_mempool_thresholds(thresholds);
+        }
 
         tracing::info!(
             rate = self.rate.get(),
             users = self.users.map(|u| u.get()),
+            chained = ?self.chained,
             "attaching transaction workload"
         );
 
@@ -478,6 +635,96 @@ impl<Caps> TransactionFlowBuilder<Caps> {
     }
 }
 
+/// Builder for the open-loop load generator.
+pub struct LoadGeneratorBuilder<Caps> {
+    builder: CoreScenarioBuilder<Caps>,
+    workload: transaction::LoadGenerator,
+}
+
+impl<Caps> LoadGeneratorBuilder<Caps> {
+    fn new(builder: CoreScenarioBuilder<Caps>) -> Self {
+        Self {
+            builder,
+            workload: transaction::LoadGenerator::default(),
+        }
+    }
+
+    #[must_use]
+    /// Sustain a constant rate of `tps` transactions per second.
+    pub fn tps(self, tps: f64) -> Self {
+        self.profile(transaction::RateProfile::Constant { tps })
+    }
+
+    #[must_use]
+    /// Follow a ramp, step or spike profile instead of a constant rate.
+    pub fn profile(mut self, profile: transaction::RateProfile) -> Self {
+        self.workload = self.workload.with_profile(profile);
+        self
+    }
+
+    #[must_use]
+    /// Limit how many wallets seed the note pool (ignores zero).
+    pub fn users(mut self, users: usize) -> Self {
+        match NonZeroUsize::new(users) {
+            Some(value) => self.workload = self.workload.with_user_limit(Some(value)),
+            None => tracing::warn!(
+                users,
+                "load generator user count must be non-zero; keeping previous setting"
+            ),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Number of notes each transaction splits its input into (ignores zero).
+    pub fn fan_out(mut self, fan_out: usize) -> Self {
+        match NonZeroUsize::new(fan_out) {
+            Some(value) => self.workload = self.workload.with_fan_out(value),
+            None => tracing::warn!(
+                fan_out,
+                "load generator fan-out must be non-zero; keeping previous setting"
+            ),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Cap outstanding submissions (ignores zero).
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        match NonZeroUsize::new(max_in_flight) {
+            Some(value) => self.workload = self.workload.with_max_in_flight(value),
+            None => tracing::warn!(
+                max_in_flight,
+                "load generator in-flight cap must be non-zero; keeping previous setting"
+            ),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Fail the scenario if accepted transactions fall below `ratio` of the
+    /// target.
+    pub fn min_achieved_ratio(mut self, ratio: f64) -> Self {
+        self.workload = self.workload.with_min_achieved_ratio(ratio);
+        self
+    }
+
+    #[must_use]
+    /// Track per-transaction inclusion latency and enforce the given limits.
+    pub fn inclusion_latency(mut self, thresholds: transaction::LatencyThresholds) -> Self {
+        self.workload = self.workload.with_latency_thresholds(thresholds);
+        self
+    }
+
+    #[must_use]
+    /// Attach the load generator to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<Caps> {
+        tracing::info!(profile = ?self.workload.profile(), "attaching load generator");
+        self.builder = self.builder.with_workload(self.workload);
+        self.builder
+    }
+}
+
 /// Chaos helpers for scenarios that can control nodes.
 pub trait ChaosBuilderExt: Sized {
     /// Entry point into chaos workloads.
@@ -533,6 +780,24 @@ impl ChaosBuilder {
             include_nodes: true,
         }
     }
+
+    /// Configure a network partition chaos workload.
+    #[must_use]
+    pub fn partition(self) -> ChaosPartitionBuilder {
+        const DEFAULT_PARTITION_GROUPS: usize = 2;
+        const DEFAULT_PARTITION_INITIAL_DELAY: Duration = Duration::from_secs(10);
+        const DEFAULT_PARTITION_DURATION: Duration = Duration::from_secs(30);
+        const DEFAULT_PARTITION_HEAL_DURATION: Duration = Duration::from_secs(30);
+
+        ChaosPartitionBuilder {
+            builder: self.builder,
+            groups: DEFAULT_PARTITION_GROUPS,
+            initial_delay: DEFAULT_PARTITION_INITIAL_DELAY,
+            partition_duration: DEFAULT_PARTITION_DURATION,
+            heal_duration: DEFAULT_PARTITION_HEAL_DURATION,
+            cycles: 1,
+        }
+    }
 }
 
 pub struct ChaosRestartBuilder {
@@ -620,3 +885,78 @@ impl ChaosRestartBuilder {
         self.builder
     }
 }
+
+pub struct ChaosPartitionBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    groups: usize,
+    initial_delay: Duration,
+    partition_duration: Duration,
+    heal_duration: Duration,
+    cycles: usize,
+}
+
+impl ChaosPartitionBuilder {
+    #[must_use]
+    /// Number of groups to split the topology into (at least two).
+    pub fn groups(mut self, groups: usize) -> Self {
+        if groups < 2 {
+            tracing::warn!(
+                groups,
+                "chaos partition needs at least two groups; keeping previous value"
+            );
+        } else {
+            self.groups = groups;
+        }
+        self
+    }
+
+    #[must_use]
+    /// Delay before the first partition is applied.
+    pub const fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    #[must_use]
+    /// How long each partition is held before healing.
+    pub fn partition_duration(mut self, duration: Duration) -> Self {
+        if duration.is_zero() {
+            tracing::warn!("chaos partition duration must be non-zero; keeping previous value");
+        } else {
+            self.partition_duration = duration;
+        }
+        self
+    }
+
+    #[must_use]
+    /// How long the network stays healed between partition cycles.
+    pub const fn heal_duration(mut self, duration: Duration) -> Self {
+        self.heal_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// Number of partition/heal cycles to run.
+    pub fn cycles(mut self, cycles: usize) -> Self {
+        if cycles == 0 {
+            tracing::warn!("chaos partition cycles must be non-zero; keeping previous value");
+        } else {
+            self.cycles = cycles;
+        }
+        self
+    }
+
+    #[must_use]
+    /// Finalize the partition workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        let workload = NetworkPartitionWorkload::new(
+            self.groups,
+            self.initial_delay,
+            self.partition_duration,
+            self.heal_duration,
+            self.cycles,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug)]
+/// Continuous invariant: no reachable node may fall more than `max_lag`
+/// blocks behind the highest node at any sampled point during the run.
+///
+/// Nodes that cannot be queried (e.g. while chaos restarts them) are skipped
+/// for that sample rather than counted as lagging.
+pub struct MaxBlockLag {
+    max_lag: u64,
+    interval: Duration,
+    fail_fast: bool,
+}
+
+impl MaxBlockLag {
+    #[must_use]
+    pub const fn new(max_lag: u64) -> Self {
+        Self {
+            max_lag,
+            interval: CHECK_INTERVAL,
+            fail_fast: true,
+        }
+    }
+
+    #[must_use]
+    /// Sets how often heights are sampled while workloads run.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Records violations and fails at evaluation instead of aborting the run.
+    pub const fn without_fail_fast(mut self) -> Self {
+        self.fail_fast = false;
+        self
+    }
+
+    /// Queries every node's height; `None` marks a node that did not answer.
+    async fn sample(ctx: &RunContext) -> Vec<(String, Option<u64>)> {
+        let mut heights = Vec::new();
+        for (idx, client) in ctx.node_clients().all_clients().iter().enumerate() {
+            let height = match client.consensus_info().await {
+                Ok(info) => Some(info.height),
+                Err(err) => {
+                    tracing::debug!(node = idx, %err, "block lag: consensus_info failed");
+                    None
+                }
+            };
+            heights.push((format!("node-{idx}"), height));
+        }
+        heights
+    }
+}
+
+#[derive(Debug, Error)]
+enum MaxBlockLagError {
+    #[error("{node} at height {height} is {lag} blocks behind {leader} (max_lag={max_lag})")]
+    Lagging {
+        node: String,
+        height: u64,
+        lag: u64,
+        leader: String,
+        max_lag: u64,
+    },
+}
+
+/// Fails when the lowest reachable node is more than `max_lag` blocks behind
+/// the highest one. Unreachable nodes are ignored.
+fn check_lag(heights: &[(String, Option<u64>)], max_lag: u64) -> Result<(), MaxBlockLagError> {
+    let reachable = heights
+        .iter()
+        .filter_map(|(node, height)| height.map(|height| (node, height)));
+    let Some((leader, max_height)) = reachable.clone().max_by_key(|(_, height)| *height) else {
+        return Ok(());
+    };
+    let Some((node, height)) = reachable.min_by_key(|(_, height)| *height) else {
+        return Ok(());
+    };
+
+    let lag = max_height - height;
+    if lag > max_lag {
+        return Err(MaxBlockLagError::Lagging {
+            node: node.clone(),
+            height,
+            lag,
+            leader: leader.clone(),
+            max_lag,
+        });
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Expectation for MaxBlockLag {
+    fn name(&self) -> &'static str {
+        "max_block_lag"
+    }
+
+    fn check_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    async fn check(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let heights = Self::sample(ctx).await;
+        check_lag(&heights, self.max_lag).map_err(Into::into)
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.check(ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaxBlockLagError, check_lag};
+
+    fn heights(values: &[Option<u64>]) -> Vec<(String, Option<u64>)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, height)| (format!("node-{idx}"), *height))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_lag_within_the_limit() {
+        assert!(check_lag(&heights(&[Some(10), Some(8), Some(9)]), 2).is_ok());
+        assert!(check_lag(&heights(&[]), 0).is_ok());
+    }
+
+    #[test]
+    fn reports_the_lowest_node_over_the_limit() {
+        let err = check_lag(&heights(&[Some(10), Some(6), Some(7)]), 2)
+            .expect_err("node-1 lags by 4 blocks");
+
+        let MaxBlockLagError::Lagging {
+            node,
+            height,
+            lag,
+            leader,
+            max_lag,
+        } = err;
+        assert_eq!(node, "node-1");
+        assert_eq!(height, 6);
+        assert_eq!(lag, 4);
+        assert_eq!(leader, "node-0");
+        assert_eq!(max_lag, 2);
+    }
+
+    #[test]
+    fn ignores_unreachable_nodes() {
+        assert!(check_lag(&heights(&[Some(10), None, Some(9)]), 1).is_ok());
+        assert!(check_lag(&heights(&[None, None]), 0).is_ok());
+
+        let err = check_lag(&heights(&[None, Some(3), Some(9)]), 1)
+            .expect_err("reachable nodes still lag");
+        assert!(
+            err.to_string()
+                .starts_with("node-1 at height 3 is 6 blocks behind node-2")
+        );
+    }
+}
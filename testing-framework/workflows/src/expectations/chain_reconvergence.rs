@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use lb_core::header::HeaderId;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::time::sleep;
+
+const POLL_ATTEMPTS: usize = 30;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug)]
+/// Checks that all nodes agree on the same tip at some point after the run,
+/// i.e. that divergent chains were reconciled by fork choice.
+pub struct ChainReconvergence {
+    attempts: usize,
+    interval: Duration,
+}
+
+impl Default for ChainReconvergence {
+    fn default() -> Self {
+        Self {
+            attempts: POLL_ATTEMPTS,
+            interval: POLL_INTERVAL,
+        }
+    }
+}
+
+impl ChainReconvergence {
+    #[must_use]
+    /// Sets how many times tips are sampled before giving up.
+    pub const fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    #[must_use]
+    /// Sets the delay between tip samples.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+#[derive(Debug, Error)]
+enum ChainReconvergenceError {
+    #[error("chain reconvergence requires at least one node")]
+    MissingParticipants,
+    #[error("nodes did not converge on a single tip after {attempts} samples:\n{details}")]
+    Diverged { attempts: usize, details: String },
+}
+
+struct TipSample {
+    label: String,
+    height: u64,
+    tip: HeaderId,
+}
+
+#[async_trait]
+impl Expectation for ChainReconvergence {
+    fn name(&self) -> &'static str {
+        "chain_reconvergence"
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err(ChainReconvergenceError::MissingParticipants.into());
+        }
+
+        let attempts = self.attempts.max(1);
+        let mut last_samples = Vec::new();
+        for attempt in 0..attempts {
+            let mut samples = Vec::with_capacity(clients.len());
+            for (idx, client) in clients.iter().enumerate() {
+                match client.consensus_info().await {
+                    Ok(info) => samples.push(TipSample {
+                        label: format!("node-{idx}"),
+                        height: info.height,
+                        tip: info.tip,
+                    }),
+                    Err(err) => {
+                        tracing::debug!(node = idx, %err, attempt, "consensus_info failed");
+                    }
+                }
+            }
+
+            if converged(&samples, clients.len()) {
+                tracing::info!(
+                    attempt,
+                    height = samples[0].height,
+                    tip = ?samples[0].tip,
+                    "chain reconvergence expectation satisfied"
+                );
+                return Ok(());
+            }
+
+            last_samples = samples;
+            sleep(self.interval).await;
+        }
+
+        Err(ChainReconvergenceError::Diverged {
+            attempts,
+            details: describe_samples(&last_samples),
+        }
+        .into())
+    }
+}
+
+/// Whether every one of the `nodes` nodes answered and all report the same
+/// tip.
+fn converged(samples: &[TipSample], nodes: usize) -> bool {
+    nodes > 0 && samples.len() == nodes && samples.windows(2).all(|pair| pair[0].tip == pair[1].tip)
+}
+
+fn describe_samples(samples: &[TipSample]) -> String {
+    samples
+        .iter()
+        .map(|sample| {
+            format!(
+                "- {} (height={}, tip={:?})",
+                sample.label, sample.height, sample.tip
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_core::header::HeaderId;
+
+    use super::{TipSample, converged, describe_samples};
+
+    fn sample(index: usize, height: u64, tip: u8) -> TipSample {
+        TipSample {
+            label: format!("node-{index}"),
+            height,
+            tip: HeaderId::from([tip; 32]),
+        }
+    }
+
+    #[test]
+    fn nodes_agreeing_on_a_tip_have_converged() {
+        let samples = vec![sample(0, 10, 7), sample(1, 10, 7), sample(2, 10, 7)];
+        assert!(converged(&samples, 3));
+        assert!(converged(&samples[..1], 1));
+        assert!(!converged(&[], 0));
+    }
+
+    #[test]
+    fn divergent_or_unreachable_nodes_have_not_converged() {
+        let diverged = vec![sample(0, 10, 7), sample(1, 10, 7), sample(2, 9, 3)];
+        assert!(!converged(&diverged, 3));
+
+        let unreachable = vec![sample(0, 10, 7), sample(2, 10, 7)];
+        assert!(!converged(&unreachable, 3));
+
+        let details = describe_samples(&diverged);
+        assert_eq!(details.lines().count(), 3);
+        assert!(
+            details
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("- node-2 (height=9")
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use async_trait::async_trait;
+use lb_core::header::HeaderId;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, RunContext},
+};
+use thiserror::Error;
+use tokio::time::sleep;
+
+const REQUEST_RETRIES: usize = 5;
+const REQUEST_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Checks that no two nodes finalized different blocks at the same height.
+///
+/// At capture time the lowest LIB across nodes is recorded as an anchor. At
+/// evaluation every node's chain from its LIB back to that anchor is fetched
+/// via `consensus_headers`, and all nodes must agree on the header at every
+/// height they have both finalized. Header ranges are paged, so nodes that
+/// cap their responses are followed back until the requested header.
+pub struct ChainSafety {
+    anchor: Option<ChainPoint>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ChainPoint {
+    header: HeaderId,
+    height: u64,
+}
+
+/// One node's view at evaluation: its tip, its LIB and the finalized headers
+/// from its LIB back towards the anchor, newest first.
+#[derive(Debug)]
+struct FinalizedChain {
+    tip: ChainPoint,
+    lib: ChainPoint,
+    headers: Vec<HeaderId>,
+}
+
+impl FinalizedChain {
+    fn descends_from(&self, anchor: ChainPoint) -> bool {
+        self.headers.last() == Some(&anchor.header)
+    }
+
+    /// Header the node holds at the anchor height, if it finalized that far.
+    fn header_at(&self, anchor: ChainPoint) -> Option<HeaderId> {
+        let depth = self.lib.height.checked_sub(anchor.height)?;
+        self.headers.get(usize::try_from(depth).ok()?).copied()
+    }
+}
+
+#[derive(Debug, Error)]
+enum ChainSafetyError {
+    #[error("chain safety requires at least one node")]
+    MissingParticipants,
+    #[error("chain safety anchor was not captured")]
+    MissingAnchor,
+    #[error("{node} request failed: {source}")]
+    RequestFailed {
+        node: String,
+        #[source]
+        source: DynError,
+    },
+    #[error("{node} returned no headers between tip and LIB")]
+    EmptyHeaders { node: String },
+    #[error(
+        "finalized chains do not descend from anchor {anchor:?} at height {height}:\n{details}"
+    )]
+    AnchorNotAncestor {
+        anchor: HeaderId,
+        height: u64,
+        details: String,
+    },
+    #[error("finalized chains diverge at height {height}:\n{details}")]
+    Diverged { height: u64, details: String },
+}
+
+impl ChainSafety {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { anchor: None }
+    }
+
+    /// Returns the node's tip and LIB with their heights. The LIB height is
+    /// counted down from the reported tip height, so both come from one
+    /// consensus snapshot and cannot race with chain growth.
+    async fn chain_points(client: &ApiClient) -> Result<(ChainPoint, ChainPoint), DynError> {
+        let info = client.consensus_info().await?;
+        let headers = header_range(client, info.tip, info.lib, usize::MAX).await?;
+        if headers.last() != Some(&info.lib) {
+            return Err("header range from tip did not reach the LIB".into());
+        }
+        let depth = u64::try_from(headers.len() - 1).unwrap_or(u64::MAX);
+        let tip = ChainPoint {
+            header: info.tip,
+            height: info.height,
+        };
+        let lib = ChainPoint {
+            header: info.lib,
+            height: info.height.saturating_sub(depth),
+        };
+        Ok((tip, lib))
+    }
+
+    /// Finalized headers of one node from its LIB back towards `anchor`,
+    /// going no deeper than the anchor height.
+    async fn finalized_chain(
+        node: &str,
+        client: &ApiClient,
+        anchor: ChainPoint,
+    ) -> Result<FinalizedChain, ChainSafetyError> {
+        let (tip, lib) = with_retries(node, || Self::chain_points(client)).await?;
+        let span = usize::try_from(lib.height.saturating_sub(anchor.height))
+            .unwrap_or(usize::MAX)
+            .saturating_add(1);
+        let headers = with_retries(node, || async move {
+            header_range(client, lib.header, anchor.header, span).await
+        })
+        .await?;
+
+        if headers.is_empty() {
+            return Err(ChainSafetyError::EmptyHeaders {
+                node: node.to_owned(),
+            });
+        }
+        Ok(FinalizedChain { tip, lib, headers })
+    }
+}
+
+#[async_trait]
+impl Expectation for ChainSafety {
+    fn name(&self) -> &'static str {
+        "chain_safety"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err(ChainSafetyError::MissingParticipants.into());
+        }
+
+        let mut anchor: Option<ChainPoint> = None;
+        for (idx, client) in clients.iter().enumerate() {
+            let node = format!("node-{idx}");
+            let (_, point) = with_retries(&node, || Self::chain_points(client)).await?;
+            if anchor.is_none_or(|current| point.height < current.height) {
+                anchor = Some(point);
+            }
+        }
+
+        tracing::info!(anchor = ?anchor, "chain safety: captured anchor");
+        self.anchor = anchor;
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let anchor = self.anchor.ok_or(ChainSafetyError::MissingAnchor)?;
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err(ChainSafetyError::MissingParticipants.into());
+        }
+
+        let mut chains = Vec::with_capacity(clients.len());
+        let mut detached = Vec::new();
+        for (idx, client) in clients.iter().enumerate() {
+            let node = format!("node-{idx}");
+            let chain = Self::finalized_chain(&node, client, anchor).await?;
+            tracing::debug!(
+                node = %node,
+                tip_height = chain.tip.height,
+                lib_height = chain.lib.height,
+                "chain safety: sampled finalized chain"
+            );
+            if chain.descends_from(anchor) {
+                let mut headers = chain.headers;
+                headers.reverse();
+                chains.push((node, headers));
+            } else {
+                detached.push(describe_detached(&node, &chain, anchor));
+            }
+        }
+
+        if !detached.is_empty() {
+            return Err(ChainSafetyError::AnchorNotAncestor {
+                anchor: anchor.header,
+                height: anchor.height,
+                details: detached.join("\n"),
+            }
+            .into());
+        }
+
+        if let Some((offset, groups)) = first_divergence(&chains) {
+            let details = groups
+                .iter()
+                .map(|(header, nodes)| format!("- {header:?}: {}", nodes.join(", ")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(ChainSafetyError::Diverged {
+                height: anchor.height + offset as u64,
+                details,
+            }
+            .into());
+        }
+
+        let common = chains
+            .iter()
+            .map(|(_, chain)| chain.len())
+            .min()
+            .unwrap_or(0);
+        tracing::info!(
+            anchor_height = anchor.height,
+            common_finalized_height = anchor.height + common.saturating_sub(1) as u64,
+            "chain safety expectation satisfied"
+        );
+        Ok(())
+    }
+}
+
+/// Headers from `from` back to `to`, newest first, at most `limit` of them.
+/// Nodes may cap each response, so this keeps paging from the oldest header
+/// received until it reaches `to`, the limit, or a page adds nothing new.
+async fn header_range(
+    client: &ApiClient,
+    from: HeaderId,
+    to: HeaderId,
+    limit: usize,
+) -> Result<Vec<HeaderId>, DynError> {
+    let mut headers: Vec<HeaderId> = Vec::new();
+    let mut cursor = from;
+    loop {
+        let page = client.consensus_headers(Some(cursor), Some(to)).await?;
+        let before = headers.len();
+        // Pages after the first start at the cursor, which is already held.
+        let skip = usize::from(!headers.is_empty() && page.first() == Some(&cursor));
+        headers.extend(page.into_iter().skip(skip));
+        match headers.last() {
+            Some(last) if *last != to && headers.len() < limit && headers.len() > before => {
+                cursor = *last;
+            }
+            _ => break,
+        }
+    }
+    headers.truncate(limit);
+    Ok(headers)
+}
+
+fn describe_detached(node: &str, chain: &FinalizedChain, anchor: ChainPoint) -> String {
+    let at_anchor = chain
+        .header_at(anchor)
+        .map_or_else(|| "nothing".to_owned(), |header| format!("{header:?}"));
+    format!(
+        "- {node}: tip {:?} at height {}, LIB {:?} at height {}, holds {at_anchor} at height {}",
+        chain.tip.header, chain.tip.height, chain.lib.header, chain.lib.height, anchor.height
+    )
+}
+
+/// Finds the lowest index where nodes that finalized that index disagree, and
+/// groups the nodes by the header they hold there.
+fn first_divergence(
+    chains: &[(String, Vec<HeaderId>)],
+) -> Option<(usize, Vec<(HeaderId, Vec<String>)>)> {
+    let longest = chains.iter().map(|(_, chain)| chain.len()).max()?;
+    (0..longest).find_map(|offset| {
+        let mut groups: BTreeMap<[u8; 32], (HeaderId, Vec<String>)> = BTreeMap::new();
+        for (node, chain) in chains {
+            if let Some(header) = chain.get(offset) {
+                groups
+                    .entry((*header).into())
+                    .or_insert_with(|| (*header, Vec::new()))
+                    .1
+                    .push(node.clone());
+            }
+        }
+        (groups.len() > 1).then(|| (offset, groups.into_values().collect()))
+    })
+}
+
+async fn with_retries<T, F, Fut>(node: &str, mut request: F) -> Result<T, ChainSafetyError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DynError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(source) if attempt + 1 >= REQUEST_RETRIES => {
+                tracing::warn!(node, %source, "chain safety: request failed after retries");
+                return Err(ChainSafetyError::RequestFailed {
+                    node: node.to_owned(),
+                    source,
+                });
+            }
+            Err(_) => {
+                attempt += 1;
+                sleep(REQUEST_RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_core::header::HeaderId;
+
+    use super::{ChainPoint, FinalizedChain, describe_detached, first_divergence};
+
+    fn header(byte: u8) -> HeaderId {
+        HeaderId::from([byte; 32])
+    }
+
+    #[test]
+    fn reports_first_conflicting_height_and_nodes() {
+        let chains = vec![
+            ("node-0".to_owned(), vec![header(0), header(1), header(2)]),
+            ("node-1".to_owned(), vec![header(0), header(1)]),
+            ("node-2".to_owned(), vec![header(0), header(1), header(9)]),
+        ];
+
+        let (offset, groups) = first_divergence(&chains).expect("divergence");
+        assert_eq!(offset, 2);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].1, vec!["node-0".to_owned()]);
+        assert_eq!(groups[1].1, vec!["node-2".to_owned()]);
+
+        assert!(first_divergence(&chains[..2]).is_none());
+    }
+
+    #[test]
+    fn detached_nodes_are_named_with_tip_lib_and_anchor_header() {
+        let anchor = ChainPoint {
+            header: header(1),
+            height: 10,
+        };
+        let chain = FinalizedChain {
+            tip: ChainPoint {
+                header: header(7),
+                height: 15,
+            },
+            lib: ChainPoint {
+                header: header(6),
+                height: 12,
+            },
+            headers: vec![header(6), header(5), header(4)],
+        };
+
+        assert!(!chain.descends_from(anchor));
+        assert_eq!(chain.header_at(anchor), Some(header(4)));
+        let details = describe_detached("node-2", &chain, anchor);
+        assert!(details.starts_with("- node-2: tip "));
+        assert!(details.contains("at height 15"));
+        assert!(details.contains("at height 12"));
+        assert!(details.contains(&format!("holds {:?} at height 10", header(4))));
+
+        let behind = ChainPoint {
+            header: header(6),
+            height: 9,
+        };
+        let chain = FinalizedChain {
+            lib: behind,
+            ..chain
+        };
+        assert_eq!(chain.header_at(anchor), None);
+        assert!(describe_detached("node-2", &chain, anchor).contains("holds nothing"));
+    }
+}
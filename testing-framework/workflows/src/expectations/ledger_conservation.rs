@@ -0,0 +1,486 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::{
+    AuthenticatedMantleTx as _, GenesisTx as _, Note, Transaction as _, TxHash, Utxo,
+    ledger::{NoteId, Tx as LedgerTx},
+};
+use lb_key_management_system_service::keys::ZkPublicKey;
+use testing_framework_core::scenario::{BlockRecord, DynError, Expectation, RunContext};
+use thiserror::Error;
+
+/// Violations listed in the failure message; the rest are only counted.
+const MAX_REPORTED_VIOLATIONS: usize = 10;
+
+/// How seeded wallet balances are compared after the run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BalanceCheck {
+    /// Seeded accounts together must still hold their genesis funds, less the
+    /// fees they paid. Fits workloads that move value between accounts.
+    #[default]
+    Total,
+    /// Every seeded account must end with its own genesis balance, less the
+    /// fees it paid. Fits workloads where each account pays itself.
+    PerAccount,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Replays the canonical chain observed by the block feed on top of the
+/// genesis notes and checks the resulting ledger.
+///
+/// Every input must spend a note that exists and was not spent before, no
+/// transaction may create more value than it consumes beyond the configured
+/// reward allowance, and the seeded wallet accounts must end up holding what
+/// the workloads moved around (see [`BalanceCheck`]). Value consumed but not
+/// re-emitted by a transaction is counted as a fee, split between the owners
+/// of its inputs in proportion to the value each put in.
+///
+/// Every canonical block has to still be held by the block feed; a chain with
+/// blocks the feed no longer retains cannot be replayed and fails.
+pub struct LedgerConservation {
+    balances: BalanceCheck,
+    reward_allowance: u64,
+    genesis: Option<GenesisLedger>,
+}
+
+#[derive(Clone, Debug)]
+struct GenesisLedger {
+    tx_hash: TxHash,
+    notes: HashMap<NoteId, Note>,
+    wallets: HashMap<ZkPublicKey, u64>,
+}
+
+#[derive(Debug, Error)]
+enum LedgerConservationError {
+    #[error("ledger conservation requires at least one node")]
+    MissingNodes,
+    #[error("ledger conservation genesis was not captured")]
+    NotCaptured,
+    #[error("block feed has not observed a canonical chain")]
+    EmptyChain,
+    #[error("{missing} of {total} canonical blocks are not held by the block feed")]
+    MissingBlocks { missing: usize, total: usize },
+    #[error("ledger conservation found {count} violation(s):\n{details}")]
+    Violations { count: usize, details: String },
+}
+
+impl LedgerConservation {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Selects how seeded wallet balances are checked.
+    pub const fn with_balance_check(mut self, balances: BalanceCheck) -> Self {
+        self.balances = balances;
+        self
+    }
+
+    #[must_use]
+    /// Total value transactions may create beyond their inputs, e.g. leader
+    /// rewards paid out as ledger notes.
+    pub const fn with_reward_allowance(mut self, allowance: u64) -> Self {
+        self.reward_allowance = allowance;
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for LedgerConservation {
+    fn name(&self) -> &'static str {
+        "ledger_conservation"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.genesis.is_some() {
+            return Ok(());
+        }
+
+        let node = ctx
+            .descriptors()
+            .nodes()
+            .first()
+            .ok_or(LedgerConservationError::MissingNodes)?;
+        let ledger_tx = node
+            .general
+            .consensus_config
+            .genesis_tx
+            .mantle_tx()
+            .ledger_tx
+            .clone();
+        let tx_hash = ledger_tx.hash();
+        let notes = ledger_tx
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, note)| (Utxo::new(tx_hash, idx, *note).id(), *note))
+            .collect::<HashMap<_, _>>();
+
+        let mut wallets = ctx
+            .descriptors()
+            .config()
+            .wallet()
+            .accounts
+            .iter()
+            .map(|account| (account.public_key(), 0))
+            .collect::<HashMap<_, u64>>();
+        for note in notes.values() {
+            if let Some(balance) = wallets.get_mut(&note.pk) {
+                *balance += note.value;
+            }
+        }
+
+        tracing::info!(
+            genesis_notes = notes.len(),
+            wallets = wallets.len(),
+            "ledger conservation captured genesis"
+        );
+        self.genesis = Some(GenesisLedger {
+            tx_hash,
+            notes,
+            wallets,
+        });
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let genesis = self
+            .genesis
+            .as_ref()
+            .ok_or(LedgerConservationError::NotCaptured)?;
+        let feed = ctx.block_feed();
+        let chain = feed.canonical_chain();
+        if chain.is_empty() {
+            return Err(LedgerConservationError::EmptyChain.into());
+        }
+
+        let mut replay = LedgerReplay::new(genesis);
+        // Blocks the feed no longer tracks cannot be checked either.
+        let pruned = feed.pruned_canonical_blocks();
+        let mut missing_blocks = pruned;
+        for header in &chain {
+            match feed.known_record(header) {
+                Some(record) => replay.apply_block(&record),
+                None => missing_blocks += 1,
+            }
+        }
+
+        if replay.minted > self.reward_allowance {
+            replay.violations.push(format!(
+                "transactions created {} more value than they consumed (allowance {})",
+                replay.minted, self.reward_allowance
+            ));
+        }
+        replay.check_wallets(genesis, self.balances);
+
+        if missing_blocks > 0 {
+            return Err(LedgerConservationError::MissingBlocks {
+                missing: missing_blocks,
+                total: pruned + chain.len(),
+            }
+            .into());
+        }
+
+        tracing::info!(
+            blocks = chain.len(),
+            transactions = replay.transactions,
+            unspent_notes = replay.notes.len(),
+            fees = replay.fees,
+            minted = replay.minted,
+            violations = replay.violations.len(),
+            "ledger conservation replayed canonical chain"
+        );
+
+        if replay.violations.is_empty() {
+            return Ok(());
+        }
+        let count = replay.violations.len();
+        let mut details = replay
+            .violations
+            .iter()
+            .take(MAX_REPORTED_VIOLATIONS)
+            .map(|violation| format!("  {violation}"))
+            .collect::<Vec<_>>();
+        if count > MAX_REPORTED_VIOLATIONS {
+            details.push(format!(
+                "  ... and {} more",
+                count - MAX_REPORTED_VIOLATIONS
+            ));
+        }
+        Err(LedgerConservationError::Violations {
+            count,
+            details: details.join("\n"),
+        }
+        .into())
+    }
+}
+
+/// Unspent notes and running totals while replaying the chain.
+struct LedgerReplay {
+    genesis_hash: TxHash,
+    notes: HashMap<NoteId, Note>,
+    spent: HashSet<NoteId>,
+    fees_by_payer: HashMap<ZkPublicKey, u64>,
+    fees: u64,
+    minted: u64,
+    transactions: usize,
+    violations: Vec<String>,
+}
+
+impl LedgerReplay {
+    fn new(genesis: &GenesisLedger) -> Self {
+        Self {
+            genesis_hash: genesis.tx_hash,
+            notes: genesis.notes.clone(),
+            spent: HashSet::new(),
+            fees_by_payer: HashMap::new(),
+            fees: 0,
+            minted: 0,
+            transactions: 0,
+            violations: Vec::new(),
+        }
+    }
+
+    fn apply_block(&mut self, record: &BlockRecord) {
+        for tx in record.block.transactions() {
+            let ledger_tx = &tx.mantle_tx().ledger_tx;
+            if ledger_tx.hash() == self.genesis_hash {
+                continue;
+            }
+            self.apply_tx(&record.header, ledger_tx);
+        }
+    }
+
+    fn apply_tx(&mut self, block: &impl fmt::Debug, ledger_tx: &LedgerTx) {
+        let tx_hash = ledger_tx.hash();
+        self.transactions += 1;
+
+        let mut consumed = 0u64;
+        let mut payers = Vec::<(ZkPublicKey, u64)>::new();
+        for note_id in &ledger_tx.inputs {
+            if let Some(note) = self.notes.remove(note_id) {
+                self.spent.insert(*note_id);
+                consumed += note.value;
+                match payers.iter_mut().find(|(pk, _)| *pk == note.pk) {
+                    Some((_, value)) => *value += note.value,
+                    None => payers.push((note.pk, note.value)),
+                }
+            } else if self.spent.contains(note_id) {
+                self.violations.push(format!(
+                    "tx {tx_hash:?} in block {block:?} spends note {note_id:?} twice"
+                ));
+            } else {
+                self.violations.push(format!(
+                    "tx {tx_hash:?} in block {block:?} spends unknown note {note_id:?}"
+                ));
+            }
+        }
+
+        for (idx, note) in ledger_tx.outputs.iter().enumerate() {
+            self.notes
+                .insert(Utxo::new(tx_hash, idx, *note).id(), *note);
+        }
+
+        let produced = ledger_tx.outputs.iter().map(|note| note.value).sum::<u64>();
+        if produced > consumed {
+            self.minted += produced - consumed;
+        } else {
+            let fee = consumed - produced;
+            self.fees += fee;
+            for (payer, share) in split_fee(fee, consumed, &payers) {
+                *self.fees_by_payer.entry(payer).or_default() += share;
+            }
+        }
+    }
+
+    fn check_wallets(&mut self, genesis: &GenesisLedger, balances: BalanceCheck) {
+        if genesis.wallets.is_empty() {
+            return;
+        }
+
+        let mut actual = genesis
+            .wallets
+            .keys()
+            .map(|pk| (*pk, 0))
+            .collect::<HashMap<_, u64>>();
+        for note in self.notes.values() {
+            if let Some(balance) = actual.get_mut(&note.pk) {
+                *balance += note.value;
+            }
+        }
+        let expected = genesis
+            .wallets
+            .iter()
+            .map(|(pk, funds)| {
+                let fees = self.fees_by_payer.get(pk).copied().unwrap_or_default();
+                (*pk, funds.saturating_sub(fees))
+            })
+            .collect::<HashMap<_, u64>>();
+
+        match balances {
+            BalanceCheck::Total => {
+                let expected_total = expected.values().sum::<u64>();
+                let actual_total = actual.values().sum::<u64>();
+                if expected_total != actual_total {
+                    self.violations.push(format!(
+                        "seeded wallets hold {actual_total}, expected {expected_total}"
+                    ));
+                }
+            }
+            BalanceCheck::PerAccount => {
+                // Sorted so repeated failures list accounts in a stable order.
+                let mismatches = expected
+                    .iter()
+                    .filter_map(|(pk, expected)| {
+                        let actual = actual.get(pk).copied().unwrap_or_default();
+                        (actual != *expected).then(|| (format!("{pk:?}"), (actual, *expected)))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                for (pk, (actual, expected)) in mismatches {
+                    self.violations
+                        .push(format!("wallet {pk} holds {actual}, expected {expected}"));
+                }
+            }
+        }
+    }
+}
+
+/// Splits `fee` between the input owners in proportion to the value each put
+/// into the `consumed` total. The rounding remainder goes to the first owner.
+fn split_fee(fee: u64, consumed: u64, payers: &[(ZkPublicKey, u64)]) -> Vec<(ZkPublicKey, u64)> {
+    if consumed == 0 {
+        return Vec::new();
+    }
+    let mut shares = payers
+        .iter()
+        .map(|(pk, value)| {
+            let share = u128::from(fee) * u128::from(*value) / u128::from(consumed);
+            (*pk, share as u64)
+        })
+        .collect::<Vec<_>>();
+    let assigned = shares.iter().map(|(_, share)| share).sum::<u64>();
+    if let Some((_, first)) = shares.first_mut() {
+        *first += fee - assigned;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use lb_core::mantle::{
+        Note, Transaction as _, Utxo, ledger::Tx as LedgerTx, tx_builder::MantleTxBuilder,
+    };
+    use lb_key_management_system_service::keys::ZkPublicKey;
+    use testing_framework_config::topology::configs::wallet::WalletAccount;
+
+    use super::{BalanceCheck, GenesisLedger, LedgerReplay};
+
+    fn key(index: u64) -> ZkPublicKey {
+        WalletAccount::deterministic(index, 1).public_key()
+    }
+
+    fn ledger_tx(inputs: &[Utxo], outputs: &[Note]) -> LedgerTx {
+        let builder = inputs.iter().fold(MantleTxBuilder::new(), |builder, utxo| {
+            builder.add_ledger_input(*utxo)
+        });
+        outputs
+            .iter()
+            .fold(builder, |builder, note| builder.add_ledger_output(*note))
+            .build()
+            .ledger_tx
+    }
+
+    fn utxos(tx: &LedgerTx) -> Vec<Utxo> {
+        let tx_hash = tx.hash();
+        tx.outputs
+            .iter()
+            .enumerate()
+            .map(|(idx, note)| Utxo::new(tx_hash, idx, *note))
+            .collect()
+    }
+
+    /// Genesis funding `alice` with 100 and `bob` with 50.
+    fn genesis() -> (GenesisLedger, Vec<Utxo>) {
+        let tx = ledger_tx(&[], &[Note::new(100, key(0)), Note::new(50, key(1))]);
+        let utxos = utxos(&tx);
+        let genesis = GenesisLedger {
+            tx_hash: tx.hash(),
+            notes: utxos.iter().map(|utxo| (utxo.id(), utxo.note)).collect(),
+            wallets: HashMap::from([(key(0), 100), (key(1), 50)]),
+        };
+        (genesis, utxos)
+    }
+
+    #[test]
+    fn transfers_conserve_value_less_fees() {
+        let (genesis, notes) = genesis();
+        let mut replay = LedgerReplay::new(&genesis);
+
+        let transfer = ledger_tx(&notes[..1], &[Note::new(60, key(1)), Note::new(30, key(0))]);
+        replay.apply_tx(&"block-1", &transfer);
+        let change = utxos(&transfer);
+        replay.apply_tx(
+            &"block-2",
+            &ledger_tx(&change[1..], &[Note::new(30, key(0))]),
+        );
+
+        assert_eq!(
+            (replay.transactions, replay.fees, replay.minted),
+            (2, 10, 0)
+        );
+        replay.check_wallets(&genesis, BalanceCheck::Total);
+        assert!(replay.violations.is_empty(), "{:?}", replay.violations);
+
+        // Alice paid the fee but sent 60 to Bob, so per-account balances
+        // differ from genesis.
+        replay.check_wallets(&genesis, BalanceCheck::PerAccount);
+        assert_eq!(replay.violations.len(), 2, "{:?}", replay.violations);
+    }
+
+    #[test]
+    fn fees_are_split_by_input_value() {
+        let (genesis, notes) = genesis();
+        let mut replay = LedgerReplay::new(&genesis);
+
+        // 150 in, 120 out: alice put in two thirds, bob one third.
+        replay.apply_tx(
+            &"block-1",
+            &ledger_tx(&notes, &[Note::new(80, key(0)), Note::new(40, key(1))]),
+        );
+
+        assert_eq!(replay.fees, 30);
+        assert_eq!(replay.fees_by_payer[&key(0)], 20);
+        assert_eq!(replay.fees_by_payer[&key(1)], 10);
+        replay.check_wallets(&genesis, BalanceCheck::PerAccount);
+        assert!(replay.violations.is_empty(), "{:?}", replay.violations);
+    }
+
+    #[test]
+    fn reports_double_and_unknown_spends_and_minting() {
+        let (genesis, notes) = genesis();
+        let mut replay = LedgerReplay::new(&genesis);
+
+        replay.apply_tx(
+            &"block-1",
+            &ledger_tx(&notes[..1], &[Note::new(100, key(0))]),
+        );
+        replay.apply_tx(
+            &"block-2",
+            &ledger_tx(&notes[..1], &[Note::new(100, key(0))]),
+        );
+        let unknown = Utxo::new(genesis.tx_hash, 7, Note::new(5, key(1)));
+        replay.apply_tx(&"block-3", &ledger_tx(&[unknown], &[Note::new(5, key(1))]));
+
+        assert_eq!(replay.violations.len(), 2, "{:?}", replay.violations);
+        assert!(replay.violations[0].contains("twice"));
+        assert!(replay.violations[1].contains("unknown note"));
+        // Outputs of transactions with missing inputs count as minted value.
+        assert_eq!(replay.minted, 105);
+    }
+}
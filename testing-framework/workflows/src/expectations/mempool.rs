@@ -0,0 +1,435 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::TxHash;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::time::sleep;
+
+use crate::workloads::transaction::SubmissionTracker;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_LISTED_SLOW: usize = 10;
+
+/// Limits enforced by [`MempoolExpectation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MempoolThresholds {
+    max_pending: usize,
+    drain_timeout: Duration,
+    max_propagation: Option<Duration>,
+    sample_interval: Duration,
+}
+
+impl Default for MempoolThresholds {
+    fn default() -> Self {
+        Self {
+            max_pending: 0,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            max_propagation: None,
+            sample_interval: SAMPLE_INTERVAL,
+        }
+    }
+}
+
+impl MempoolThresholds {
+    #[must_use]
+    /// Every node's mempool must drop to at most `max_pending` transactions
+    /// once workloads stop.
+    pub const fn with_max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending;
+        self
+    }
+
+    #[must_use]
+    /// How long the mempools get to drain after workloads stop.
+    pub const fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Upper bound for a submitted transaction to reach every other node's
+    /// mempool. Requires a submission tracker.
+    pub const fn with_max_propagation(mut self, limit: Duration) -> Self {
+        self.max_propagation = Some(limit);
+        self
+    }
+
+    #[must_use]
+    /// How often mempool depth (and propagation) is sampled while workloads
+    /// run. Propagation times are only as precise as this interval.
+    pub const fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+}
+
+/// Mempool depth of every node at one point of the run.
+#[derive(Clone, Debug)]
+pub struct MempoolSample {
+    /// Time since the first sample.
+    pub elapsed: Duration,
+    /// Pending transactions per node label; `None` when the node could not be
+    /// queried.
+    pub depths: Vec<(String, Option<usize>)>,
+}
+
+impl MempoolSample {
+    /// Deepest mempool in this sample.
+    #[must_use]
+    pub fn max_depth(&self) -> Option<usize> {
+        self.depths.iter().filter_map(|(_, depth)| *depth).max()
+    }
+}
+
+/// Shared log of mempool samples; clones share the same log.
+#[derive(Clone, Debug, Default)]
+pub struct MempoolSamples {
+    inner: Arc<Mutex<Vec<MempoolSample>>>,
+}
+
+impl MempoolSamples {
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<MempoolSample> {
+        self.lock().clone()
+    }
+
+    /// Deepest mempool seen on any node so far.
+    #[must_use]
+    pub fn peak_depth(&self) -> Option<usize> {
+        self.lock()
+            .iter()
+            .filter_map(MempoolSample::max_depth)
+            .max()
+    }
+
+    fn push(&self, sample: MempoolSample) {
+        self.lock().push(sample);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<MempoolSample>> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Samples mempool depth on every node while workloads run and, once they
+/// stop, requires all mempools to drain below a threshold.
+///
+/// With a [`SubmissionTracker`] attached it also follows every submitted
+/// transaction: it counts as propagated once every node other than the one it
+/// was submitted to reports it pending or already in a block.
+#[derive(Clone)]
+pub struct MempoolExpectation {
+    thresholds: MempoolThresholds,
+    tracker: Option<SubmissionTracker>,
+    samples: MempoolSamples,
+    started: Option<Instant>,
+    propagated: HashMap<TxHash, Duration>,
+    last_propagation_sample: Option<Instant>,
+}
+
+#[derive(Debug, Error)]
+enum MempoolError {
+    #[error("mempools did not drain to {max_pending} within {timeout:?}:\n{depths}")]
+    NotDrained {
+        max_pending: usize,
+        timeout: Duration,
+        depths: String,
+    },
+    #[error(
+        "{slow}/{submitted} transactions took longer than {limit:?} to reach every node:\n{details}"
+    )]
+    SlowPropagation {
+        slow: usize,
+        submitted: usize,
+        limit: Duration,
+        details: String,
+    },
+}
+
+impl MempoolExpectation {
+    pub const NAME: &'static str = "mempool";
+
+    #[must_use]
+    pub fn new(thresholds: MempoolThresholds) -> Self {
+        Self {
+            thresholds,
+            tracker: None,
+            samples: MempoolSamples::default(),
+            started: None,
+            propagated: HashMap::new(),
+            last_propagation_sample: None,
+        }
+    }
+
+    #[must_use]
+    /// Follow the transactions recorded in `tracker` for propagation checks.
+    pub fn with_submissions(mut self, tracker: SubmissionTracker) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Depth samples recorded so far.
+    #[must_use]
+    pub fn samples(&self) -> MempoolSamples {
+        self.samples.clone()
+    }
+
+    async fn sample_depths(&mut self, ctx: &RunContext) {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let depths = ctx.node_clients().cluster_client().mempool_depths().await;
+        tracing::debug!(?depths, "mempool depth sample");
+        self.samples.push(MempoolSample {
+            elapsed: started.elapsed(),
+            depths,
+        });
+    }
+
+    /// Polls every node for the tracked transactions that have not reached
+    /// all of them yet.
+    async fn sample_propagation(&mut self, ctx: &RunContext) {
+        let Some(tracker) = &self.tracker else {
+            return;
+        };
+        let pending = tracker
+            .snapshot()
+            .into_iter()
+            .filter(|(hash, _)| !self.propagated.contains_key(hash))
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            return;
+        }
+
+        let hashes = pending.iter().map(|(hash, _)| *hash).collect::<Vec<_>>();
+        let mut accepted_by = vec![0usize; hashes.len()];
+        let clients = ctx.node_clients().all_clients();
+        for (idx, client) in clients.iter().enumerate() {
+            match client.mempool_status(&hashes).await {
+                Ok(statuses) => {
+                    for (count, status) in accepted_by.iter_mut().zip(statuses) {
+                        if status.is_accepted() {
+                            *count += 1;
+                        }
+                    }
+                }
+                Err(err) => tracing::debug!(node = idx, %err, "mempool status request failed"),
+            }
+        }
+
+        // The submission target counts towards `accepted_by`, so every node
+        // has the transaction once the count reaches the cluster size.
+        let now = Instant::now();
+        self.last_propagation_sample = Some(now);
+        for ((hash, record), accepted) in pending.into_iter().zip(accepted_by) {
+            if accepted >= clients.len() {
+                self.propagated
+                    .insert(hash, now.saturating_duration_since(record.submitted_at));
+            }
+        }
+    }
+
+    async fn wait_for_drain(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let max_pending = self.thresholds.max_pending;
+        let deadline = Instant::now() + self.thresholds.drain_timeout;
+        loop {
+            let depths = ctx.node_clients().cluster_client().mempool_depths().await;
+            let drained = depths
+                .iter()
+                .all(|(_, depth)| depth.is_some_and(|depth| depth <= max_pending));
+            if drained {
+                tracing::info!(max_pending, "mempools drained");
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                let mut summary = String::new();
+                for (node, depth) in &depths {
+                    match depth {
+                        Some(depth) => {
+                            let _ = writeln!(summary, "- {node}: {depth} pending");
+                        }
+                        None => {
+                            let _ = writeln!(summary, "- {node}: unreachable");
+                        }
+                    }
+                }
+                return Err(MempoolError::NotDrained {
+                    max_pending,
+                    timeout: self.thresholds.drain_timeout,
+                    depths: summary,
+                }
+                .into());
+            }
+            sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Transactions still unseen somewhere are only judged when they were
+    /// sampled for the full `limit` before workloads stopped.
+    fn check_propagation(&self) -> Result<(), DynError> {
+        let (Some(limit), Some(tracker)) = (self.thresholds.max_propagation, &self.tracker) else {
+            return Ok(());
+        };
+        let Some(last_sample) = self.last_propagation_sample else {
+            return Ok(());
+        };
+
+        let submissions = tracker.snapshot();
+        let mut slow = submissions
+            .iter()
+            .filter_map(|(hash, record)| match self.propagated.get(hash) {
+                Some(delay) if *delay <= limit => None,
+                Some(delay) => Some((*hash, record.target.clone(), Some(*delay))),
+                None if record.submitted_at + limit <= last_sample => {
+                    Some((*hash, record.target.clone(), None))
+                }
+                None => None,
+            })
+            .collect::<Vec<_>>();
+        if slow.is_empty() {
+            return Ok(());
+        }
+
+        slow.sort_by_key(|(_, _, delay)| std::cmp::Reverse(delay.unwrap_or(Duration::MAX)));
+        let mut details = String::new();
+        for (hash, target, delay) in slow.iter().take(MAX_LISTED_SLOW) {
+            let _ = match delay {
+                Some(delay) => writeln!(details, "- {hash:?} from {target}: {delay:?}"),
+                None => writeln!(
+                    details,
+                    "- {hash:?} from {target}: never reached every node"
+                ),
+            };
+        }
+        if slow.len() > MAX_LISTED_SLOW {
+            let _ = writeln!(details, "- ... and {} more", slow.len() - MAX_LISTED_SLOW);
+        }
+        Err(MempoolError::SlowPropagation {
+            slow: slow.len(),
+            submitted: submissions.len(),
+            limit,
+            details,
+        }
+        .into())
+    }
+}
+
+#[async_trait]
+impl Expectation for MempoolExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn check_interval(&self) -> Option<Duration> {
+        Some(self.thresholds.sample_interval)
+    }
+
+    fn fail_fast(&self) -> bool {
+        false
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sample_depths(ctx).await;
+        Ok(())
+    }
+
+    async fn check(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sample_depths(ctx).await;
+        self.sample_propagation(ctx).await;
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        tracing::info!(
+            samples = self.samples.snapshot().len(),
+            peak_depth = self.samples.peak_depth(),
+            propagated = self.propagated.len(),
+            "mempool expectation evaluating"
+        );
+        self.check_propagation()?;
+        self.wait_for_drain(ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use lb_core::mantle::{Note, Transaction as _, TxHash, tx_builder::MantleTxBuilder};
+    use testing_framework_config::topology::configs::wallet::WalletAccount;
+
+    use super::{MempoolExpectation, MempoolSample, MempoolSamples, MempoolThresholds};
+    use crate::workloads::transaction::SubmissionTracker;
+
+    fn hash(seed: u64) -> TxHash {
+        let owner = WalletAccount::deterministic(0, 1).public_key();
+        MantleTxBuilder::new()
+            .add_ledger_output(Note::new(seed + 1, owner))
+            .build()
+            .ledger_tx
+            .hash()
+    }
+
+    #[test]
+    fn peak_depth_ignores_unreachable_nodes() {
+        let samples = MempoolSamples::default();
+        assert_eq!(samples.peak_depth(), None);
+        samples.push(MempoolSample {
+            elapsed: Duration::ZERO,
+            depths: vec![("node-0".to_owned(), Some(3)), ("node-1".to_owned(), None)],
+        });
+        samples.push(MempoolSample {
+            elapsed: Duration::from_secs(2),
+            depths: vec![
+                ("node-0".to_owned(), Some(1)),
+                ("node-1".to_owned(), Some(7)),
+            ],
+        });
+
+        assert_eq!(samples.peak_depth(), Some(7));
+    }
+
+    #[test]
+    fn slow_and_unseen_transactions_fail_propagation() {
+        let tracker = SubmissionTracker::new();
+        for seed in 0..3 {
+            tracker.record(hash(seed), "node-0");
+        }
+        let limit = Duration::from_secs(5);
+        let mut expectation =
+            MempoolExpectation::new(MempoolThresholds::default().with_max_propagation(limit))
+                .with_submissions(tracker);
+        expectation
+            .propagated
+            .insert(hash(0), Duration::from_secs(1));
+        expectation
+            .propagated
+            .insert(hash(1), Duration::from_secs(10));
+
+        // Not sampled yet: nothing to judge.
+        assert!(expectation.check_propagation().is_ok());
+
+        // The unseen transaction has not been sampled for the full limit.
+        expectation.last_propagation_sample = Some(Instant::now() + Duration::from_secs(1));
+        let err = expectation.check_propagation().expect_err("slow tx");
+        assert!(err.to_string().starts_with("1/3 transactions"), "{err}");
+
+        expectation.last_propagation_sample = Some(Instant::now() + 2 * limit);
+        let err = expectation
+            .check_propagation()
+            .expect_err("slow and unseen txs");
+        assert!(err.to_string().starts_with("2/3 transactions"), "{err}");
+        assert!(
+            err.to_string().contains("never reached every node"),
+            "{err}"
+        );
+    }
+}
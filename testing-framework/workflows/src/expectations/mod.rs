@@ -1,3 +1,15 @@
+mod block_lag;
+mod chain_reconvergence;
+mod chain_safety;
 mod consensus_liveness;
+mod ledger_conservation;
+mod mempool;
+mod resources;
 
+pub use block_lag::MaxBlockLag;
+pub use chain_reconvergence::ChainReconvergence;
+pub use chain_safety::ChainSafety;
 pub use consensus_liveness::ConsensusLiveness;
+pub use ledger_conservation::{BalanceCheck, LedgerConservation};
+pub use mempool::{MempoolExpectation, MempoolSample, MempoolSamples, MempoolThresholds};
+pub use resources::{MaxRss, MemoryGrowth};
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+const MIN_GROWTH_SAMPLES: usize = 5;
+
+#[derive(Debug, Error)]
+enum ResourceExpectationError {
+    #[error("resource usage is not sampled by this deployer")]
+    NotSampled,
+    #[error("{node} reached {rss_mib} MiB RSS (limit {limit_mib} MiB)")]
+    RssExceeded {
+        node: String,
+        rss_mib: u64,
+        limit_mib: u64,
+    },
+    #[error("{node} memory grew {growth:.1} MiB/min (limit {limit:.1} MiB/min)")]
+    MemoryGrowth {
+        node: String,
+        growth: f64,
+        limit: f64,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Continuous invariant: no node's resident memory may exceed `limit_bytes`
+/// at any sample. Requires a deployer that samples resource usage (the local
+/// deployer does).
+pub struct MaxRss {
+    limit_bytes: u64,
+    interval: Duration,
+    fail_fast: bool,
+}
+
+impl MaxRss {
+    #[must_use]
+    pub const fn new(limit_bytes: u64) -> Self {
+        Self {
+            limit_bytes,
+            interval: CHECK_INTERVAL,
+            fail_fast: true,
+        }
+    }
+
+    #[must_use]
+    /// Limit expressed in MiB.
+    pub const fn mib(limit: u64) -> Self {
+        Self::new(limit * BYTES_PER_MIB)
+    }
+
+    #[must_use]
+    /// Sets how often the sampled series are checked while workloads run.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Records violations and fails at evaluation instead of aborting the run.
+    pub const fn without_fail_fast(mut self) -> Self {
+        self.fail_fast = false;
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for MaxRss {
+    fn name(&self) -> &'static str {
+        "max_rss"
+    }
+
+    fn check_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+
+    async fn check(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let usage = ctx
+            .resource_usage()
+            .ok_or(ResourceExpectationError::NotSampled)?;
+
+        for node in usage.nodes() {
+            let Some(peak) = usage.peak_rss(&node) else {
+                continue;
+            };
+            if peak > self.limit_bytes {
+                return Err(ResourceExpectationError::RssExceeded {
+                    node,
+                    rss_mib: peak / BYTES_PER_MIB,
+                    limit_mib: self.limit_bytes / BYTES_PER_MIB,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.check(ctx).await
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Leak check for soak tests: at the end of the run, each node's RSS trend
+/// must stay under `max_mib_per_min`.
+///
+/// The trend is a least-squares fit over the samples of the node's current
+/// process taken after the warmup, so startup allocation and restarts do not
+/// count as growth. Nodes with too few samples are skipped.
+pub struct MemoryGrowth {
+    max_mib_per_min: f64,
+    warmup: Duration,
+}
+
+impl MemoryGrowth {
+    #[must_use]
+    pub const fn new(max_mib_per_min: f64) -> Self {
+        Self {
+            max_mib_per_min,
+            warmup: Duration::ZERO,
+        }
+    }
+
+    #[must_use]
+    /// Ignore samples taken before `warmup` has passed since sampling began.
+    pub const fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for MemoryGrowth {
+    fn name(&self) -> &'static str {
+        "memory_growth"
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let usage = ctx
+            .resource_usage()
+            .ok_or(ResourceExpectationError::NotSampled)?;
+
+        for node in usage.nodes() {
+            let samples = usage
+                .series(&node)
+                .iter()
+                .filter(|sample| sample.elapsed >= self.warmup)
+                .count();
+            if samples < MIN_GROWTH_SAMPLES {
+                tracing::debug!(node = %node, samples, "memory growth: too few samples");
+                continue;
+            }
+            let Some(growth) = usage.memory_growth_mib_per_min(&node, self.warmup) else {
+                continue;
+            };
+            if growth > self.max_mib_per_min {
+                return Err(ResourceExpectationError::MemoryGrowth {
+                    node,
+                    growth,
+                    limit: self.max_mib_per_min,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,23 @@
 pub mod builder;
 pub mod expectations;
 pub mod manual;
+pub mod scenario_file;
 pub mod util;
 pub mod workloads;
 
 pub use builder::{ChaosBuilderExt, ObservabilityBuilderExt, ScenarioBuilderExt};
-pub use expectations::ConsensusLiveness;
+pub use expectations::{
+    BalanceCheck, ChainReconvergence, ChainSafety, ConsensusLiveness, LedgerConservation,
+    MaxBlockLag, MaxRss, MemoryGrowth, MempoolExpectation, MempoolThresholds,
+};
 pub use manual::{start_node_with_timeout, wait_for_min_height};
-pub use workloads::transaction::TxInclusionExpectation;
+pub use scenario_file::{ScenarioFile, ScenarioRegistry};
+pub use workloads::{
+    channel::{
+        ChannelLog, ChannelOrderingExpectation, ChannelRecord, ChannelWorkload, InvalidInscription,
+    },
+    transaction::{
+        AdversarialWorkload, AttackKind, LatencyThresholds, TxInclusionExpectation,
+        TxLatencyExpectation, TxRejectionExpectation,
+    },
+};
@@ -0,0 +1,391 @@
+//! Scenario definitions loaded from YAML or TOML files.
+//!
+//! A file describes the topology, wallets, run duration and the workloads,
+//! expectations and chaos to attach. Components are referenced by name and
+//! resolved through a [`ScenarioRegistry`], so the same file format can drive
+//! custom workloads registered by downstream crates.
+
+mod registry;
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub use registry::{ComponentFactory, ScenarioRegistry};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use testing_framework_config::topology::configs::{
+    network::Libp2pNetworkLayout, node_spec::NodeSpecs, stake::StakeDistribution,
+};
+use testing_framework_core::scenario::{Builder as CoreScenarioBuilder, DynError};
+use thiserror::Error;
+
+use crate::builder::ScenarioBuilderExt as _;
+
+#[derive(Debug, Error)]
+pub enum ScenarioFileError {
+    #[error("failed to read scenario file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid scenario definition: {0}")]
+    Parse(#[from] serde_yaml::Error),
+    #[error("invalid TOML scenario definition: {0}")]
+    ParseToml(#[from] toml::de::Error),
+    #[error("unknown {kind} '{name}' (known: {known})")]
+    UnknownComponent {
+        kind: ComponentKind,
+        name: String,
+        known: String,
+    },
+    #[error("{kind} '{name}' has invalid parameters: {source}")]
+    InvalidParams {
+        kind: ComponentKind,
+        name: String,
+        source: DynError,
+    },
+}
+
+/// Section of a scenario file a component is listed under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentKind {
+    Workload,
+    Expectation,
+    Chaos,
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Workload => "workload",
+            Self::Expectation => "expectation",
+            Self::Chaos => "chaos",
+        })
+    }
+}
+
+/// A complete scenario as written in a definition file.
+///
+/// ```yaml
+/// name: restart-smoke
+/// topology:
+///   nodes: 4
+///   layout: ring
+/// wallets: 200
+/// duration_secs: 120
+/// workloads:
+///   - name: transactions
+///     rate: 5
+///     users: 100
+/// expectations:
+///   - name: consensus_liveness
+/// chaos:
+///   - name: restart
+///     min_delay_secs: 20
+///     max_delay_secs: 40
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    /// Free-form label used in logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub topology: TopologySection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallets: Option<WalletsSection>,
+    pub duration_secs: u64,
+    /// Fixes the scenario seed for replayable chaos and workload choices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workloads: Vec<ComponentSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expectations: Vec<ComponentSpec>,
+    /// Chaos workloads; these need a deployer with node control.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chaos: Vec<ComponentSpec>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopologySection {
+    pub nodes: usize,
+    /// Libp2p layout; defaults to a star.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Libp2pNetworkLayout>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake_distribution: Option<StakeDistribution>,
+    #[serde(default, skip_serializing_if = "NodeSpecs::is_empty")]
+    pub node_specs: NodeSpecs,
+}
+
+/// Either a plain wallet count funded with the default amount per wallet, or
+/// an explicit total split across `users`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WalletsSection {
+    Users(usize),
+    Funded { total_funds: u64, users: usize },
+}
+
+/// A named component plus its parameters, which sit next to `name`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComponentSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub params: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl ComponentSpec {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_param(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_yaml::Value>,
+    ) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Decodes the parameters into the component's own parameter type.
+    pub fn params<P: DeserializeOwned>(&self) -> Result<P, serde_yaml::Error> {
+        let mapping = self
+            .params
+            .iter()
+            .map(|(key, value)| (serde_yaml::Value::String(key.clone()), value.clone()))
+            .collect();
+        serde_yaml::from_value(serde_yaml::Value::Mapping(mapping))
+    }
+}
+
+impl ScenarioFile {
+    /// Reads and parses a scenario file. Files ending in `.toml` are parsed
+    /// as TOML and everything else as YAML, which covers JSON too since JSON
+    /// is a subset of YAML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioFileError> {
+        let path = path.as_ref();
+        let raw = fs::read_to_string(path).map_err(|source| ScenarioFileError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml_str(&raw)
+        } else {
+            Self::from_yaml_str(&raw)
+        }
+    }
+
+    pub fn from_toml_str(raw: &str) -> Result<Self, ScenarioFileError> {
+        Ok(toml::from_str(raw)?)
+    }
+
+    pub fn from_yaml_str(raw: &str) -> Result<Self, ScenarioFileError> {
+        Ok(serde_yaml::from_str(raw)?)
+    }
+
+    pub fn to_yaml_string(&self) -> Result<String, ScenarioFileError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    #[must_use]
+    pub const fn requires_node_control(&self) -> bool {
+        !self.chaos.is_empty()
+    }
+
+    #[must_use]
+    pub const fn run_duration(&self) -> Duration {
+        Duration::from_secs(self.duration_secs)
+    }
+
+    /// Turns the definition into a scenario builder, resolving every
+    /// workload, expectation and chaos entry through `registry`.
+    pub fn to_builder<Caps: Default + 'static>(
+        &self,
+        registry: &ScenarioRegistry<Caps>,
+    ) -> Result<CoreScenarioBuilder<Caps>, ScenarioFileError> {
+        let topology = &self.topology;
+        let mut builder = CoreScenarioBuilder::<Caps>::topology_with(|mut t| {
+            t = t.nodes(topology.nodes);
+            t = match &topology.layout {
+                Some(layout) => t.network_layout(layout.clone()),
+                None => t.network_star(),
+            };
+            if let Some(distribution) = &topology.stake_distribution {
+                t = t.stake_distribution(distribution.clone());
+            }
+            for (index, spec) in &topology.node_specs {
+                t = t.node_spec(*index, spec.clone());
+            }
+            t
+        })
+        .with_run_duration(self.run_duration());
+
+        builder = match self.wallets {
+            Some(WalletsSection::Users(users)) => builder.wallets(users),
+            Some(WalletsSection::Funded { total_funds, users }) => {
+                builder.initialize_wallet(total_funds, users)
+            }
+            None => builder,
+        };
+        if let Some(seed) = self.seed {
+            builder = builder.with_seed(seed);
+        }
+
+        let sections = [
+            (ComponentKind::Workload, &self.workloads),
+            (ComponentKind::Expectation, &self.expectations),
+            (ComponentKind::Chaos, &self.chaos),
+        ];
+        for (kind, specs) in sections {
+            for spec in specs {
+                builder = registry.apply(kind, builder, spec)?;
+            }
+        }
+
+        tracing::info!(
+            name = self.name.as_deref().unwrap_or("unnamed"),
+            nodes = topology.nodes,
+            workloads = self.workloads.len(),
+            expectations = self.expectations.len(),
+            chaos = self.chaos.len(),
+            "scenario file resolved"
+        );
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_config::topology::configs::network::Libp2pNetworkLayout;
+    use testing_framework_core::scenario::NodeControlCapability;
+
+    use super::{ComponentKind, ScenarioFile, ScenarioFileError, ScenarioRegistry, WalletsSection};
+
+    const SAMPLE: &str = r"
+name: sample
+topology:
+  nodes: 3
+  layout:
+    random_regular:
+      degree: 2
+      seed: 7
+wallets:
+  total_funds: 3000
+  users: 30
+duration_secs: 60
+workloads:
+  - name: load
+    profile:
+      ramp:
+        from: 1.0
+        to: 5.0
+        over_secs: 30
+expectations:
+  - name: max_block_lag
+    max_lag: 4
+chaos:
+  - name: partition
+    groups: 2
+";
+
+    #[test]
+    fn parses_and_resolves_sample_definition() {
+        let file = ScenarioFile::from_yaml_str(SAMPLE).expect("sample parses");
+        assert_eq!(
+            file.topology.layout,
+            Some(Libp2pNetworkLayout::RandomRegular { degree: 2, seed: 7 })
+        );
+        assert_eq!(
+            file.wallets,
+            Some(WalletsSection::Funded {
+                total_funds: 3000,
+                users: 30
+            })
+        );
+        assert!(file.requires_node_control());
+
+        let registry = ScenarioRegistry::<NodeControlCapability>::builtin_with_chaos();
+        assert!(file.to_builder(&registry).is_ok());
+
+        let reparsed = ScenarioFile::from_yaml_str(&file.to_yaml_string().unwrap()).unwrap();
+        assert_eq!(reparsed, file);
+    }
+
+    #[test]
+    fn toml_definitions_match_yaml() {
+        const TOML_SAMPLE: &str = r#"
+name = "sample"
+duration_secs = 60
+
+[topology]
+nodes = 3
+layout = { random_regular = { degree = 2, seed = 7 } }
+
+[wallets]
+total_funds = 3000
+users = 30
+
+[[workloads]]
+name = "load"
+profile = { ramp = { from = 1.0, to = 5.0, over_secs = 30 } }
+
+[[expectations]]
+name = "max_block_lag"
+max_lag = 4
+
+[[chaos]]
+name = "partition"
+groups = 2
+"#;
+
+        let file = ScenarioFile::from_toml_str(TOML_SAMPLE).expect("toml sample parses");
+        assert_eq!(file, ScenarioFile::from_yaml_str(SAMPLE).unwrap());
+        assert!(matches!(
+            ScenarioFile::from_toml_str("duration_secs = 60"),
+            Err(ScenarioFileError::ParseToml(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_components_and_params() {
+        let mut file = ScenarioFile::from_yaml_str(SAMPLE).expect("sample parses");
+        let err = file
+            .to_builder(&ScenarioRegistry::<()>::builtin())
+            .err()
+            .expect("chaos is not registered without node control");
+        assert!(matches!(
+            err,
+            ScenarioFileError::UnknownComponent {
+                kind: ComponentKind::Chaos,
+                ..
+            }
+        ));
+
+        file.chaos.clear();
+        file.expectations[0]
+            .params
+            .insert("max_lagg".to_owned(), 4.into());
+        let err = file
+            .to_builder(&ScenarioRegistry::<()>::builtin())
+            .err()
+            .expect("misspelled parameter is rejected");
+        assert!(matches!(
+            err,
+            ScenarioFileError::InvalidParams {
+                kind: ComponentKind::Expectation,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,666 @@
+use std::{collections::BTreeMap, num::NonZeroUsize, time::Duration};
+
+use serde::Deserialize;
+use testing_framework_core::scenario::{
+    Builder as CoreScenarioBuilder, DynError, NodeControlCapability,
+};
+
+use super::{ComponentKind, ComponentSpec, ScenarioFileError};
+use crate::{
+    builder::{ChaosBuilderExt as _, ScenarioBuilderExt as _},
+    expectations::{
+        BalanceCheck, ChainReconvergence, ChainSafety, ConsensusLiveness, LedgerConservation,
+        MaxBlockLag, MaxRss, MemoryGrowth, MempoolExpectation, MempoolThresholds,
+    },
+    workloads::transaction::{AttackKind, LatencyThresholds, RateProfile},
+};
+
+/// Attaches one named component to a scenario builder.
+pub type ComponentFactory<Caps> = Box<
+    dyn Fn(CoreScenarioBuilder<Caps>, &ComponentSpec) -> Result<CoreScenarioBuilder<Caps>, DynError>
+        + Send
+        + Sync,
+>;
+
+/// Maps the names used in scenario files to workloads, expectations and chaos.
+///
+/// [`ScenarioRegistry::builtin`] covers every capability-independent
+/// component; chaos needs node control and is only available from
+/// [`ScenarioRegistry::builtin_with_chaos`]. Custom components can be added
+/// with the `register_*` methods.
+pub struct ScenarioRegistry<Caps> {
+    workloads: BTreeMap<String, ComponentFactory<Caps>>,
+    expectations: BTreeMap<String, ComponentFactory<Caps>>,
+    chaos: BTreeMap<String, ComponentFactory<Caps>>,
+}
+
+impl<Caps> Default for ScenarioRegistry<Caps> {
+    fn default() -> Self {
+        Self {
+            workloads: BTreeMap::new(),
+            expectations: BTreeMap::new(),
+            chaos: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Caps: 'static> ScenarioRegistry<Caps> {
+    /// Registry with the built-in transaction workloads and expectations:
+    ///
+    /// | section | name |
+    /// |---------|------|
+    /// | workloads | `transactions`, `load`, `adversarial`, `channels` |
+    /// | expectations | `consensus_liveness`, `chain_safety`, `max_block_lag`, `chain_reconvergence`, `ledger_conservation`, `mempool`, `max_rss`, `memory_growth` |
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::default()
+            .register_workload("transactions", apply_transactions)
+            .register_workload("load", apply_load)
+            .register_workload("adversarial", apply_adversarial)
+            .register_workload("channels", apply_channels)
+            .register_expectation("consensus_liveness", apply_consensus_liveness)
+            .register_expectation("chain_safety", apply_chain_safety)
+            .register_expectation("max_block_lag", apply_max_block_lag)
+            .register_expectation("chain_reconvergence", apply_chain_reconvergence)
+            .register_expectation("ledger_conservation", apply_ledger_conservation)
+            .register_expectation("mempool", apply_mempool)
+            .register_expectation("max_rss", apply_max_rss)
+            .register_expectation("memory_growth", apply_memory_growth)
+    }
+
+    #[must_use]
+    pub fn register_workload<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(
+                CoreScenarioBuilder<Caps>,
+                &ComponentSpec,
+            ) -> Result<CoreScenarioBuilder<Caps>, DynError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.workloads.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    #[must_use]
+    pub fn register_expectation<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(
+                CoreScenarioBuilder<Caps>,
+                &ComponentSpec,
+            ) -> Result<CoreScenarioBuilder<Caps>, DynError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.expectations.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    #[must_use]
+    pub fn register_chaos<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(
+                CoreScenarioBuilder<Caps>,
+                &ComponentSpec,
+            ) -> Result<CoreScenarioBuilder<Caps>, DynError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.chaos.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    /// Names registered for `kind`, in sorted order.
+    pub fn names(&self, kind: ComponentKind) -> impl Iterator<Item = &str> {
+        self.section(kind).keys().map(String::as_str)
+    }
+
+    pub(super) fn apply(
+        &self,
+        kind: ComponentKind,
+        builder: CoreScenarioBuilder<Caps>,
+        spec: &ComponentSpec,
+    ) -> Result<CoreScenarioBuilder<Caps>, ScenarioFileError> {
+        let Some(factory) = self.section(kind).get(&spec.name) else {
+            return Err(ScenarioFileError::UnknownComponent {
+                kind,
+                name: spec.name.clone(),
+                known: self.names(kind).collect::<Vec<_>>().join(", "),
+            });
+        };
+
+        factory(builder, spec).map_err(|source| ScenarioFileError::InvalidParams {
+            kind,
+            name: spec.name.clone(),
+            source,
+        })
+    }
+
+    fn section(&self, kind: ComponentKind) -> &BTreeMap<String, ComponentFactory<Caps>> {
+        match kind {
+            ComponentKind::Workload => &self.workloads,
+            ComponentKind::Expectation => &self.expectations,
+            ComponentKind::Chaos => &self.chaos,
+        }
+    }
+}
+
+impl ScenarioRegistry<NodeControlCapability> {
+    /// [`ScenarioRegistry::builtin`] plus the `restart` and `partition` chaos
+    /// workloads.
+    #[must_use]
+    pub fn builtin_with_chaos() -> Self {
+        Self::builtin()
+            .register_chaos("restart", apply_chaos_restart)
+            .register_chaos("partition", apply_chaos_partition)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NoParams {}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LatencyParams {
+    min_inclusion_ratio: Option<f64>,
+    max_p50_ms: Option<u64>,
+    max_p95_ms: Option<u64>,
+    max_ms: Option<u64>,
+}
+
+impl LatencyParams {
+    fn thresholds(&self) -> LatencyThresholds {
+        let mut thresholds = LatencyThresholds::default();
+        if let Some(ratio) = self.min_inclusion_ratio {
+            thresholds = thresholds.with_min_inclusion_ratio(ratio);
+        }
+        if let Some(limit) = self.max_p50_ms {
+            thresholds = thresholds.with_max_p50(Duration::from_millis(limit));
+        }
+        if let Some(limit) = self.max_p95_ms {
+            thresholds = thresholds.with_max_p95(Duration::from_millis(limit));
+        }
+        if let Some(limit) = self.max_ms {
+            thresholds = thresholds.with_max_latency(Duration::from_millis(limit));
+        }
+        thresholds
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MempoolParams {
+    max_pending: Option<usize>,
+    drain_timeout_secs: Option<u64>,
+    max_propagation_ms: Option<u64>,
+    sample_interval_secs: Option<u64>,
+}
+
+impl MempoolParams {
+    fn thresholds(&self) -> MempoolThresholds {
+        let mut thresholds = MempoolThresholds::default();
+        if let Some(max_pending) = self.max_pending {
+            thresholds = thresholds.with_max_pending(max_pending);
+        }
+        if let Some(secs) = self.drain_timeout_secs {
+            thresholds = thresholds.with_drain_timeout(Duration::from_secs(secs));
+        }
+        if let Some(limit) = self.max_propagation_ms {
+            thresholds = thresholds.with_max_propagation(Duration::from_millis(limit));
+        }
+        if let Some(secs) = self.sample_interval_secs {
+            thresholds = thresholds.with_sample_interval(Duration::from_secs(secs));
+        }
+        thresholds
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChainedParams {
+    fan_in: usize,
+    fan_out: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TransactionsParams {
+    rate: Option<u64>,
+    users: Option<usize>,
+    chained: Option<ChainedParams>,
+    latency: Option<LatencyParams>,
+    mempool: Option<MempoolParams>,
+}
+
+fn apply_transactions<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: TransactionsParams = spec.params()?;
+    Ok(builder.transactions_with(|mut txs| {
+        if let Some(rate) = params.rate {
+            txs = txs.rate(rate);
+        }
+        if let Some(users) = params.users {
+            txs = txs.users(users);
+        }
+        if let Some(chained) = &params.chained {
+            txs = txs.chained_spends(chained.fan_in, chained.fan_out);
+        }
+        if let Some(latency) = &params.latency {
+            txs = txs.inclusion_latency(latency.thresholds());
+        }
+        if let Some(mempool) = &params.mempool {
+            txs = txs.mempool(mempool.thresholds());
+        }
+        txs
+    }))
+}
+
+/// File form of [`RateProfile`], with durations in whole seconds.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum RateProfileParams {
+    Constant {
+        tps: f64,
+    },
+    Ramp {
+        from: f64,
+        to: f64,
+        over_secs: u64,
+    },
+    Step {
+        start: f64,
+        step: f64,
+        every_secs: u64,
+    },
+    Spike {
+        base: f64,
+        peak: f64,
+        at_secs: u64,
+        duration_secs: u64,
+    },
+}
+
+impl From<&RateProfileParams> for RateProfile {
+    fn from(params: &RateProfileParams) -> Self {
+        match *params {
+            RateProfileParams::Constant { tps } => Self::Constant { tps },
+            RateProfileParams::Ramp {
+                from,
+                to,
+                over_secs,
+            } => Self::Ramp {
+                from,
+                to,
+                over: Duration::from_secs(over_secs),
+            },
+            RateProfileParams::Step {
+                start,
+                step,
+                every_secs,
+            } => Self::Step {
+                start,
+                step,
+                every: Duration::from_secs(every_secs),
+            },
+            RateProfileParams::Spike {
+                base,
+                peak,
+                at_secs,
+                duration_secs,
+            } => Self::Spike {
+                base,
+                peak,
+                at: Duration::from_secs(at_secs),
+                duration: Duration::from_secs(duration_secs),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LoadParams {
+    tps: Option<f64>,
+    profile: Option<RateProfileParams>,
+    users: Option<usize>,
+    fan_out: Option<usize>,
+    max_in_flight: Option<usize>,
+    min_achieved_ratio: Option<f64>,
+    latency: Option<LatencyParams>,
+}
+
+fn apply_load<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: LoadParams = spec.params()?;
+    if params.tps.is_some() && params.profile.is_some() {
+        return Err("set either `tps` or `profile`, not both".into());
+    }
+
+    Ok(builder.load_with(|mut load| {
+        if let Some(tps) = params.tps {
+            load = load.tps(tps);
+        }
+        if let Some(profile) = &params.profile {
+            load = load.profile(profile.into());
+        }
+        if let Some(users) = params.users {
+            load = load.users(users);
+        }
+        if let Some(fan_out) = params.fan_out {
+            load = load.fan_out(fan_out);
+        }
+        if let Some(max_in_flight) = params.max_in_flight {
+            load = load.max_in_flight(max_in_flight);
+        }
+        if let Some(ratio) = params.min_achieved_ratio {
+            load = load.min_achieved_ratio(ratio);
+        }
+        if let Some(latency) = &params.latency {
+            load = load.inclusion_latency(latency.thresholds());
+        }
+        load
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AdversarialParams {
+    kinds: Vec<AttackKind>,
+    interval_secs: Option<u64>,
+    accounts: Option<usize>,
+    inscription_bytes: Option<usize>,
+    min_valid_inclusions: Option<u64>,
+}
+
+fn apply_adversarial<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: AdversarialParams = spec.params()?;
+    let accounts = params
+        .accounts
+        .map(|accounts| NonZeroUsize::new(accounts).ok_or("accounts must be non-zero"))
+        .transpose()?;
+    Ok(builder.adversarial_transactions_with(|mut workload| {
+        workload = workload.with_kinds(params.kinds);
+        if let Some(secs) = params.interval_secs {
+            workload = workload.with_interval(Duration::from_secs(secs));
+        }
+        if let Some(accounts) = accounts {
+            workload = workload.with_reserved_accounts(accounts);
+        }
+        if let Some(bytes) = params.inscription_bytes {
+            workload = workload.with_inscription_bytes(bytes);
+        }
+        if let Some(count) = params.min_valid_inclusions {
+            workload = workload.with_min_valid_inclusions(count);
+        }
+        workload
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChannelParams {
+    channels: Option<usize>,
+    interval_secs: Option<u64>,
+    payload_bytes: Option<usize>,
+    invalid_every: Option<usize>,
+    min_messages: Option<usize>,
+}
+
+fn apply_channels<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: ChannelParams = spec.params()?;
+    let channels = params
+        .channels
+        .map(|channels| NonZeroUsize::new(channels).ok_or("channels must be non-zero"))
+        .transpose()?;
+    let invalid_every = params
+        .invalid_every
+        .map(|every| NonZeroUsize::new(every).ok_or("invalid_every must be non-zero"))
+        .transpose()?;
+    Ok(builder.channels_with(|mut workload| {
+        if let Some(channels) = channels {
+            workload = workload.with_channels(channels);
+        }
+        if let Some(secs) = params.interval_secs {
+            workload = workload.with_interval(Duration::from_secs(secs));
+        }
+        if let Some(bytes) = params.payload_bytes {
+            workload = workload.with_payload_bytes(bytes);
+        }
+        if let Some(every) = invalid_every {
+            workload = workload.with_invalid_every(every);
+        }
+        if let Some(count) = params.min_messages {
+            workload = workload.with_min_messages(count);
+        }
+        workload
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConsensusLivenessParams {
+    lag_allowance: Option<u64>,
+}
+
+fn apply_consensus_liveness<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: ConsensusLivenessParams = spec.params()?;
+    let mut expectation = ConsensusLiveness::default();
+    if let Some(lag_allowance) = params.lag_allowance {
+        expectation = expectation.with_lag_allowance(lag_allowance);
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+fn apply_chain_safety<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let NoParams {} = spec.params()?;
+    Ok(builder.with_expectation(ChainSafety::new()))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaxBlockLagParams {
+    max_lag: u64,
+    #[serde(default)]
+    interval_secs: Option<u64>,
+    #[serde(default = "default_fail_fast")]
+    fail_fast: bool,
+}
+
+const fn default_fail_fast() -> bool {
+    true
+}
+
+fn apply_max_block_lag<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: MaxBlockLagParams = spec.params()?;
+    let mut expectation = MaxBlockLag::new(params.max_lag);
+    if let Some(secs) = params.interval_secs {
+        expectation = expectation.with_interval(Duration::from_secs(secs));
+    }
+    if !params.fail_fast {
+        expectation = expectation.without_fail_fast();
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChainReconvergenceParams {
+    attempts: Option<usize>,
+    interval_secs: Option<u64>,
+}
+
+fn apply_chain_reconvergence<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: ChainReconvergenceParams = spec.params()?;
+    let mut expectation = ChainReconvergence::default();
+    if let Some(attempts) = params.attempts {
+        expectation = expectation.with_attempts(attempts);
+    }
+    if let Some(secs) = params.interval_secs {
+        expectation = expectation.with_interval(Duration::from_secs(secs));
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LedgerConservationParams {
+    per_account: bool,
+    reward_allowance: Option<u64>,
+}
+
+fn apply_ledger_conservation<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: LedgerConservationParams = spec.params()?;
+    let mut expectation = LedgerConservation::new();
+    if params.per_account {
+        expectation = expectation.with_balance_check(BalanceCheck::PerAccount);
+    }
+    if let Some(allowance) = params.reward_allowance {
+        expectation = expectation.with_reward_allowance(allowance);
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+fn apply_mempool<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: MempoolParams = spec.params()?;
+    Ok(builder.with_expectation(MempoolExpectation::new(params.thresholds())))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaxRssParams {
+    max_mib: u64,
+    #[serde(default)]
+    interval_secs: Option<u64>,
+    #[serde(default = "default_fail_fast")]
+    fail_fast: bool,
+}
+
+fn apply_max_rss<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: MaxRssParams = spec.params()?;
+    let mut expectation = MaxRss::mib(params.max_mib);
+    if let Some(secs) = params.interval_secs {
+        expectation = expectation.with_interval(Duration::from_secs(secs));
+    }
+    if !params.fail_fast {
+        expectation = expectation.without_fail_fast();
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemoryGrowthParams {
+    max_mib_per_min: f64,
+    #[serde(default)]
+    warmup_secs: u64,
+}
+
+fn apply_memory_growth<Caps>(
+    builder: CoreScenarioBuilder<Caps>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<Caps>, DynError> {
+    let params: MemoryGrowthParams = spec.params()?;
+    Ok(builder.with_expectation(
+        MemoryGrowth::new(params.max_mib_per_min)
+            .with_warmup(Duration::from_secs(params.warmup_secs)),
+    ))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChaosRestartParams {
+    min_delay_secs: Option<u64>,
+    max_delay_secs: Option<u64>,
+    target_cooldown_secs: Option<u64>,
+}
+
+fn apply_chaos_restart(
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<NodeControlCapability>, DynError> {
+    let params: ChaosRestartParams = spec.params()?;
+    Ok(builder.chaos_with(|chaos| {
+        let mut restart = chaos.restart();
+        if let Some(secs) = params.min_delay_secs {
+            restart = restart.min_delay(Duration::from_secs(secs));
+        }
+        if let Some(secs) = params.max_delay_secs {
+            restart = restart.max_delay(Duration::from_secs(secs));
+        }
+        if let Some(secs) = params.target_cooldown_secs {
+            restart = restart.target_cooldown(Duration::from_secs(secs));
+        }
+        restart.apply()
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChaosPartitionParams {
+    groups: Option<usize>,
+    initial_delay_secs: Option<u64>,
+    partition_duration_secs: Option<u64>,
+    heal_duration_secs: Option<u64>,
+    cycles: Option<usize>,
+}
+
+fn apply_chaos_partition(
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    spec: &ComponentSpec,
+) -> Result<CoreScenarioBuilder<NodeControlCapability>, DynError> {
+    let params: ChaosPartitionParams = spec.params()?;
+    Ok(builder.chaos_with(|chaos| {
+        let mut partition = chaos.partition();
+        if let Some(groups) = params.groups {
+            partition = partition.groups(groups);
+        }
+        if let Some(secs) = params.initial_delay_secs {
+            partition = partition.initial_delay(Duration::from_secs(secs));
+        }
+        if let Some(secs) = params.partition_duration_secs {
+            partition = partition.partition_duration(Duration::from_secs(secs));
+        }
+        if let Some(secs) = params.heal_duration_secs {
+            partition = partition.heal_duration(Duration::from_secs(secs));
+        }
+        if let Some(cycles) = params.cycles {
+            partition = partition.cycles(cycles);
+        }
+        partition.apply()
+    }))
+}
@@ -9,15 +9,34 @@ use testing_framework_core::scenario::DynError;
 /// Builds a signed inscription transaction with deterministic payload for
 /// testing.
 pub fn create_inscription_transaction_with_id(id: ChannelId) -> Result<SignedMantleTx, DynError> {
+    create_inscription_transaction(id, format!("Test channel inscription {id:?}").into_bytes())
+}
+
+/// Builds a signed root inscription carrying `payload` on channel `id`.
+pub fn create_inscription_transaction(
+    id: ChannelId,
+    payload: Vec<u8>,
+) -> Result<SignedMantleTx, DynError> {
     let signing_key = Ed25519Key::from_bytes(&[0u8; 32]);
-    let signer = signing_key.public_key();
+    create_channel_inscription(id, MsgId::root(), payload, &signing_key).map(|(tx, _)| tx)
+}
 
+/// Builds a transaction appending `payload` to channel `id` after `parent`,
+/// declared and signed by `signing_key`. Returns the transaction and the id
+/// of the new message.
+pub fn create_channel_inscription(
+    id: ChannelId,
+    parent: MsgId,
+    payload: Vec<u8>,
+    signing_key: &Ed25519Key,
+) -> Result<(SignedMantleTx, MsgId), DynError> {
     let inscription_op = InscriptionOp {
         channel_id: id,
-        inscription: format!("Test channel inscription {id:?}").into_bytes(),
-        parent: MsgId::root(),
-        signer,
+        inscription: payload,
+        parent,
+        signer: signing_key.public_key(),
     };
+    let msg_id = inscription_op.id();
 
     let mantle_tx = MantleTx {
         ops: vec![Op::ChannelInscribe(inscription_op)],
@@ -34,10 +53,11 @@ pub fn create_inscription_transaction_with_id(id: ChannelId) -> Result<SignedMan
     let zk_signature = ZkKey::multi_sign(&[zk_key], tx_hash.as_ref())
         .map_err(|err| format!("zk signature generation failed: {err}"))?;
 
-    SignedMantleTx::new(
+    let signed_tx = SignedMantleTx::new(
         mantle_tx,
         vec![OpProof::Ed25519Sig(signature)],
         zk_signature,
     )
-    .map_err(|err| format!("failed to build signed mantle transaction: {err}").into())
+    .map_err(|err| format!("failed to build signed mantle transaction: {err}"))?;
+    Ok((signed_tx, msg_id))
 }
@@ -0,0 +1,713 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::{
+    AuthenticatedMantleTx as _, SignedMantleTx,
+    ops::{
+        Op,
+        channel::{ChannelId, Ed25519PublicKey, MsgId},
+    },
+};
+use lb_key_management_system_service::keys::Ed25519Key;
+use rand::{Rng as _, rngs::StdRng};
+use testing_framework_core::scenario::{
+    BlockFeedEvent, BlockRecord, DynError, Expectation, RunContext, Workload,
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{MissedTickBehavior, interval},
+};
+
+use crate::{
+    util::tx::create_channel_inscription, workloads::util::submit_transaction_to_any_node,
+};
+
+const DEFAULT_CHANNELS: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_MIN_MESSAGES: usize = 2;
+/// In-flight messages not seen in a block within this window are resubmitted.
+const PENDING_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_LISTED_VIOLATIONS: usize = 10;
+
+/// Why an inscription sent by [`ChannelWorkload`] must not be accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidInscription {
+    /// Points at the channel root although the channel already has messages.
+    WrongParent,
+    /// Extends the channel tip but is signed by a key the channel never saw.
+    WrongSigner,
+}
+
+/// Everything a [`ChannelWorkload`] wrote to one channel.
+#[derive(Clone, Debug)]
+pub struct ChannelRecord {
+    pub id: ChannelId,
+    pub signer: Ed25519PublicKey,
+    /// Messages accepted by a node, in append order. Each one names the
+    /// previous one as its parent; the first one names the root.
+    pub messages: Vec<MsgId>,
+    /// Inscriptions submitted to probe validation, with whether a node
+    /// accepted the submission.
+    pub invalid: Vec<(InvalidInscription, MsgId, bool)>,
+}
+
+/// Shared log of the channels written by a [`ChannelWorkload`]; clones share
+/// the same log.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelLog {
+    inner: Arc<Mutex<Vec<ChannelRecord>>>,
+}
+
+impl ChannelLog {
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<ChannelRecord> {
+        self.lock().clone()
+    }
+
+    fn update(&self, channel: usize, f: impl FnOnce(&mut ChannelRecord)) {
+        if let Some(record) = self.lock().get_mut(channel) {
+            f(record);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<ChannelRecord>> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Opens `channels` channels, each with its own Ed25519 signer, and keeps
+/// appending parent-linked inscriptions to them for the whole run.
+///
+/// A channel has at most one message in flight: the next one is only built
+/// once the previous one was seen in a canonical block, so parents always
+/// point at the channel tip. Messages whose block is orphaned by a reorg go
+/// back in flight ahead of any newer one. Optionally every `invalid_every`-th
+/// append is followed by an inscription with a wrong parent and one with a
+/// wrong signer.
+#[derive(Clone)]
+pub struct ChannelWorkload {
+    channels: NonZeroUsize,
+    interval: Duration,
+    payload_bytes: usize,
+    invalid_every: Option<NonZeroUsize>,
+    min_messages: usize,
+    log: ChannelLog,
+}
+
+impl ChannelWorkload {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            channels: DEFAULT_CHANNELS,
+            interval: DEFAULT_INTERVAL,
+            payload_bytes: 0,
+            invalid_every: None,
+            min_messages: DEFAULT_MIN_MESSAGES,
+            log: ChannelLog::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_channels(mut self, channels: NonZeroUsize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    #[must_use]
+    /// How often each idle channel gets its next message.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Pads every message to at least `bytes` bytes.
+    pub const fn with_payload_bytes(mut self, bytes: usize) -> Self {
+        self.payload_bytes = bytes;
+        self
+    }
+
+    #[must_use]
+    /// Probes validation with a wrong-parent and a wrong-signer inscription
+    /// after every `every`-th message of a channel.
+    pub const fn with_invalid_every(mut self, every: NonZeroUsize) -> Self {
+        self.invalid_every = Some(every);
+        self
+    }
+
+    #[must_use]
+    /// Messages every channel must have on chain at the end of the run.
+    pub const fn with_min_messages(mut self, min_messages: usize) -> Self {
+        self.min_messages = min_messages;
+        self
+    }
+
+    #[must_use]
+    pub const fn log(&self) -> &ChannelLog {
+        &self.log
+    }
+}
+
+impl Default for ChannelWorkload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Workload for ChannelWorkload {
+    fn name(&self) -> &'static str {
+        "channel_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(ChannelOrderingExpectation::new(
+            self.log.clone(),
+            self.min_messages,
+        ))]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        ChannelWriter::new(self, ctx).run().await
+    }
+}
+
+struct ChannelState {
+    id: ChannelId,
+    signing_key: Ed25519Key,
+    /// Last message on the canonical chain.
+    tip: MsgId,
+    appended: usize,
+    /// Messages submitted but not on the canonical chain yet, oldest first.
+    /// Only a reorg leaves more than one.
+    in_flight: Vec<InFlight>,
+}
+
+struct InFlight {
+    msg_id: MsgId,
+    tx: Arc<SignedMantleTx>,
+    submitted_at: Instant,
+}
+
+struct ChannelWriter<'a> {
+    config: &'a ChannelWorkload,
+    ctx: &'a RunContext,
+    rng: StdRng,
+    channels: Vec<ChannelState>,
+}
+
+impl<'a> ChannelWriter<'a> {
+    fn new(config: &'a ChannelWorkload, ctx: &'a RunContext) -> Self {
+        let mut rng = ctx.seeded_rng(config.name());
+        let channels = (0..config.channels.get())
+            .map(|_| ChannelState {
+                id: ChannelId::from(rng.r#gen::<[u8; 32]>()),
+                signing_key: Ed25519Key::from_bytes(&rng.r#gen::<[u8; 32]>()),
+                tip: MsgId::root(),
+                appended: 0,
+                in_flight: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        *config.log.lock() = channels
+            .iter()
+            .map(|channel| ChannelRecord {
+                id: channel.id,
+                signer: channel.signing_key.public_key(),
+                messages: Vec::new(),
+                invalid: Vec::new(),
+            })
+            .collect();
+
+        Self {
+            config,
+            ctx,
+            rng,
+            channels,
+        }
+    }
+
+    async fn run(mut self) -> Result<(), DynError> {
+        let mut events = self.ctx.block_feed().subscribe_events();
+        let mut ticker = interval(self.config.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let deadline = Instant::now() + self.ctx.run_duration();
+
+        tracing::info!(
+            channels = self.channels.len(),
+            interval = ?self.config.interval,
+            "channel workload started"
+        );
+
+        while Instant::now() < deadline {
+            tokio::select! {
+                _ = ticker.tick() => self.append_round().await,
+                event = events.recv() => match event {
+                    Ok(BlockFeedEvent::Block(record)) => self.observe_block(&record),
+                    Ok(BlockFeedEvent::Reorg(reorg)) => {
+                        for record in reorg.dropped.iter().rev() {
+                            self.revert_block(record);
+                        }
+                        for record in &reorg.adopted {
+                            self.observe_block(record);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "channel workload block feed lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                () = tokio::time::sleep_until(deadline.into()) => break,
+            }
+        }
+
+        let appended = self.channels.iter().map(|c| c.appended).sum::<usize>();
+        tracing::info!(appended, "channel workload finished");
+        Ok(())
+    }
+
+    fn observe_block(&mut self, record: &BlockRecord) {
+        let included = inscriptions(record)
+            .into_iter()
+            .map(|(_, msg_id, _, _)| msg_id)
+            .collect::<HashSet<_>>();
+        for channel in &mut self.channels {
+            while let Some(oldest) = channel.in_flight.first()
+                && included.contains(&oldest.msg_id)
+            {
+                channel.tip = oldest.msg_id;
+                channel.in_flight.remove(0);
+            }
+        }
+    }
+
+    /// Puts the channel tips inscribed by an orphaned block back in flight,
+    /// newest first, so they are resubmitted before anything built on them.
+    fn revert_block(&mut self, record: &BlockRecord) {
+        let txs = record.block.transactions().into_iter().collect::<Vec<_>>();
+        for tx in txs.into_iter().rev() {
+            for op in tx.mantle_tx().ops.iter().rev() {
+                let Op::ChannelInscribe(inscription) = op else {
+                    continue;
+                };
+                let msg_id = inscription.id();
+                let Some(channel) = self
+                    .channels
+                    .iter_mut()
+                    .find(|channel| channel.id == inscription.channel_id && channel.tip == msg_id)
+                else {
+                    continue;
+                };
+                tracing::debug!(msg = ?msg_id, "channel message orphaned by a reorg");
+                channel.tip = inscription.parent;
+                channel.in_flight.insert(
+                    0,
+                    InFlight {
+                        msg_id,
+                        tx: Arc::new(SignedMantleTx::clone(tx)),
+                        submitted_at: Instant::now(),
+                    },
+                );
+            }
+        }
+    }
+
+    async fn append_round(&mut self) {
+        for index in 0..self.channels.len() {
+            if let Err(err) = self.append(index).await {
+                tracing::debug!(channel = index, %err, "channel append failed");
+            }
+        }
+    }
+
+    async fn append(&mut self, index: usize) -> Result<(), DynError> {
+        let payload = self.payload(index, self.channels[index].appended);
+        let channel = &mut self.channels[index];
+        if !channel.in_flight.is_empty() {
+            for in_flight in &mut channel.in_flight {
+                if in_flight.submitted_at.elapsed() < PENDING_TIMEOUT {
+                    continue;
+                }
+                tracing::debug!(channel = index, msg = ?in_flight.msg_id, "resubmitting stale message");
+                submit_transaction_to_any_node(self.ctx, Arc::clone(&in_flight.tx)).await?;
+                in_flight.submitted_at = Instant::now();
+            }
+            return Ok(());
+        }
+
+        let (tx, msg_id) =
+            create_channel_inscription(channel.id, channel.tip, payload, &channel.signing_key)?;
+        let tx = Arc::new(tx);
+        submit_transaction_to_any_node(self.ctx, Arc::clone(&tx)).await?;
+
+        channel.appended += 1;
+        channel.in_flight.push(InFlight {
+            msg_id,
+            tx,
+            submitted_at: Instant::now(),
+        });
+        self.config
+            .log
+            .update(index, |record| record.messages.push(msg_id));
+
+        let probe = self
+            .config
+            .invalid_every
+            .is_some_and(|every| channel.appended % every.get() == 0);
+        if probe {
+            self.probe_validation(index, msg_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Submits a wrong-parent and a wrong-signer inscription on top of
+    /// `tip`, the message just appended to the channel.
+    async fn probe_validation(&mut self, index: usize, tip: MsgId) -> Result<(), DynError> {
+        let stranger = Ed25519Key::from_bytes(&self.rng.r#gen::<[u8; 32]>());
+        let channel = &self.channels[index];
+        let probes = [
+            (
+                InvalidInscription::WrongParent,
+                create_channel_inscription(
+                    channel.id,
+                    MsgId::root(),
+                    b"wrong parent".to_vec(),
+                    &channel.signing_key,
+                )?,
+            ),
+            (
+                InvalidInscription::WrongSigner,
+                create_channel_inscription(channel.id, tip, b"wrong signer".to_vec(), &stranger)?,
+            ),
+        ];
+
+        for (kind, (tx, msg_id)) in probes {
+            let accepted = submit_transaction_to_any_node(self.ctx, Arc::new(tx))
+                .await
+                .is_ok();
+            tracing::debug!(
+                channel = index,
+                ?kind,
+                accepted,
+                "submitted invalid inscription"
+            );
+            self.config.log.update(index, |record| {
+                record.invalid.push((kind, msg_id, accepted))
+            });
+        }
+        Ok(())
+    }
+
+    fn payload(&self, index: usize, sequence: usize) -> Vec<u8> {
+        let mut payload = format!("channel {index} message {sequence}").into_bytes();
+        if payload.len() < self.config.payload_bytes {
+            payload.resize(self.config.payload_bytes, b'.');
+        }
+        payload
+    }
+}
+
+/// Channel inscriptions of a block in order: channel, message id, parent and
+/// signer.
+fn inscriptions(record: &BlockRecord) -> Vec<(ChannelId, MsgId, MsgId, Ed25519PublicKey)> {
+    let mut found = Vec::new();
+    for tx in record.block.transactions() {
+        for op in &tx.mantle_tx().ops {
+            if let Op::ChannelInscribe(inscription) = op {
+                found.push((
+                    inscription.channel_id,
+                    inscription.id(),
+                    inscription.parent,
+                    inscription.signer,
+                ));
+            }
+        }
+    }
+    found
+}
+
+#[derive(Debug, Error)]
+enum ChannelOrderingError {
+    #[error("channel workload opened no channels")]
+    NoChannels,
+    #[error("{missing} of {total} canonical blocks are not held by the block feed")]
+    MissingBlocks { missing: usize, total: usize },
+    #[error("{count} channel ordering violation(s):\n{details}")]
+    Violations { count: usize, details: String },
+}
+
+/// Replays the canonical chain observed by the block feed and checks every
+/// channel written by a [`ChannelWorkload`]: its on-chain messages must be
+/// exactly a prefix of what the workload appended, in order and without
+/// gaps, all signed by the channel's signer; no wrong-parent or wrong-signer
+/// probe may be accepted by a node or included; and each channel needs at
+/// least `min_messages` messages on chain. Fails if any canonical block fell
+/// out of the feed's retention window.
+#[derive(Clone)]
+pub struct ChannelOrderingExpectation {
+    log: ChannelLog,
+    min_messages: usize,
+}
+
+impl ChannelOrderingExpectation {
+    pub const NAME: &'static str = "channel_ordering";
+
+    #[must_use]
+    pub const fn new(log: ChannelLog, min_messages: usize) -> Self {
+        Self { log, min_messages }
+    }
+}
+
+#[async_trait]
+impl Expectation for ChannelOrderingExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let channels = self.log.snapshot();
+        if channels.is_empty() {
+            return Err(ChannelOrderingError::NoChannels.into());
+        }
+
+        let feed = ctx.block_feed();
+        let chain = feed.canonical_chain();
+        let mut on_chain: HashMap<ChannelId, Vec<(MsgId, MsgId, Ed25519PublicKey)>> =
+            HashMap::new();
+        // Blocks the feed no longer tracks cannot be checked either.
+        let pruned = feed.pruned_canonical_blocks();
+        let mut missing_blocks = pruned;
+        for header in &chain {
+            let Some(record) = feed.known_record(header) else {
+                missing_blocks += 1;
+                continue;
+            };
+            for (channel, msg_id, parent, signer) in inscriptions(&record) {
+                on_chain
+                    .entry(channel)
+                    .or_default()
+                    .push((msg_id, parent, signer));
+            }
+        }
+
+        if missing_blocks > 0 {
+            return Err(ChannelOrderingError::MissingBlocks {
+                missing: missing_blocks,
+                total: pruned + chain.len(),
+            }
+            .into());
+        }
+
+        let mut violations = Vec::new();
+        for (index, channel) in channels.iter().enumerate() {
+            let observed = on_chain.remove(&channel.id).unwrap_or_default();
+            check_channel(
+                index,
+                channel,
+                &observed,
+                self.min_messages,
+                &mut violations,
+            );
+        }
+
+        if violations.is_empty() {
+            tracing::info!(channels = channels.len(), "channel ordering holds");
+            return Ok(());
+        }
+        let count = violations.len();
+        let mut details = String::new();
+        for violation in violations.iter().take(MAX_LISTED_VIOLATIONS) {
+            let _ = writeln!(details, "- {violation}");
+        }
+        if count > MAX_LISTED_VIOLATIONS {
+            let _ = writeln!(details, "- ... and {} more", count - MAX_LISTED_VIOLATIONS);
+        }
+        Err(ChannelOrderingError::Violations { count, details }.into())
+    }
+}
+
+fn check_channel(
+    index: usize,
+    channel: &ChannelRecord,
+    observed: &[(MsgId, MsgId, Ed25519PublicKey)],
+    min_messages: usize,
+    violations: &mut Vec<String>,
+) {
+    let invalid = channel
+        .invalid
+        .iter()
+        .map(|(kind, msg_id, _)| (*msg_id, *kind))
+        .collect::<HashMap<_, _>>();
+    for (kind, msg_id, accepted) in &channel.invalid {
+        if *accepted {
+            violations.push(format!(
+                "channel {index}: {kind:?} inscription {msg_id:?} was accepted by a node"
+            ));
+        }
+    }
+
+    let mut expected = channel.messages.iter();
+    let mut previous = MsgId::root();
+    let mut valid = 0usize;
+    for (msg_id, parent, signer) in observed {
+        if let Some(kind) = invalid.get(msg_id) {
+            violations.push(format!(
+                "channel {index}: {kind:?} inscription {msg_id:?} was included"
+            ));
+            continue;
+        }
+        if *signer != channel.signer {
+            violations.push(format!(
+                "channel {index}: message {msg_id:?} signed by a foreign key"
+            ));
+        }
+        if *parent != previous {
+            violations.push(format!(
+                "channel {index}: message {msg_id:?} has parent {parent:?}, expected {previous:?}"
+            ));
+        }
+        match expected.next() {
+            Some(next) if next == msg_id => {}
+            Some(next) => violations.push(format!(
+                "channel {index}: message #{valid} is {msg_id:?}, expected {next:?}"
+            )),
+            None => violations.push(format!(
+                "channel {index}: unexpected message {msg_id:?} after the last appended one"
+            )),
+        }
+        previous = *msg_id;
+        valid += 1;
+    }
+
+    if valid < min_messages {
+        violations.push(format!(
+            "channel {index}: only {valid} of {} appended messages on chain (required {min_messages})",
+            channel.messages.len()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_core::mantle::ops::channel::{ChannelId, Ed25519PublicKey, MsgId};
+    use lb_key_management_system_service::keys::Ed25519Key;
+
+    use super::{ChannelRecord, InvalidInscription, check_channel};
+    use crate::util::tx::create_channel_inscription;
+
+    struct Fixture {
+        record: ChannelRecord,
+        key: Ed25519Key,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let key = Ed25519Key::from_bytes(&[1; 32]);
+            Self {
+                record: ChannelRecord {
+                    id: ChannelId::from([7; 32]),
+                    signer: key.public_key(),
+                    messages: Vec::new(),
+                    invalid: Vec::new(),
+                },
+                key,
+            }
+        }
+
+        fn inscribe(&self, parent: MsgId, payload: &str, key: &Ed25519Key) -> MsgId {
+            create_channel_inscription(self.record.id, parent, payload.as_bytes().to_vec(), key)
+                .expect("inscription")
+                .1
+        }
+
+        /// Appends `count` linked messages and returns them as observed on
+        /// chain.
+        fn append(&mut self, count: usize) -> Vec<(MsgId, MsgId, Ed25519PublicKey)> {
+            let mut parent = MsgId::root();
+            let mut observed = Vec::new();
+            for sequence in 0..count {
+                let msg_id = self.inscribe(parent, &format!("message {sequence}"), &self.key);
+                self.record.messages.push(msg_id);
+                observed.push((msg_id, parent, self.record.signer));
+                parent = msg_id;
+            }
+            observed
+        }
+
+        fn check(&self, observed: &[(MsgId, MsgId, Ed25519PublicKey)]) -> Vec<String> {
+            let mut violations = Vec::new();
+            check_channel(0, &self.record, observed, 2, &mut violations);
+            violations
+        }
+    }
+
+    #[test]
+    fn an_in_order_prefix_passes() {
+        let mut fixture = Fixture::new();
+        let observed = fixture.append(3);
+
+        assert!(fixture.check(&observed[..2]).is_empty());
+        assert!(fixture.check(&observed).is_empty());
+    }
+
+    #[test]
+    fn gaps_reordering_and_short_chains_are_reported() {
+        let mut fixture = Fixture::new();
+        let observed = fixture.append(3);
+
+        let skipped = [observed[0], observed[2]];
+        let violations = fixture.check(&skipped);
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(violations[0].contains("has parent"));
+        assert!(violations[1].contains("message #1"));
+
+        let violations = fixture.check(&observed[..1]);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("only 1 of 3"));
+    }
+
+    #[test]
+    fn probes_must_be_refused_and_never_included() {
+        let mut fixture = Fixture::new();
+        let observed = fixture.append(2);
+        let stranger = Ed25519Key::from_bytes(&[2; 32]);
+        let wrong_parent = fixture.inscribe(MsgId::root(), "wrong parent", &fixture.key);
+        let wrong_signer = fixture.inscribe(observed[1].0, "wrong signer", &stranger);
+        fixture.record.invalid = vec![
+            (InvalidInscription::WrongParent, wrong_parent, false),
+            (InvalidInscription::WrongSigner, wrong_signer, true),
+        ];
+
+        let violations = fixture.check(&observed);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("WrongSigner inscription"));
+        assert!(violations[0].contains("accepted by a node"));
+
+        let mut with_probe = observed.clone();
+        with_probe.push((wrong_parent, MsgId::root(), fixture.record.signer));
+        let violations = fixture.check(&with_probe);
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(violations[1].contains("WrongParent inscription"));
+        assert!(violations[1].contains("was included"));
+    }
+
+    #[test]
+    fn foreign_signers_are_reported() {
+        let mut fixture = Fixture::new();
+        let mut observed = fixture.append(2);
+        observed[1].2 = Ed25519Key::from_bytes(&[3; 32]).public_key();
+
+        let violations = fixture.check(&observed);
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("signed by a foreign key"));
+    }
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, time::Duration};
 
 use async_trait::async_trait;
-use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
+use rand::{Rng, seq::SliceRandom as _};
 use testing_framework_core::scenario::{DynError, RunContext, Workload};
 use tokio::time::{Instant, sleep};
 use tracing::info;
@@ -53,7 +53,7 @@ impl RandomRestartWorkload {
         targets
     }
 
-    fn random_delay(&self) -> Duration {
+    fn random_delay(&self, rng: &mut impl Rng) -> Duration {
         if self.max_delay <= self.min_delay {
             return self.min_delay;
         }
@@ -62,7 +62,7 @@ impl RandomRestartWorkload {
             .checked_sub(self.min_delay)
             .unwrap_or(MIN_DELAY_SPREAD_FALLBACK)
             .as_secs_f64();
-        let offset = thread_rng().gen_range(0.0..=spread);
+        let offset = rng.gen_range(0.0..=spread);
         let delay = self
             .min_delay
             .checked_add(Duration::from_secs_f64(offset))
@@ -85,6 +85,7 @@ impl RandomRestartWorkload {
         &self,
         targets: &[Target],
         cooldowns: &HashMap<Target, Instant>,
+        rng: &mut (impl Rng + Send),
     ) -> Result<Target, DynError> {
         if targets.is_empty() {
             return Err("chaos restart workload has no eligible targets".into());
@@ -115,12 +116,12 @@ impl RandomRestartWorkload {
                 .filter(|target| cooldowns.get(target).is_none_or(|ready| *ready <= now))
                 .collect();
 
-            if let Some(choice) = available.choose(&mut thread_rng()).cloned() {
+            if let Some(choice) = available.choose(rng).cloned() {
                 tracing::debug!(?choice, "chaos restart picked target");
                 return Ok(choice);
             }
 
-            if let Some(choice) = targets.choose(&mut thread_rng()).cloned() {
+            if let Some(choice) = targets.choose(rng).cloned() {
                 return Ok(choice);
             }
             return Err("chaos restart workload has no eligible targets".into());
@@ -148,14 +149,16 @@ impl Workload for RandomRestartWorkload {
             config = ?self,
             nodes = ctx.descriptors().nodes().len(),
             target_count = targets.len(),
+            seed = ctx.seed(),
             "starting chaos restart workload"
         );
 
+        let mut rng = ctx.seeded_rng(self.name());
         let mut cooldowns = self.initialize_cooldowns(&targets);
 
         loop {
-            sleep(self.random_delay()).await;
-            let target = self.pick_target(&targets, &cooldowns).await?;
+            sleep(self.random_delay(&mut rng)).await;
+            let target = self.pick_target(&targets, &cooldowns, &mut rng).await?;
 
             match target {
                 Target::Node(ref name) => {
@@ -1,4 +1,6 @@
+pub mod channel;
 pub mod chaos;
+pub mod partition;
 pub mod transaction;
 pub mod util;
 
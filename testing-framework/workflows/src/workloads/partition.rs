@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    scenario::{DynError, Expectation, RunContext, RunMetrics, Workload},
+    topology::generation::GeneratedTopology,
+};
+use tokio::time::sleep;
+use tracing::info;
+
+use crate::expectations::ChainReconvergence;
+
+/// Splits the topology into groups for a window and then heals it, so chains
+/// built on each side have to be reconciled by fork choice.
+#[derive(Debug)]
+pub struct NetworkPartitionWorkload {
+    groups: usize,
+    initial_delay: Duration,
+    partition_duration: Duration,
+    heal_duration: Duration,
+    cycles: usize,
+}
+
+impl NetworkPartitionWorkload {
+    /// Creates a partition workload.
+    ///
+    /// Nodes are split into `groups` contiguous groups. The first group keeps
+    /// the original network; every other group is moved into a partition of
+    /// its own for `partition_duration`. After healing, the workload waits
+    /// `heal_duration` before the next of `cycles` partitions.
+    #[must_use]
+    pub const fn new(
+        groups: usize,
+        initial_delay: Duration,
+        partition_duration: Duration,
+        heal_duration: Duration,
+        cycles: usize,
+    ) -> Self {
+        Self {
+            groups,
+            initial_delay,
+            partition_duration,
+            heal_duration,
+            cycles,
+        }
+    }
+
+    fn partition_groups(&self, node_count: usize) -> Vec<Vec<String>> {
+        split_into_groups(node_count, self.groups)
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|index| format!("node-{index}"))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Workload for NetworkPartitionWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_partition"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(ChainReconvergence::default())]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let node_count = descriptors.nodes().len();
+        if self.groups < 2 {
+            return Err("partition workload requires at least two groups".into());
+        }
+        if node_count < self.groups {
+            return Err(format!(
+                "partition workload needs at least {} nodes (nodes={node_count})",
+                self.groups
+            )
+            .into());
+        }
+        if self.cycles == 0 {
+            return Err("partition workload requires at least one cycle".into());
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "partition workload requires node control".to_owned())?;
+
+        let groups = self.partition_groups(ctx.descriptors().nodes().len());
+        info!(config = ?self, ?groups, "starting partition workload");
+
+        sleep(self.initial_delay).await;
+
+        for cycle in 0..self.cycles {
+            for group in groups.iter().skip(1) {
+                info!(cycle, ?group, "partitioning nodes");
+                handle
+                    .partition_nodes(group)
+                    .await
+                    .map_err(|err| format!("node partition failed: {err}"))?;
+            }
+
+            sleep(self.partition_duration).await;
+
+            for name in groups.iter().skip(1).flatten() {
+                info!(cycle, name, "healing partitioned node");
+                handle
+                    .reconnect_node(name)
+                    .await
+                    .map_err(|err| format!("node reconnect failed: {err}"))?;
+            }
+
+            if cycle + 1 < self.cycles {
+                sleep(self.heal_duration).await;
+            }
+        }
+
+        info!(
+            cycles = self.cycles,
+            "partition workload healed all partitions"
+        );
+        Ok(())
+    }
+}
+
+/// Split `0..node_count` into `groups` contiguous, near-equal index groups.
+fn split_into_groups(node_count: usize, groups: usize) -> Vec<Vec<usize>> {
+    let groups = groups.clamp(1, node_count.max(1));
+    let base = node_count / groups;
+    let extra = node_count % groups;
+
+    let mut next = 0;
+    (0..groups)
+        .map(|group| {
+            let size = base + usize::from(group < extra);
+            let members = (next..next + size).collect();
+            next += size;
+            members
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_into_groups;
+
+    #[test]
+    fn splits_nodes_into_contiguous_groups() {
+        assert_eq!(split_into_groups(5, 2), vec![vec![0, 1, 2], vec![3, 4]]);
+        assert_eq!(
+            split_into_groups(4, 4),
+            vec![vec![0], vec![1], vec![2], vec![3]]
+        );
+        assert_eq!(split_into_groups(2, 3), vec![vec![0], vec![1]]);
+    }
+}
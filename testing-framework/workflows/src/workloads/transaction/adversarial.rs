@@ -0,0 +1,905 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::{
+    MantleTx, Note, SignedMantleTx, Transaction as _, TxHash, Utxo, ops::channel::ChannelId,
+    tx_builder::MantleTxBuilder,
+};
+use lb_key_management_system_service::keys::ZkKey;
+use rand::{Rng as _, rngs::StdRng};
+use serde::Deserialize;
+use testing_framework_config::topology::configs::wallet::WalletAccount;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{
+        BlockFeedEvent, BlockRecord, DynError, Expectation, RunContext, RunMetrics,
+        Workload as ScenarioWorkload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{sleep, timeout},
+};
+
+use super::{
+    chained::{build_chained_transaction, split_value},
+    workload::wallet_utxo_map,
+};
+use crate::util::tx::create_inscription_transaction;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_RESERVED_ACCOUNTS: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+const DEFAULT_INSCRIPTION_BYTES: usize = 1024 * 1024;
+const DEFAULT_PROBE_NOTES: NonZeroUsize = NonZeroUsize::new(64).unwrap();
+/// Offset added to real output indices to address notes that do not exist.
+const MISSING_OUTPUT_OFFSET: usize = 1_000_000;
+const MAX_LISTED_VIOLATIONS: usize = 10;
+
+/// Kind of invalid transaction submitted by [`AdversarialWorkload`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttackKind {
+    /// Two different transactions spending the same note, sent to two
+    /// different nodes at once. At most one of them may be included.
+    DoubleSpend,
+    /// A spend of a real note signed with the wrong key.
+    BadSignature,
+    /// A correctly signed spend of a note that was never created.
+    UnknownNote,
+    /// A spend that adds a zero-value output.
+    ZeroValueOutput,
+    /// A channel inscription far above any sane payload size.
+    OversizedInscription,
+}
+
+impl AttackKind {
+    pub const ALL: [Self; 5] = [
+        Self::DoubleSpend,
+        Self::BadSignature,
+        Self::UnknownNote,
+        Self::ZeroValueOutput,
+        Self::OversizedInscription,
+    ];
+
+    /// Whether nodes must refuse this transaction outright. Each half of a
+    /// double spend is valid on its own, so a node may accept one of them.
+    #[must_use]
+    pub const fn must_be_rejected(self) -> bool {
+        !matches!(self, Self::DoubleSpend)
+    }
+
+    /// Whether the attack spends one of the probe notes.
+    const fn spends_probe_note(self) -> bool {
+        matches!(
+            self,
+            Self::BadSignature | Self::UnknownNote | Self::ZeroValueOutput
+        )
+    }
+}
+
+/// How a node answered an invalid submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Accepted,
+    /// The node refused the transaction with a client error (HTTP 4xx).
+    Rejected(String),
+    /// The submission failed otherwise (transport error, HTTP 5xx), which
+    /// says nothing about whether the node validated it.
+    Failed(String),
+}
+
+/// One submission of an invalid transaction.
+#[derive(Clone, Debug)]
+pub struct AttackRecord {
+    pub kind: AttackKind,
+    pub tx_hash: TxHash,
+    /// Base URL of the node the transaction was sent to.
+    pub node: String,
+    pub outcome: SubmissionOutcome,
+    /// The conflicting transaction of a double spend.
+    pub twin: Option<TxHash>,
+}
+
+impl AttackRecord {
+    #[must_use]
+    pub const fn accepted(&self) -> bool {
+        matches!(self.outcome, SubmissionOutcome::Accepted)
+    }
+
+    #[must_use]
+    pub const fn rejected(&self) -> bool {
+        matches!(self.outcome, SubmissionOutcome::Rejected(_))
+    }
+}
+
+/// Shared log of every invalid submission; clones share the same log.
+#[derive(Clone, Debug, Default)]
+pub struct AttackLog {
+    inner: Arc<Mutex<AttackLogInner>>,
+}
+
+#[derive(Debug, Default)]
+struct AttackLogInner {
+    attacks: Vec<AttackRecord>,
+    setup: Vec<TxHash>,
+}
+
+impl AttackLog {
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<AttackRecord> {
+        self.lock().attacks.clone()
+    }
+
+    /// Valid transactions the workload submitted to prepare its attacks.
+    #[must_use]
+    pub fn setup_transactions(&self) -> Vec<TxHash> {
+        self.lock().setup.clone()
+    }
+
+    fn record(&self, record: AttackRecord) {
+        self.lock().attacks.push(record);
+    }
+
+    fn record_setup(&self, tx_hash: TxHash) {
+        self.lock().setup.push(tx_hash);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AttackLogInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Submits malformed and invalid transactions for the whole run, cycling
+/// through the configured [`AttackKind`]s.
+///
+/// It spends from the last `reserved_accounts` seeded wallets so it does not
+/// compete with valid traffic drawn from the front of the wallet list; limit
+/// the transaction workload's `users` accordingly. Every double spend burns
+/// one reserved account, so double spends stop once those run out.
+///
+/// Probes that spend a note (bad signatures, unknown notes, zero-value
+/// outputs) each get a fresh one, so no probe is refused only because an
+/// earlier probe was wrongly included. Before the first attack, the first
+/// reserved account's note is split into `probe_notes` notes by one valid
+/// transaction; probes stop once those are used up.
+#[derive(Clone)]
+pub struct AdversarialWorkload {
+    kinds: Vec<AttackKind>,
+    interval: Duration,
+    reserved_accounts: NonZeroUsize,
+    probe_notes: NonZeroUsize,
+    inscription_bytes: usize,
+    min_valid_inclusions: u64,
+    log: AttackLog,
+    accounts: Vec<(WalletAccount, Utxo)>,
+}
+
+impl AdversarialWorkload {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            kinds: AttackKind::ALL.to_vec(),
+            interval: DEFAULT_INTERVAL,
+            reserved_accounts: DEFAULT_RESERVED_ACCOUNTS,
+            probe_notes: DEFAULT_PROBE_NOTES,
+            inscription_bytes: DEFAULT_INSCRIPTION_BYTES,
+            min_valid_inclusions: 1,
+            log: AttackLog::default(),
+            accounts: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Restricts the attacks to `kinds` (all of them when empty).
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = AttackKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        if self.kinds.is_empty() {
+            self.kinds = AttackKind::ALL.to_vec();
+        }
+        self
+    }
+
+    #[must_use]
+    /// Pause between two invalid submissions.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Number of seeded wallets, taken from the end of the list, this
+    /// workload may spend from.
+    pub const fn with_reserved_accounts(mut self, accounts: NonZeroUsize) -> Self {
+        self.reserved_accounts = accounts;
+        self
+    }
+
+    #[must_use]
+    /// Number of fresh notes prepared for probes, i.e. how many probes can
+    /// be sent during the run.
+    pub const fn with_probe_notes(mut self, notes: NonZeroUsize) -> Self {
+        self.probe_notes = notes;
+        self
+    }
+
+    #[must_use]
+    /// Payload size of oversized inscriptions.
+    pub const fn with_inscription_bytes(mut self, bytes: usize) -> Self {
+        self.inscription_bytes = bytes;
+        self
+    }
+
+    #[must_use]
+    /// Transactions outside this workload that must still be included while
+    /// it runs (zero disables the check).
+    pub const fn with_min_valid_inclusions(mut self, count: u64) -> Self {
+        self.min_valid_inclusions = count;
+        self
+    }
+
+    #[must_use]
+    pub const fn log(&self) -> &AttackLog {
+        &self.log
+    }
+}
+
+impl Default for AdversarialWorkload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for AdversarialWorkload {
+    fn name(&self) -> &'static str {
+        "adversarial_tx_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(TxRejectionExpectation::new(
+            self.log.clone(),
+            self.min_valid_inclusions,
+        ))]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let reference_node = descriptors
+            .nodes()
+            .first()
+            .ok_or("adversarial workload requires at least one node in the topology")?;
+        let utxo_map = wallet_utxo_map(reference_node);
+
+        self.accounts = descriptors
+            .config()
+            .wallet()
+            .accounts
+            .iter()
+            .rev()
+            .filter_map(|account| {
+                utxo_map
+                    .get(&account.public_key())
+                    .map(|utxo| (account.clone(), *utxo))
+            })
+            .take(self.reserved_accounts.get())
+            .collect();
+        if self.accounts.is_empty() {
+            return Err(
+                "adversarial workload initialization failed: no wallet accounts matched genesis notes"
+                    .into(),
+            );
+        }
+
+        tracing::info!(
+            accounts = self.accounts.len(),
+            kinds = ?self.kinds,
+            "adversarial workload accounts reserved"
+        );
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        Attacker::new(self, ctx)?.run().await
+    }
+}
+
+struct Attacker<'a> {
+    config: &'a AdversarialWorkload,
+    ctx: &'a RunContext,
+    clients: Vec<ApiClient>,
+    rng: StdRng,
+    /// Account that signs the probes.
+    probe: WalletAccount,
+    /// Its genesis note, split into the probe notes before the first attack.
+    probe_genesis: Option<Utxo>,
+    /// Probe notes not used yet; each probe spends one.
+    probe_notes: Vec<Utxo>,
+    /// Accounts left for double spends, each usable once.
+    double_spenders: Vec<(WalletAccount, Utxo)>,
+    attempts: usize,
+}
+
+impl<'a> Attacker<'a> {
+    fn new(config: &'a AdversarialWorkload, ctx: &'a RunContext) -> Result<Self, DynError> {
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err("adversarial workload requires at least one node client".into());
+        }
+        let mut accounts = config.accounts.clone();
+        if accounts.is_empty() {
+            return Err("adversarial workload has no reserved accounts".into());
+        }
+        let (probe, probe_genesis) = accounts.remove(0);
+
+        Ok(Self {
+            config,
+            ctx,
+            clients,
+            rng: ctx.seeded_rng(config.name()),
+            probe,
+            probe_genesis: Some(probe_genesis),
+            probe_notes: Vec::new(),
+            double_spenders: accounts,
+            attempts: 0,
+        })
+    }
+
+    async fn run(mut self) -> Result<(), DynError> {
+        let deadline = Instant::now() + self.ctx.run_duration();
+        let mut kinds = self.config.kinds.iter().copied().cycle();
+
+        if self
+            .config
+            .kinds
+            .iter()
+            .any(|kind| kind.spends_probe_note())
+            && let Err(err) = self.prepare_probe_notes(deadline).await
+        {
+            tracing::warn!(%err, "could not prepare probe notes; probes are skipped");
+        }
+
+        while Instant::now() < deadline {
+            let Some(kind) = kinds.next() else {
+                break;
+            };
+            if let Err(err) = self.attack(kind).await {
+                tracing::warn!(?kind, %err, "could not build adversarial transaction");
+            }
+            sleep(
+                self.config
+                    .interval
+                    .min(deadline.saturating_duration_since(Instant::now())),
+            )
+            .await;
+        }
+
+        tracing::info!(
+            submissions = self.config.log.snapshot().len(),
+            "adversarial workload finished"
+        );
+        Ok(())
+    }
+
+    /// Splits the probe account's genesis note into fresh probe notes and
+    /// waits until the split is included.
+    async fn prepare_probe_notes(&mut self, deadline: Instant) -> Result<(), DynError> {
+        let Some(genesis) = self.probe_genesis.take() else {
+            return Ok(());
+        };
+        let outputs = split_value(genesis.note.value, self.config.probe_notes.get())
+            .into_iter()
+            .map(|value| Note::new(value, self.probe.public_key()))
+            .collect();
+        let split = build_chained_transaction(&self.probe, &[genesis], outputs)?;
+        let split_hash = split.hash();
+
+        let mut events = self.ctx.block_feed().subscribe_events();
+        self.random_client().submit_transaction(&split).await?;
+        self.config.log.record_setup(split_hash);
+        timeout(
+            deadline.saturating_duration_since(Instant::now()),
+            wait_for_inclusion(&mut events, split_hash),
+        )
+        .await
+        .map_err(|_| "probe note split was not included before the run ended")??;
+
+        let ledger_tx = &split.mantle_tx().ledger_tx;
+        let ledger_hash = ledger_tx.hash();
+        self.probe_notes = ledger_tx
+            .outputs
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(idx, note)| Utxo::new(ledger_hash, idx, *note))
+            .collect();
+        tracing::info!(notes = self.probe_notes.len(), "probe notes ready");
+        Ok(())
+    }
+
+    async fn attack(&mut self, kind: AttackKind) -> Result<(), DynError> {
+        self.attempts += 1;
+        let account = self.probe.clone();
+        let tx = match kind {
+            AttackKind::DoubleSpend => return self.double_spend().await,
+            AttackKind::OversizedInscription => {
+                let channel = ChannelId::from(self.rng.r#gen::<[u8; 32]>());
+                create_inscription_transaction(channel, vec![0xAB; self.config.inscription_bytes])?
+            }
+            AttackKind::BadSignature | AttackKind::UnknownNote | AttackKind::ZeroValueOutput => {
+                let Some(utxo) = self.probe_notes.pop() else {
+                    tracing::debug!(?kind, "no probe notes left; skipping");
+                    return Ok(());
+                };
+                self.probe(kind, &account, utxo)?
+            }
+        };
+
+        let client = self.random_client();
+        let record = submit(&client, kind, &tx, None).await;
+        self.config.log.record(record);
+        Ok(())
+    }
+
+    /// Builds a probe of `kind` that spends the fresh note `utxo`.
+    fn probe(
+        &self,
+        kind: AttackKind,
+        account: &WalletAccount,
+        utxo: Utxo,
+    ) -> Result<SignedMantleTx, DynError> {
+        let tx = match kind {
+            AttackKind::BadSignature => {
+                let mantle_tx = spend(
+                    &[utxo],
+                    vec![Note::new(utxo.note.value, account.public_key())],
+                );
+                sign_unchecked(mantle_tx, &[ZkKey::zero()])?
+            }
+            AttackKind::UnknownNote => {
+                let missing = Utxo::new(
+                    utxo.tx_hash,
+                    MISSING_OUTPUT_OFFSET + self.attempts,
+                    utxo.note,
+                );
+                let mantle_tx = spend(
+                    &[missing],
+                    vec![Note::new(utxo.note.value, account.public_key())],
+                );
+                sign_unchecked(mantle_tx, &[account.secret_key.clone()])?
+            }
+            AttackKind::ZeroValueOutput => {
+                let mantle_tx = spend(
+                    &[utxo],
+                    vec![
+                        Note::new(utxo.note.value, account.public_key()),
+                        Note::new(0, account.public_key()),
+                    ],
+                );
+                sign_unchecked(mantle_tx, &[account.secret_key.clone()])?
+            }
+            AttackKind::DoubleSpend | AttackKind::OversizedInscription => {
+                return Err(format!("{kind:?} does not spend a probe note").into());
+            }
+        };
+        Ok(tx)
+    }
+
+    /// Sends two conflicting spends of one note to two different nodes at
+    /// the same time.
+    async fn double_spend(&mut self) -> Result<(), DynError> {
+        let Some((account, utxo)) = self.double_spenders.pop() else {
+            tracing::debug!("no accounts left for double spends; skipping");
+            return Ok(());
+        };
+        let to_self = build_chained_transaction(
+            &account,
+            &[utxo],
+            vec![Note::new(utxo.note.value, account.public_key())],
+        )?;
+        let to_probe = build_chained_transaction(
+            &account,
+            &[utxo],
+            vec![Note::new(utxo.note.value, self.probe.public_key())],
+        )?;
+
+        let first = self.random_client();
+        let second = self
+            .clients
+            .iter()
+            .find(|client| client.base_url() != first.base_url())
+            .cloned()
+            .unwrap_or_else(|| first.clone());
+        let (a, b) = tokio::join!(
+            submit(
+                &first,
+                AttackKind::DoubleSpend,
+                &to_self,
+                Some(to_probe.hash())
+            ),
+            submit(
+                &second,
+                AttackKind::DoubleSpend,
+                &to_probe,
+                Some(to_self.hash())
+            ),
+        );
+        self.config.log.record(a);
+        self.config.log.record(b);
+        Ok(())
+    }
+
+    fn random_client(&mut self) -> ApiClient {
+        let idx = self.rng.gen_range(0..self.clients.len());
+        self.clients[idx].clone()
+    }
+}
+
+fn spend(inputs: &[Utxo], outputs: Vec<Note>) -> MantleTx {
+    let builder = inputs.iter().fold(MantleTxBuilder::new(), |builder, utxo| {
+        builder.add_ledger_input(*utxo)
+    });
+    outputs
+        .into_iter()
+        .fold(builder, |builder, note| builder.add_ledger_output(note))
+        .build()
+}
+
+/// Signs `mantle_tx` with `keys` without the structural checks of
+/// `SignedMantleTx::new`, so invalid transactions reach the node as built.
+fn sign_unchecked(mantle_tx: MantleTx, keys: &[ZkKey]) -> Result<SignedMantleTx, DynError> {
+    let tx_hash = mantle_tx.hash();
+    let ledger_tx_proof = ZkKey::multi_sign(keys, tx_hash.as_ref())
+        .map_err(|err| format!("adversarial transaction signing failed: {err}"))?;
+    Ok(SignedMantleTx {
+        mantle_tx,
+        ops_proofs: Vec::new(),
+        ledger_tx_proof,
+    })
+}
+
+async fn submit(
+    client: &ApiClient,
+    kind: AttackKind,
+    tx: &SignedMantleTx,
+    twin: Option<TxHash>,
+) -> AttackRecord {
+    let tx_hash = tx.hash();
+    let node = client.base_url().to_string();
+    let outcome = match client.submit_transaction(tx).await {
+        Ok(()) => {
+            tracing::debug!(?kind, ?tx_hash, %node, "invalid transaction accepted");
+            SubmissionOutcome::Accepted
+        }
+        Err(err) if err.status().is_some_and(|status| status.is_client_error()) => {
+            tracing::debug!(?kind, ?tx_hash, %node, %err, "invalid transaction rejected");
+            SubmissionOutcome::Rejected(err.to_string())
+        }
+        Err(err) => {
+            tracing::debug!(?kind, ?tx_hash, %node, %err, "invalid transaction submission failed");
+            SubmissionOutcome::Failed(err.to_string())
+        }
+    };
+    AttackRecord {
+        kind,
+        tx_hash,
+        node,
+        outcome,
+        twin,
+    }
+}
+
+async fn wait_for_inclusion(
+    events: &mut broadcast::Receiver<BlockFeedEvent>,
+    tx_hash: TxHash,
+) -> Result<(), DynError> {
+    loop {
+        let blocks = match events.recv().await {
+            Ok(BlockFeedEvent::Block(record)) => vec![record],
+            Ok(BlockFeedEvent::Reorg(reorg)) => reorg.adopted.clone(),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::debug!(skipped, "probe note split wait lagged");
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                return Err("block feed closed before the probe note split was included".into());
+            }
+        };
+        for record in &blocks {
+            for tx in record.block.transactions() {
+                if tx.hash() == tx_hash {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum TxRejectionError {
+    #[error("tx rejection expectation not captured")]
+    NotCaptured,
+    #[error("adversarial workload submitted no transactions")]
+    NoAttempts,
+    #[error("{count} invalid transaction(s) were not handled correctly:\n{details}")]
+    Violations { count: usize, details: String },
+    #[error(
+        "only {observed} valid transactions included alongside the attacks (required {required})"
+    )]
+    ValidTrafficStalled { observed: u64, required: u64 },
+}
+
+/// Checks the outcome of an [`AdversarialWorkload`]: nodes refused every
+/// transaction that is invalid on its own, no such transaction ever appeared
+/// in a block, no double spend had both halves included, and valid
+/// transactions kept being included meanwhile.
+#[derive(Clone)]
+pub struct TxRejectionExpectation {
+    log: AttackLog,
+    min_valid_inclusions: u64,
+    included: Option<Arc<Mutex<HashSet<TxHash>>>>,
+}
+
+impl TxRejectionExpectation {
+    pub const NAME: &'static str = "tx_rejection";
+
+    #[must_use]
+    pub const fn new(log: AttackLog, min_valid_inclusions: u64) -> Self {
+        Self {
+            log,
+            min_valid_inclusions,
+            included: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for TxRejectionExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.included.is_some() {
+            return Ok(());
+        }
+        let included = Arc::new(Mutex::new(HashSet::new()));
+        spawn_inclusion_capture(ctx.block_feed().subscribe(), Arc::clone(&included));
+        self.included = Some(included);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let included = self
+            .included
+            .as_ref()
+            .ok_or(TxRejectionError::NotCaptured)?
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let attacks = self.log.snapshot();
+        if attacks.is_empty() {
+            return Err(TxRejectionError::NoAttempts.into());
+        }
+
+        let violations = attack_violations(&attacks, &included);
+
+        let by_kind =
+            attacks
+                .iter()
+                .fold(HashMap::<_, (usize, usize)>::new(), |mut acc, attack| {
+                    let entry = acc.entry(attack.kind).or_default();
+                    entry.0 += 1;
+                    if attack.rejected() {
+                        entry.1 += 1;
+                    }
+                    acc
+                });
+        tracing::info!(
+            ?by_kind,
+            "adversarial submissions (total, rejected) by kind"
+        );
+
+        if !violations.is_empty() {
+            let count = violations.len();
+            let mut details = String::new();
+            for violation in violations.iter().take(MAX_LISTED_VIOLATIONS) {
+                let _ = writeln!(details, "- {violation}");
+            }
+            if count > MAX_LISTED_VIOLATIONS {
+                let _ = writeln!(details, "- ... and {} more", count - MAX_LISTED_VIOLATIONS);
+            }
+            return Err(TxRejectionError::Violations { count, details }.into());
+        }
+
+        let valid = valid_inclusions(&included, &attacks, &self.log.setup_transactions());
+        if valid < self.min_valid_inclusions {
+            return Err(TxRejectionError::ValidTrafficStalled {
+                observed: valid,
+                required: self.min_valid_inclusions,
+            }
+            .into());
+        }
+
+        tracing::info!(
+            attacks = attacks.len(),
+            valid_inclusions = valid,
+            "invalid transactions rejected"
+        );
+        Ok(())
+    }
+}
+
+/// Everything the nodes got wrong about `attacks`, given the transactions
+/// seen in blocks.
+fn attack_violations(attacks: &[AttackRecord], included: &HashSet<TxHash>) -> Vec<String> {
+    let mut violations = Vec::new();
+    for attack in attacks
+        .iter()
+        .filter(|attack| attack.kind.must_be_rejected())
+    {
+        match &attack.outcome {
+            SubmissionOutcome::Accepted => violations.push(format!(
+                "{} accepted {:?} tx {:?}",
+                attack.node, attack.kind, attack.tx_hash
+            )),
+            SubmissionOutcome::Failed(error) => violations.push(format!(
+                "{} did not reject {:?} tx {:?} with a client error: {error}",
+                attack.node, attack.kind, attack.tx_hash
+            )),
+            SubmissionOutcome::Rejected(_) => {}
+        }
+        if included.contains(&attack.tx_hash) {
+            violations.push(format!(
+                "{:?} tx {:?} was included in a block",
+                attack.kind, attack.tx_hash
+            ));
+        }
+    }
+    // Each pair is logged twice, once per half; report it once.
+    let mut reported = HashSet::new();
+    for attack in attacks {
+        let Some(twin) = attack.twin else {
+            continue;
+        };
+        if included.contains(&attack.tx_hash)
+            && included.contains(&twin)
+            && !reported.contains(&attack.tx_hash)
+        {
+            reported.insert(twin);
+            violations.push(format!(
+                "both halves of double spend {:?} / {twin:?} were included",
+                attack.tx_hash
+            ));
+        }
+    }
+    violations
+}
+
+/// Included transactions that are neither attacks nor the workload's setup.
+fn valid_inclusions(included: &HashSet<TxHash>, attacks: &[AttackRecord], setup: &[TxHash]) -> u64 {
+    let own = attacks
+        .iter()
+        .map(|attack| attack.tx_hash)
+        .chain(setup.iter().copied())
+        .collect::<HashSet<_>>();
+    included.iter().filter(|hash| !own.contains(hash)).count() as u64
+}
+
+fn spawn_inclusion_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    included: Arc<Mutex<HashSet<TxHash>>>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let mut included = included
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    for tx in record.block.transactions() {
+                        included.insert(tx.hash());
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "tx rejection capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use lb_core::mantle::{Note, Transaction as _, TxHash};
+    use testing_framework_config::topology::configs::wallet::WalletAccount;
+
+    use super::{
+        AttackKind, AttackRecord, SubmissionOutcome, attack_violations, spend, valid_inclusions,
+    };
+
+    fn hash(seed: u64) -> TxHash {
+        let owner = WalletAccount::deterministic(0, 1).public_key();
+        spend(&[], vec![Note::new(seed + 1, owner)])
+            .ledger_tx
+            .hash()
+    }
+
+    fn attack(kind: AttackKind, seed: u64, outcome: SubmissionOutcome) -> AttackRecord {
+        AttackRecord {
+            kind,
+            tx_hash: hash(seed),
+            node: format!("node-{seed}"),
+            outcome,
+            twin: None,
+        }
+    }
+
+    fn rejected() -> SubmissionOutcome {
+        SubmissionOutcome::Rejected("400 Bad Request".to_owned())
+    }
+
+    #[test]
+    fn only_client_errors_count_as_rejections() {
+        let attacks = [
+            attack(AttackKind::BadSignature, 1, rejected()),
+            attack(
+                AttackKind::UnknownNote,
+                2,
+                SubmissionOutcome::Failed("500 Internal Server Error".to_owned()),
+            ),
+            attack(AttackKind::ZeroValueOutput, 3, SubmissionOutcome::Accepted),
+        ];
+
+        let violations = attack_violations(&attacks, &HashSet::new());
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(violations[0].contains("did not reject UnknownNote"));
+        assert!(violations[1].contains("accepted ZeroValueOutput"));
+
+        // A rejected transaction must still never be included.
+        let violations = attack_violations(&attacks[..1], &HashSet::from([hash(1)]));
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("was included in a block"));
+    }
+
+    #[test]
+    fn double_spends_fail_only_when_both_halves_are_included() {
+        let mut first = attack(AttackKind::DoubleSpend, 1, SubmissionOutcome::Accepted);
+        let mut second = attack(
+            AttackKind::DoubleSpend,
+            2,
+            SubmissionOutcome::Failed("connection reset".to_owned()),
+        );
+        first.twin = Some(second.tx_hash);
+        second.twin = Some(first.tx_hash);
+        let attacks = [first, second];
+
+        assert!(attack_violations(&attacks, &HashSet::from([hash(1)])).is_empty());
+        let violations = attack_violations(&attacks, &HashSet::from([hash(1), hash(2)]));
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert!(violations[0].contains("both halves"));
+    }
+
+    #[test]
+    fn valid_inclusions_skip_attacks_and_setup() {
+        let attacks = [attack(AttackKind::BadSignature, 1, rejected())];
+        let included = HashSet::from([hash(1), hash(2), hash(3), hash(4)]);
+
+        assert_eq!(valid_inclusions(&included, &attacks, &[hash(2)]), 2);
+    }
+}
@@ -0,0 +1,499 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    num::NonZeroUsize,
+    sync::Arc,
+    time::Duration,
+};
+
+use lb_core::mantle::{
+    AuthenticatedMantleTx as _, Note, SignedMantleTx, Transaction as _, TxHash, Utxo,
+    tx_builder::MantleTxBuilder,
+};
+use lb_key_management_system_service::keys::{ZkKey, ZkPublicKey};
+use testing_framework_config::topology::configs::wallet::WalletAccount;
+use testing_framework_core::scenario::{BlockFeedEvent, BlockRecord, DynError, RunContext};
+use tokio::{
+    sync::broadcast,
+    time::{Instant, interval_at},
+};
+
+use super::tracker::SubmissionTracker;
+use crate::workloads::util::submit_transaction_to_any_node;
+
+/// Submitted transactions not seen in a block within this window are given up
+/// on. Their inputs are not spent again: the transaction may still land, and
+/// re-spending them would be a double spend.
+const PENDING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Shape of the transactions built when the workload keeps re-spending its
+/// own outputs instead of only the genesis notes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainedSpends {
+    fan_in: NonZeroUsize,
+    fan_out: NonZeroUsize,
+}
+
+impl ChainedSpends {
+    /// Each transaction consumes up to `fan_in` notes of one owner and splits
+    /// their value into up to `fan_out` notes for the next owners in line.
+    #[must_use]
+    pub const fn new(fan_in: NonZeroUsize, fan_out: NonZeroUsize) -> Self {
+        Self { fan_in, fan_out }
+    }
+
+    #[must_use]
+    pub const fn fan_in(&self) -> NonZeroUsize {
+        self.fan_in
+    }
+
+    #[must_use]
+    pub const fn fan_out(&self) -> NonZeroUsize {
+        self.fan_out
+    }
+}
+
+impl Default for ChainedSpends {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::MIN, NonZeroUsize::MIN)
+    }
+}
+
+struct PendingSpend {
+    owner: usize,
+    inputs: Vec<Utxo>,
+    submitted_at: Instant,
+}
+
+/// Spendable notes per wallet account plus the spends still waiting for
+/// inclusion. Only canonical blocks credit outputs.
+pub(super) struct UtxoPool {
+    accounts: Vec<WalletAccount>,
+    owners: HashMap<ZkPublicKey, usize>,
+    available: Vec<VecDeque<Utxo>>,
+    pending: HashMap<TxHash, PendingSpend>,
+    /// Expired or orphaned spends; their outputs are credited if they land.
+    tombstones: HashSet<TxHash>,
+    /// Spends whose outputs were credited from a canonical block.
+    included: HashSet<TxHash>,
+    next_owner: usize,
+    next_recipient: usize,
+}
+
+impl UtxoPool {
+    pub(super) fn new(genesis: Vec<(WalletAccount, Utxo)>) -> Self {
+        let mut accounts = Vec::with_capacity(genesis.len());
+        let mut available = Vec::with_capacity(genesis.len());
+        for (account, utxo) in genesis {
+            accounts.push(account);
+            available.push(VecDeque::from([utxo]));
+        }
+        let owners = accounts
+            .iter()
+            .enumerate()
+            .map(|(index, account)| (account.public_key(), index))
+            .collect();
+
+        Self {
+            accounts,
+            owners,
+            available,
+            pending: HashMap::new(),
+            tombstones: HashSet::new(),
+            included: HashSet::new(),
+            next_owner: 0,
+            next_recipient: 1,
+        }
+    }
+
+    pub(super) fn spendable(&self) -> usize {
+        self.available.iter().map(VecDeque::len).sum()
+    }
+
+    pub(super) fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub(super) fn account(&self, owner: usize) -> &WalletAccount {
+        &self.accounts[owner]
+    }
+
+    /// Picks the next owner in round-robin order, preferring one that can fill
+    /// the whole fan-in, and takes its inputs out of the pool.
+    pub(super) fn take_inputs(&mut self, fan_in: usize) -> Option<(usize, Vec<Utxo>)> {
+        let count = self.accounts.len();
+        let order = (0..count).map(|offset| (self.next_owner + offset) % count);
+        let owner = order
+            .clone()
+            .find(|owner| self.available[*owner].len() >= fan_in)
+            .or_else(|| {
+                order
+                    .clone()
+                    .find(|owner| !self.available[*owner].is_empty())
+            })?;
+
+        self.next_owner = (owner + 1) % count;
+        let take = fan_in.min(self.available[owner].len());
+        Some((owner, self.available[owner].drain(..take).collect()))
+    }
+
+    /// Splits the value of `inputs` into up to `fan_out` notes addressed to
+    /// the next recipients in line.
+    pub(super) fn outputs_for(&mut self, inputs: &[Utxo], fan_out: usize) -> Vec<Note> {
+        let total = inputs.iter().map(|utxo| utxo.note.value).sum::<u64>();
+        let values = split_value(total, fan_out);
+        let recipients = self.recipients(values.len());
+        recipients
+            .into_iter()
+            .zip(values)
+            .map(|(pk, value)| Note::new(value, pk))
+            .collect()
+    }
+
+    fn recipients(&mut self, outputs: usize) -> Vec<ZkPublicKey> {
+        let count = self.accounts.len();
+        (0..outputs)
+            .map(|_| {
+                let recipient = self.next_recipient % count;
+                self.next_recipient = (recipient + 1) % count;
+                self.accounts[recipient].public_key()
+            })
+            .collect()
+    }
+
+    pub(super) fn restore(&mut self, owner: usize, inputs: Vec<Utxo>) {
+        self.available[owner].extend(inputs);
+    }
+
+    /// Holds `inputs` until `tx` is seen in a block or expires. Spends are
+    /// keyed by the ledger transaction hash, which is also the hash the
+    /// outputs are addressed by.
+    pub(super) fn track(&mut self, tx: &SignedMantleTx, owner: usize, inputs: Vec<Utxo>) {
+        self.pending.insert(
+            tx.mantle_tx().ledger_tx.hash(),
+            PendingSpend {
+                owner,
+                inputs,
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Applies a canonical chain update: credits the outputs of our spends in
+    /// new blocks and withdraws those of spends whose block was orphaned.
+    /// Returns how many of our spends were included.
+    pub(super) fn observe_event(&mut self, event: &BlockFeedEvent) -> usize {
+        match event {
+            BlockFeedEvent::Block(record) => self.observe_block(record),
+            BlockFeedEvent::Reorg(reorg) => {
+                for record in reorg.dropped.iter().rev() {
+                    self.revert_transactions(record.block.transactions());
+                }
+                reorg
+                    .adopted
+                    .iter()
+                    .map(|record| self.observe_block(record))
+                    .sum()
+            }
+        }
+    }
+
+    fn observe_block(&mut self, record: &BlockRecord) -> usize {
+        self.observe_transactions(record.block.transactions())
+    }
+
+    fn observe_transactions<'t>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'t SignedMantleTx>,
+    ) -> usize {
+        let mut included = 0;
+        for tx in transactions {
+            let ledger_tx = &tx.mantle_tx().ledger_tx;
+            let tx_hash = ledger_tx.hash();
+            let ours = self.pending.remove(&tx_hash).is_some() || self.tombstones.remove(&tx_hash);
+            if !ours {
+                continue;
+            }
+
+            included += 1;
+            self.included.insert(tx_hash);
+            for (idx, note) in ledger_tx.outputs.iter().enumerate() {
+                if let Some(owner) = self.owners.get(&note.pk) {
+                    self.available[*owner].push_back(Utxo::new(tx_hash, idx, *note));
+                }
+            }
+        }
+        included
+    }
+
+    /// Outputs of orphaned spends no longer exist; withdraw the ones still
+    /// spendable. The spend may land again on the new branch.
+    fn revert_transactions<'t>(
+        &mut self,
+        transactions: impl IntoIterator<Item = &'t SignedMantleTx>,
+    ) {
+        for tx in transactions {
+            let tx_hash = tx.mantle_tx().ledger_tx.hash();
+            if !self.included.remove(&tx_hash) {
+                continue;
+            }
+            for available in &mut self.available {
+                available.retain(|utxo| utxo.tx_hash != tx_hash);
+            }
+            self.tombstones.insert(tx_hash);
+        }
+    }
+
+    /// Gives up on spends pending for longer than [`PENDING_TIMEOUT`]. Their
+    /// inputs stay out of the pool; their outputs are still credited if the
+    /// spend lands later.
+    pub(super) fn expire_pending(&mut self, now: Instant) -> usize {
+        let expired: Vec<TxHash> = self
+            .pending
+            .iter()
+            .filter(|(_, spend)| now.duration_since(spend.submitted_at) >= PENDING_TIMEOUT)
+            .map(|(hash, _)| *hash)
+            .collect();
+
+        for hash in &expired {
+            self.pending.remove(hash);
+            self.tombstones.insert(*hash);
+        }
+        expired.len()
+    }
+}
+
+/// Keeps spending the outputs of previously included transactions for the
+/// whole run, learning about new notes from the block feed.
+pub(super) struct ChainedSubmission<'a> {
+    ctx: &'a RunContext,
+    spends: ChainedSpends,
+    pool: UtxoPool,
+    interval: Duration,
+    tracker: SubmissionTracker,
+}
+
+impl<'a> ChainedSubmission<'a> {
+    pub(super) fn new(
+        ctx: &'a RunContext,
+        spends: ChainedSpends,
+        genesis: Vec<(WalletAccount, Utxo)>,
+        interval: Duration,
+        tracker: SubmissionTracker,
+    ) -> Result<Self, DynError> {
+        if genesis.is_empty() {
+            return Err("chained transaction workload has no genesis notes to spend".into());
+        }
+
+        Ok(Self {
+            ctx,
+            spends,
+            pool: UtxoPool::new(genesis),
+            interval,
+            tracker,
+        })
+    }
+
+    pub(super) async fn execute(mut self) -> Result<(), DynError> {
+        let mut events = self.ctx.block_feed().subscribe_events();
+        let deadline = Instant::now() + self.ctx.run_duration();
+        let mut ticker = interval_at(Instant::now(), self.interval.max(Duration::from_millis(1)));
+        let mut submitted = 0_u64;
+        let mut included = 0_u64;
+
+        tracing::info!(
+            fan_in = self.spends.fan_in.get(),
+            fan_out = self.spends.fan_out.get(),
+            accounts = self.pool.accounts.len(),
+            interval_ms = self.interval.as_millis(),
+            "begin chained transaction submissions"
+        );
+
+        while Instant::now() < deadline {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let expired = self.pool.expire_pending(Instant::now());
+                    if expired > 0 {
+                        tracing::debug!(expired, "chained spends expired; inputs given up");
+                    }
+                    if self.submit_next().await? {
+                        submitted += 1;
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(event) => included += self.pool.observe_event(&event) as u64,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "chained spends lagged behind the block feed");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err("block feed closed during chained transaction workload".into());
+                    }
+                },
+            }
+        }
+
+        tracing::info!(
+            submitted,
+            included,
+            spendable = self.pool.spendable(),
+            pending = self.pool.pending(),
+            "chained transaction submissions finished"
+        );
+        Ok(())
+    }
+
+    /// Builds and submits one spend. Returns `false` when every note is
+    /// waiting on a pending transaction.
+    async fn submit_next(&mut self) -> Result<bool, DynError> {
+        let Some((owner, inputs)) = self.pool.take_inputs(self.spends.fan_in.get()) else {
+            tracing::debug!(
+                pending = self.pool.pending(),
+                "no spendable notes; waiting for inclusions"
+            );
+            return Ok(false);
+        };
+
+        let outputs = self.pool.outputs_for(&inputs, self.spends.fan_out.get());
+        let signed_tx = match build_chained_transaction(self.pool.account(owner), &inputs, outputs)
+        {
+            Ok(tx) => tx,
+            Err(err) => {
+                self.pool.restore(owner, inputs);
+                return Err(err);
+            }
+        };
+        let signed_tx = Arc::new(signed_tx);
+        // The tracker, like the latency and propagation expectations, keys
+        // transactions by their mantle hash.
+        let tx_hash = signed_tx.hash();
+
+        tracing::debug!(
+            ?tx_hash,
+            inputs = inputs.len(),
+            outputs = signed_tx.mantle_tx().ledger_tx.outputs.len(),
+            "submitting chained transaction"
+        );
+
+        match submit_transaction_to_any_node(self.ctx, Arc::clone(&signed_tx)).await {
+            Ok(target) => {
+                self.tracker.record(tx_hash, target);
+                self.pool.track(&signed_tx, owner, inputs);
+                Ok(true)
+            }
+            Err(err) => {
+                tracing::warn!(?tx_hash, %err, "chained transaction submission failed");
+                self.pool.restore(owner, inputs);
+                Ok(false)
+            }
+        }
+    }
+}
+
+pub(super) fn build_chained_transaction(
+    owner: &WalletAccount,
+    inputs: &[Utxo],
+    outputs: Vec<Note>,
+) -> Result<SignedMantleTx, DynError> {
+    let builder = inputs.iter().fold(MantleTxBuilder::new(), |builder, utxo| {
+        builder.add_ledger_input(*utxo)
+    });
+    let builder = outputs
+        .into_iter()
+        .fold(builder, |builder, note| builder.add_ledger_output(note));
+
+    let mantle_tx = builder.build();
+    let tx_hash = mantle_tx.hash();
+
+    // One signature per input; every input belongs to the same owner.
+    let keys = vec![owner.secret_key.clone(); inputs.len()];
+    let signature = ZkKey::multi_sign(&keys, tx_hash.as_ref()).map_err(|err| {
+        format!("Chained transaction signing failed: could not sign transaction: {err}")
+    })?;
+
+    SignedMantleTx::new(mantle_tx, Vec::new(), signature).map_err(|err| {
+        format!("Chained transaction construction failed: invalid transaction structure: {err}")
+            .into()
+    })
+}
+
+/// Splits `total` into at most `parts` non-zero values that add up to it.
+pub(super) fn split_value(total: u64, parts: usize) -> Vec<u64> {
+    let parts = (parts as u64).clamp(1, total.max(1));
+    let base = total / parts;
+    let remainder = total % parts;
+    (0..parts)
+        .map(|idx| base + u64::from(idx < remainder))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lb_core::mantle::{Note, Transaction as _, Utxo, tx_builder::MantleTxBuilder};
+    use testing_framework_config::topology::configs::wallet::WalletAccount;
+    use tokio::time::Instant;
+
+    use super::{PENDING_TIMEOUT, UtxoPool, build_chained_transaction, split_value};
+
+    fn genesis_utxo(account: &WalletAccount) -> Utxo {
+        let seed = MantleTxBuilder::new()
+            .add_ledger_output(Note::new(account.value, account.public_key()))
+            .build();
+        Utxo::new(seed.ledger_tx.hash(), 0, seed.ledger_tx.outputs[0])
+    }
+
+    #[test]
+    fn included_spends_credit_their_outputs() {
+        let accounts = [
+            WalletAccount::deterministic(0, 100),
+            WalletAccount::deterministic(1, 100),
+        ];
+        let mut pool = UtxoPool::new(
+            accounts
+                .iter()
+                .map(|account| (account.clone(), genesis_utxo(account)))
+                .collect(),
+        );
+
+        let (owner, inputs) = pool.take_inputs(1).expect("genesis note");
+        let outputs = pool.outputs_for(&inputs, 2);
+        let tx =
+            build_chained_transaction(pool.account(owner), &inputs, outputs).expect("valid spend");
+        pool.track(&tx, owner, inputs);
+        assert_eq!((pool.spendable(), pool.pending()), (1, 1));
+
+        assert_eq!(pool.observe_transactions([&tx]), 1);
+        assert_eq!((pool.spendable(), pool.pending()), (3, 0));
+    }
+
+    #[test]
+    fn expired_and_orphaned_spends_are_not_double_spent() {
+        let account = WalletAccount::deterministic(0, 100);
+        let mut pool = UtxoPool::new(vec![(account.clone(), genesis_utxo(&account))]);
+
+        let (owner, inputs) = pool.take_inputs(1).expect("genesis note");
+        let outputs = pool.outputs_for(&inputs, 2);
+        let tx =
+            build_chained_transaction(pool.account(owner), &inputs, outputs).expect("valid spend");
+        pool.track(&tx, owner, inputs);
+
+        assert_eq!(pool.expire_pending(Instant::now() + PENDING_TIMEOUT), 1);
+        assert_eq!((pool.spendable(), pool.pending()), (0, 0));
+
+        // A late inclusion still credits the outputs.
+        assert_eq!(pool.observe_transactions([&tx]), 1);
+        assert_eq!(pool.spendable(), 2);
+
+        // An orphaned block withdraws them until the spend lands again.
+        pool.revert_transactions([&tx]);
+        assert_eq!(pool.spendable(), 0);
+        assert_eq!(pool.observe_transactions([&tx]), 1);
+        assert_eq!(pool.spendable(), 2);
+    }
+
+    #[test]
+    fn split_value_preserves_total_and_avoids_empty_notes() {
+        assert_eq!(split_value(10, 3), vec![4, 3, 3]);
+        assert_eq!(split_value(2, 5), vec![1, 1]);
+        assert_eq!(split_value(7, 1), vec![7]);
+        assert_eq!(split_value(100, 4).iter().sum::<u64>(), 100);
+    }
+}
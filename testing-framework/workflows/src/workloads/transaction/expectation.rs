@@ -15,7 +15,9 @@ use testing_framework_core::scenario::{DynError, Expectation, RunContext};
 use thiserror::Error;
 use tokio::{sync::broadcast, time::sleep};
 
-use super::workload::{SubmissionPlan, limited_user_count, submission_plan};
+use super::workload::{
+    SubmissionPlan, chained_submission_plan, limited_user_count, submission_plan,
+};
 
 const MIN_INCLUSION_RATIO: f64 = 0.5;
 const CATCHUP_POLL_INTERVAL: Duration = Duration::from_secs(1);
@@ -25,6 +27,7 @@ const MAX_CATCHUP_WAIT: Duration = Duration::from_secs(60);
 pub struct TxInclusionExpectation {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
+    chained: bool,
     capture_state: Option<CaptureState>,
 }
 
@@ -58,9 +61,18 @@ impl TxInclusionExpectation {
         Self {
             txs_per_block,
             user_limit,
+            chained: false,
             capture_state: None,
         }
     }
+
+    /// Expects the uncapped target count, as submitted by the chained-spend
+    /// mode, and tracks outputs to every participating account.
+    #[must_use]
+    pub const fn chained(mut self) -> Self {
+        self.chained = true;
+        self
+    }
 }
 
 #[async_trait]
@@ -157,11 +169,19 @@ fn build_capture_plan(
     }
 
     let available = limited_user_count(expectation.user_limit, wallet_accounts.len());
-    let plan = submission_plan(expectation.txs_per_block, ctx, available)?;
+    let (plan, tracked) = if expectation.chained {
+        (
+            chained_submission_plan(expectation.txs_per_block, ctx)?,
+            available,
+        )
+    } else {
+        let plan = submission_plan(expectation.txs_per_block, ctx, available)?;
+        (plan, plan.transaction_count)
+    };
 
     let wallet_pks = wallet_accounts
         .into_iter()
-        .take(plan.transaction_count)
+        .take(tracked)
         .map(|account| account.secret_key.to_public_key())
         .collect::<HashSet<ZkPublicKey>>();
 
@@ -0,0 +1,356 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::{Transaction as _, TxHash};
+use testing_framework_core::scenario::{BlockRecord, DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::{sync::broadcast, time::sleep};
+
+use super::tracker::{SubmissionRecord, SubmissionTracker};
+
+const DEFAULT_MIN_INCLUSION_RATIO: f64 = 0.5;
+const CATCHUP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_CATCHUP_WAIT: Duration = Duration::from_secs(60);
+const MAX_LISTED_MISSING: usize = 10;
+
+/// Limits enforced by [`TxLatencyExpectation`].
+///
+/// Latency is measured from the moment a node accepted the transaction to
+/// the moment the block feed first saw a block containing it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatencyThresholds {
+    min_inclusion_ratio: f64,
+    max_p50: Option<Duration>,
+    max_p95: Option<Duration>,
+    max_latency: Option<Duration>,
+}
+
+impl Default for LatencyThresholds {
+    fn default() -> Self {
+        Self {
+            min_inclusion_ratio: DEFAULT_MIN_INCLUSION_RATIO,
+            max_p50: None,
+            max_p95: None,
+            max_latency: None,
+        }
+    }
+}
+
+impl LatencyThresholds {
+    #[must_use]
+    /// Fraction of submitted transactions that must be included (clamped to
+    /// `0.0..=1.0`).
+    pub fn with_min_inclusion_ratio(mut self, ratio: f64) -> Self {
+        self.min_inclusion_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_p50(mut self, limit: Duration) -> Self {
+        self.max_p50 = Some(limit);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_p95(mut self, limit: Duration) -> Self {
+        self.max_p95 = Some(limit);
+        self
+    }
+
+    #[must_use]
+    /// Upper bound for the slowest included transaction.
+    pub const fn with_max_latency(mut self, limit: Duration) -> Self {
+        self.max_latency = Some(limit);
+        self
+    }
+}
+
+/// Matches every transaction recorded in a [`SubmissionTracker`] against the
+/// block feed and checks inclusion ratio and latency percentiles.
+#[derive(Clone)]
+pub struct TxLatencyExpectation {
+    tracker: SubmissionTracker,
+    thresholds: LatencyThresholds,
+    inclusions: Option<Arc<Mutex<HashMap<TxHash, Instant>>>>,
+}
+
+#[derive(Debug, Error)]
+enum TxLatencyError {
+    #[error("transaction latency expectation not captured")]
+    NotCaptured,
+    #[error("no transactions were submitted")]
+    NoSubmissions,
+    #[error(
+        "only {included}/{submitted} transactions included (required ratio {required:.2}); never included:\n{missing}"
+    )]
+    InsufficientInclusions {
+        included: usize,
+        submitted: usize,
+        required: f64,
+        missing: String,
+    },
+    #[error("inclusion latency {metric} {observed:?} exceeds limit {limit:?}")]
+    LatencyExceeded {
+        metric: &'static str,
+        observed: Duration,
+        limit: Duration,
+    },
+}
+
+struct LatencyStats {
+    submitted: usize,
+    latencies: Vec<Duration>,
+    missing: Vec<(TxHash, SubmissionRecord)>,
+}
+
+impl LatencyStats {
+    fn collect(
+        submissions: HashMap<TxHash, SubmissionRecord>,
+        inclusions: &HashMap<TxHash, Instant>,
+    ) -> Self {
+        let submitted = submissions.len();
+        let mut latencies = Vec::with_capacity(submitted);
+        let mut missing = Vec::new();
+        for (hash, record) in submissions {
+            match inclusions.get(&hash) {
+                Some(included_at) => {
+                    latencies.push(included_at.saturating_duration_since(record.submitted_at));
+                }
+                None => missing.push((hash, record)),
+            }
+        }
+        latencies.sort_unstable();
+        missing.sort_by_key(|(_, record)| record.submitted_at);
+
+        Self {
+            submitted,
+            latencies,
+            missing,
+        }
+    }
+
+    fn ratio(&self) -> f64 {
+        self.latencies.len() as f64 / self.submitted as f64
+    }
+
+    fn percentile(&self, percentile: f64) -> Option<Duration> {
+        percentile_of(&self.latencies, percentile)
+    }
+
+    fn max(&self) -> Option<Duration> {
+        self.latencies.last().copied()
+    }
+
+    fn missing_summary(&self) -> String {
+        let mut summary = String::new();
+        for (hash, record) in self.missing.iter().take(MAX_LISTED_MISSING) {
+            let _ = writeln!(summary, "- {hash:?} (submitted to {})", record.target);
+        }
+        if self.missing.len() > MAX_LISTED_MISSING {
+            let _ = writeln!(
+                summary,
+                "- ... and {} more",
+                self.missing.len() - MAX_LISTED_MISSING
+            );
+        }
+        summary
+    }
+}
+
+impl TxLatencyExpectation {
+    pub const NAME: &'static str = "tx_inclusion_latency";
+
+    #[must_use]
+    pub const fn new(tracker: SubmissionTracker, thresholds: LatencyThresholds) -> Self {
+        Self {
+            tracker,
+            thresholds,
+            inclusions: None,
+        }
+    }
+
+    /// Gives in-flight transactions a bounded amount of time to land before
+    /// the statistics are computed.
+    async fn wait_for_pending(
+        &self,
+        ctx: &RunContext,
+        inclusions: &Mutex<HashMap<TxHash, Instant>>,
+    ) {
+        let pending = || {
+            let included = inclusions
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.tracker
+                .snapshot()
+                .keys()
+                .filter(|hash| !included.contains_key(hash))
+                .count()
+        };
+
+        let security_param = ctx.descriptors().config().consensus_params.security_param;
+        let mut remaining = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .map(|interval| interval.mul_f64(security_param.get() as f64))
+            .unwrap_or(MAX_CATCHUP_WAIT)
+            .min(MAX_CATCHUP_WAIT);
+        while pending() > 0 && remaining > Duration::ZERO {
+            sleep(CATCHUP_POLL_INTERVAL).await;
+            remaining = remaining.saturating_sub(CATCHUP_POLL_INTERVAL);
+        }
+    }
+
+    fn check_limit(
+        metric: &'static str,
+        observed: Option<Duration>,
+        limit: Option<Duration>,
+    ) -> Result<(), TxLatencyError> {
+        match (observed, limit) {
+            (Some(observed), Some(limit)) if observed > limit => {
+                Err(TxLatencyError::LatencyExceeded {
+                    metric,
+                    observed,
+                    limit,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for TxLatencyExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.inclusions.is_some() {
+            return Ok(());
+        }
+
+        let inclusions = Arc::new(Mutex::new(HashMap::new()));
+        spawn_inclusion_capture(ctx.block_feed().subscribe(), Arc::clone(&inclusions));
+        self.inclusions = Some(inclusions);
+
+        tracing::info!(
+            thresholds = ?self.thresholds,
+            "tx latency expectation starting capture"
+        );
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let inclusions = self
+            .inclusions
+            .as_ref()
+            .ok_or(TxLatencyError::NotCaptured)?;
+        if self.tracker.is_empty() {
+            return Err(TxLatencyError::NoSubmissions.into());
+        }
+
+        self.wait_for_pending(ctx, inclusions).await;
+
+        let stats = {
+            let included = inclusions
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            LatencyStats::collect(self.tracker.snapshot(), &included)
+        };
+        let p50 = stats.percentile(50.0);
+        let p95 = stats.percentile(95.0);
+        let max = stats.max();
+
+        tracing::info!(
+            submitted = stats.submitted,
+            included = stats.latencies.len(),
+            missing = stats.missing.len(),
+            p50_ms = p50.map(|value| value.as_millis()),
+            p95_ms = p95.map(|value| value.as_millis()),
+            max_ms = max.map(|value| value.as_millis()),
+            "tx inclusion latency summary"
+        );
+        for (hash, record) in &stats.missing {
+            tracing::debug!(?hash, target = %record.target, "transaction never included");
+        }
+
+        if stats.ratio() < self.thresholds.min_inclusion_ratio {
+            return Err(TxLatencyError::InsufficientInclusions {
+                included: stats.latencies.len(),
+                submitted: stats.submitted,
+                required: self.thresholds.min_inclusion_ratio,
+                missing: stats.missing_summary(),
+            }
+            .into());
+        }
+
+        Self::check_limit("p50", p50, self.thresholds.max_p50)?;
+        Self::check_limit("p95", p95, self.thresholds.max_p95)?;
+        Self::check_limit("max", max, self.thresholds.max_latency)?;
+        Ok(())
+    }
+}
+
+fn spawn_inclusion_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    inclusions: Arc<Mutex<HashMap<TxHash, Instant>>>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let mut included = inclusions
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    for tx in record.block.transactions() {
+                        included.entry(tx.hash()).or_insert(record.first_seen);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "tx latency capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("tx latency capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Nearest-rank percentile over an ascending slice.
+fn percentile_of(sorted: &[Duration], percentile: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::percentile_of;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples = (1..=20).map(Duration::from_secs).collect::<Vec<_>>();
+        assert_eq!(percentile_of(&samples, 50.0), Some(Duration::from_secs(10)));
+        assert_eq!(percentile_of(&samples, 95.0), Some(Duration::from_secs(19)));
+        assert_eq!(
+            percentile_of(&samples, 100.0),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            percentile_of(&samples[..1], 95.0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(percentile_of(&[], 50.0), None);
+    }
+}
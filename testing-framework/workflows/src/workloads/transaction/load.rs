@@ -0,0 +1,658 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use lb_core::mantle::{Note, SignedMantleTx, Transaction as _, Utxo};
+use lb_key_management_system_service::keys::ZkPublicKey;
+use testing_framework_config::topology::configs::wallet::WalletAccount;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    task::JoinSet,
+    time::{MissedTickBehavior, interval},
+};
+
+use super::{
+    chained::{UtxoPool, build_chained_transaction},
+    latency::{LatencyThresholds, TxLatencyExpectation},
+    tracker::SubmissionTracker,
+    workload::{apply_user_limit, wallet_utxo_map},
+};
+
+const TICK: Duration = Duration::from_millis(10);
+const SAMPLE_WINDOW: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_IN_FLIGHT: NonZeroUsize = NonZeroUsize::new(256).unwrap();
+const DEFAULT_FAN_OUT: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+/// Target submission rate over the run, in transactions per second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RateProfile {
+    /// A fixed rate for the whole run.
+    Constant { tps: f64 },
+    /// Linear change from `from` to `to` over `over`, then holds `to`.
+    Ramp { from: f64, to: f64, over: Duration },
+    /// Starts at `start` and adds `step` every `every`; useful for
+    /// saturation curves.
+    Step {
+        start: f64,
+        step: f64,
+        every: Duration,
+    },
+    /// Runs at `base`, jumping to `peak` for `duration` starting at `at`.
+    Spike {
+        base: f64,
+        peak: f64,
+        at: Duration,
+        duration: Duration,
+    },
+}
+
+impl RateProfile {
+    /// Target rate at `elapsed` since the generator started (never negative).
+    #[must_use]
+    pub fn rate_at(&self, elapsed: Duration) -> f64 {
+        let rate = match *self {
+            Self::Constant { tps } => tps,
+            Self::Ramp { from, to, over } => {
+                if over.is_zero() || elapsed >= over {
+                    to
+                } else {
+                    from + (to - from) * (elapsed.as_secs_f64() / over.as_secs_f64())
+                }
+            }
+            Self::Step { start, step, every } => {
+                if every.is_zero() {
+                    start
+                } else {
+                    let steps = (elapsed.as_secs_f64() / every.as_secs_f64()).floor();
+                    start + step * steps
+                }
+            }
+            Self::Spike {
+                base,
+                peak,
+                at,
+                duration,
+            } => {
+                if elapsed >= at && elapsed < at + duration {
+                    peak
+                } else {
+                    base
+                }
+            }
+        };
+        rate.max(0.0)
+    }
+}
+
+impl Default for RateProfile {
+    fn default() -> Self {
+        Self::Constant { tps: 1.0 }
+    }
+}
+
+/// Target and achieved rate over one sampling window.
+#[derive(Clone, Debug, Default)]
+pub struct LoadSample {
+    /// End of the window, relative to the generator start.
+    pub elapsed: Duration,
+    pub target_tps: f64,
+    pub achieved_tps: f64,
+    pub errors: u64,
+}
+
+/// Running totals of a [`LoadGenerator`].
+#[derive(Clone, Debug, Default)]
+pub struct LoadSummary {
+    pub duration: Duration,
+    /// Transactions the profile asked for so far.
+    pub target_transactions: f64,
+    /// Transactions accepted by a node.
+    pub accepted: u64,
+    /// Submissions rejected by a node or failed in transport.
+    pub errors: u64,
+    pub errors_by_node: BTreeMap<String, u64>,
+    /// Submissions skipped because `max_in_flight` requests were outstanding.
+    pub skipped_in_flight: u64,
+    /// Submissions skipped because no spendable note was available.
+    pub starved: u64,
+    pub samples: Vec<LoadSample>,
+}
+
+impl LoadSummary {
+    #[must_use]
+    pub fn target_tps(&self) -> f64 {
+        rate(self.target_transactions, self.duration)
+    }
+
+    #[must_use]
+    pub fn achieved_tps(&self) -> f64 {
+        rate(self.accepted as f64, self.duration)
+    }
+
+    /// Accepted transactions as a fraction of the target.
+    #[must_use]
+    pub fn achieved_ratio(&self) -> f64 {
+        if self.target_transactions <= 0.0 {
+            return 1.0;
+        }
+        self.accepted as f64 / self.target_transactions
+    }
+}
+
+fn rate(count: f64, duration: Duration) -> f64 {
+    if duration.is_zero() {
+        0.0
+    } else {
+        count / duration.as_secs_f64()
+    }
+}
+
+/// Shared view of a generator's [`LoadSummary`], updated once per sampling
+/// window and at the end of the run.
+#[derive(Clone, Debug, Default)]
+pub struct LoadStats {
+    inner: Arc<Mutex<LoadSummary>>,
+}
+
+impl LoadStats {
+    #[must_use]
+    pub fn snapshot(&self) -> LoadSummary {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn publish(&self, summary: &LoadSummary) {
+        *self
+            .inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = summary.clone();
+    }
+}
+
+/// Open-loop transaction generator driven by a [`RateProfile`].
+///
+/// Submissions are spawned without waiting on earlier ones and spread
+/// round-robin across nodes, one attempt each. Notes are recycled the same
+/// way as in chained mode: outputs of included transactions are split into
+/// `fan_out` notes and spent again, so a modest number of wallets can feed a
+/// high rate once the note set has grown.
+#[derive(Clone)]
+pub struct LoadGenerator {
+    profile: RateProfile,
+    user_limit: Option<NonZeroUsize>,
+    fan_out: NonZeroUsize,
+    max_in_flight: NonZeroUsize,
+    min_achieved_ratio: Option<f64>,
+    latency: Option<LatencyThresholds>,
+    submissions: SubmissionTracker,
+    stats: LoadStats,
+    accounts: Vec<(WalletAccount, Utxo)>,
+}
+
+impl LoadGenerator {
+    #[must_use]
+    pub fn new(profile: RateProfile) -> Self {
+        Self {
+            profile,
+            user_limit: None,
+            fan_out: DEFAULT_FAN_OUT,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            min_achieved_ratio: None,
+            latency: None,
+            submissions: SubmissionTracker::new(),
+            stats: LoadStats::default(),
+            accounts: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_profile(mut self, profile: RateProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    #[must_use]
+    pub const fn profile(&self) -> RateProfile {
+        self.profile
+    }
+
+    #[must_use]
+    pub const fn with_user_limit(mut self, user_limit: Option<NonZeroUsize>) -> Self {
+        self.user_limit = user_limit;
+        self
+    }
+
+    #[must_use]
+    /// Number of notes each transaction splits its input into.
+    pub const fn with_fan_out(mut self, fan_out: NonZeroUsize) -> Self {
+        self.fan_out = fan_out;
+        self
+    }
+
+    #[must_use]
+    /// Upper bound on outstanding submissions; ticks beyond it are counted
+    /// as skipped instead of queued.
+    pub const fn with_max_in_flight(mut self, max_in_flight: NonZeroUsize) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    #[must_use]
+    /// Attaches an expectation that accepted transactions reach at least
+    /// `ratio` of the profile's target.
+    pub fn with_min_achieved_ratio(mut self, ratio: f64) -> Self {
+        self.min_achieved_ratio = Some(ratio.clamp(0.0, 1.0));
+        self
+    }
+
+    #[must_use]
+    /// Attaches a [`TxLatencyExpectation`] with the given thresholds.
+    pub const fn with_latency_thresholds(mut self, thresholds: LatencyThresholds) -> Self {
+        self.latency = Some(thresholds);
+        self
+    }
+
+    #[must_use]
+    pub const fn stats(&self) -> &LoadStats {
+        &self.stats
+    }
+
+    #[must_use]
+    pub const fn submissions(&self) -> &SubmissionTracker {
+        &self.submissions
+    }
+}
+
+impl Default for LoadGenerator {
+    fn default() -> Self {
+        Self::new(RateProfile::default())
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for LoadGenerator {
+    fn name(&self) -> &'static str {
+        "load_generator"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        let mut expectations: Vec<Box<dyn Expectation>> = Vec::new();
+        if let Some(ratio) = self.min_achieved_ratio {
+            expectations.push(Box::new(AchievedRateExpectation::new(
+                self.stats.clone(),
+                ratio,
+            )));
+        }
+        if let Some(thresholds) = self.latency {
+            expectations.push(Box::new(TxLatencyExpectation::new(
+                self.submissions.clone(),
+                thresholds,
+            )));
+        }
+        expectations
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let reference_node = descriptors
+            .nodes()
+            .first()
+            .ok_or("load generator requires at least one node in the topology")?;
+        let utxo_map: HashMap<ZkPublicKey, Utxo> = wallet_utxo_map(reference_node);
+
+        let mut accounts = descriptors
+            .config()
+            .wallet()
+            .accounts
+            .iter()
+            .filter_map(|account| {
+                utxo_map
+                    .get(&account.public_key())
+                    .map(|utxo| (account.clone(), *utxo))
+            })
+            .collect::<Vec<_>>();
+        apply_user_limit(&mut accounts, self.user_limit);
+
+        if accounts.is_empty() {
+            return Err(
+                "load generator initialization failed: no wallet accounts matched genesis notes"
+                    .into(),
+            );
+        }
+
+        tracing::info!(
+            accounts = accounts.len(),
+            profile = ?self.profile,
+            "load generator accounts prepared"
+        );
+        self.accounts = accounts;
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        Generator::new(self, ctx)?.run().await
+    }
+}
+
+/// Outcome of one spawned submission.
+struct Attempt {
+    owner: usize,
+    inputs: Vec<Utxo>,
+    node: String,
+    result: Result<SignedMantleTx, DynError>,
+}
+
+#[derive(Default)]
+struct Window {
+    target: f64,
+    accepted: u64,
+    errors: u64,
+}
+
+struct Generator<'a> {
+    config: &'a LoadGenerator,
+    ctx: &'a RunContext,
+    clients: Vec<ApiClient>,
+    next_client: usize,
+    pool: UtxoPool,
+    in_flight: JoinSet<Attempt>,
+    summary: LoadSummary,
+    window: Window,
+}
+
+impl<'a> Generator<'a> {
+    fn new(config: &'a LoadGenerator, ctx: &'a RunContext) -> Result<Self, DynError> {
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err("load generator requires at least one node client".into());
+        }
+        if config.accounts.is_empty() {
+            return Err("load generator has no genesis notes to spend".into());
+        }
+
+        Ok(Self {
+            config,
+            ctx,
+            clients,
+            next_client: 0,
+            pool: UtxoPool::new(config.accounts.clone()),
+            in_flight: JoinSet::new(),
+            summary: LoadSummary::default(),
+            window: Window::default(),
+        })
+    }
+
+    async fn run(mut self) -> Result<(), DynError> {
+        let mut events = self.ctx.block_feed().subscribe_events();
+        let mut ticker = interval(TICK);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let started = Instant::now();
+        let deadline = started + self.ctx.run_duration();
+        let mut last_tick = started;
+        let mut window_started = started;
+        let mut owed = 0.0_f64;
+
+        tracing::info!(
+            profile = ?self.config.profile,
+            nodes = self.clients.len(),
+            max_in_flight = self.config.max_in_flight.get(),
+            "load generator started"
+        );
+
+        while Instant::now() < deadline {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    let now = Instant::now();
+                    let target = self.config.profile.rate_at(now - started)
+                        * (now - last_tick).as_secs_f64();
+                    last_tick = now;
+                    owed += target;
+                    self.summary.target_transactions += target;
+                    self.window.target += target;
+
+                    while owed >= 1.0 {
+                        owed -= 1.0;
+                        self.issue();
+                    }
+
+                    self.pool.expire_pending(now.into());
+                    if now - window_started >= SAMPLE_WINDOW {
+                        self.close_window(now - window_started, now - started);
+                        window_started = now;
+                    }
+                }
+                Some(joined) = self.in_flight.join_next(), if !self.in_flight.is_empty() => {
+                    match joined {
+                        Ok(attempt) => self.settle(attempt),
+                        Err(err) => {
+                            tracing::debug!(%err, "load generator submission task failed");
+                            self.record_error("unknown".to_owned());
+                        }
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        self.pool.observe_event(&event);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "load generator lagged behind the block feed");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err("block feed closed during load generation".into());
+                    }
+                },
+            }
+        }
+
+        self.in_flight.abort_all();
+        self.summary.duration = started.elapsed();
+        self.config.stats.publish(&self.summary);
+
+        tracing::info!(
+            target_tps = self.summary.target_tps(),
+            achieved_tps = self.summary.achieved_tps(),
+            accepted = self.summary.accepted,
+            errors = self.summary.errors,
+            skipped_in_flight = self.summary.skipped_in_flight,
+            starved = self.summary.starved,
+            "load generator finished"
+        );
+        Ok(())
+    }
+
+    /// Spawns one submission without waiting for it.
+    fn issue(&mut self) {
+        if self.in_flight.len() >= self.config.max_in_flight.get() {
+            self.summary.skipped_in_flight += 1;
+            return;
+        }
+        let Some((owner, inputs)) = self.pool.take_inputs(1) else {
+            self.summary.starved += 1;
+            return;
+        };
+
+        let outputs = self.pool.outputs_for(&inputs, self.config.fan_out.get());
+        let account = self.pool.account(owner).clone();
+        let client = self.clients[self.next_client % self.clients.len()].clone();
+        self.next_client = self.next_client.wrapping_add(1);
+
+        self.in_flight.spawn(async move {
+            let result = submit_once(&client, &account, &inputs, outputs).await;
+            Attempt {
+                owner,
+                inputs,
+                node: client.base_url().to_string(),
+                result,
+            }
+        });
+    }
+
+    fn settle(&mut self, attempt: Attempt) {
+        match attempt.result {
+            Ok(tx) => {
+                self.pool.track(&tx, attempt.owner, attempt.inputs);
+                self.config.submissions.record(tx.hash(), attempt.node);
+                self.summary.accepted += 1;
+                self.window.accepted += 1;
+            }
+            Err(err) => {
+                tracing::debug!(node = %attempt.node, %err, "load generator submission failed");
+                self.pool.restore(attempt.owner, attempt.inputs);
+                self.record_error(attempt.node);
+            }
+        }
+    }
+
+    fn record_error(&mut self, node: String) {
+        self.summary.errors += 1;
+        self.window.errors += 1;
+        *self.summary.errors_by_node.entry(node).or_default() += 1;
+    }
+
+    fn close_window(&mut self, length: Duration, elapsed: Duration) {
+        let window = std::mem::take(&mut self.window);
+        let sample = LoadSample {
+            elapsed,
+            target_tps: rate(window.target, length),
+            achieved_tps: rate(window.accepted as f64, length),
+            errors: window.errors,
+        };
+        tracing::debug!(
+            elapsed_s = elapsed.as_secs_f64(),
+            target_tps = sample.target_tps,
+            achieved_tps = sample.achieved_tps,
+            errors = sample.errors,
+            spendable = self.pool.spendable(),
+            in_flight = self.in_flight.len(),
+            "load generator sample"
+        );
+        self.summary.samples.push(sample);
+        self.summary.duration = elapsed;
+        self.config.stats.publish(&self.summary);
+    }
+}
+
+async fn submit_once(
+    client: &ApiClient,
+    account: &WalletAccount,
+    inputs: &[Utxo],
+    outputs: Vec<Note>,
+) -> Result<SignedMantleTx, DynError> {
+    let tx = build_chained_transaction(account, inputs, outputs)?;
+    client
+        .submit_transaction(&tx)
+        .await
+        .map_err(|err| -> DynError { err.into() })?;
+    Ok(tx)
+}
+
+#[derive(Debug, Error)]
+enum LoadGeneratorError {
+    #[error(
+        "load generator achieved {achieved_tps:.2} tx/s of {target_tps:.2} target ({ratio:.2} < {required:.2}); {errors} errors"
+    )]
+    BelowTarget {
+        achieved_tps: f64,
+        target_tps: f64,
+        ratio: f64,
+        required: f64,
+        errors: u64,
+    },
+}
+
+/// Fails when a [`LoadGenerator`] accepted fewer transactions than the
+/// required fraction of its target.
+#[derive(Clone)]
+pub struct AchievedRateExpectation {
+    stats: LoadStats,
+    min_ratio: f64,
+}
+
+impl AchievedRateExpectation {
+    #[must_use]
+    pub const fn new(stats: LoadStats, min_ratio: f64) -> Self {
+        Self { stats, min_ratio }
+    }
+}
+
+#[async_trait]
+impl Expectation for AchievedRateExpectation {
+    fn name(&self) -> &'static str {
+        "load_achieved_rate"
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let summary = self.stats.snapshot();
+        let ratio = summary.achieved_ratio();
+        if ratio < self.min_ratio {
+            return Err(LoadGeneratorError::BelowTarget {
+                achieved_tps: summary.achieved_tps(),
+                target_tps: summary.target_tps(),
+                ratio,
+                required: self.min_ratio,
+                errors: summary.errors,
+            }
+            .into());
+        }
+
+        tracing::info!(
+            achieved_tps = summary.achieved_tps(),
+            target_tps = summary.target_tps(),
+            ratio,
+            "load generator reached its target rate"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RateProfile;
+
+    #[test]
+    fn profiles_follow_their_shape() {
+        let ramp = RateProfile::Ramp {
+            from: 10.0,
+            to: 50.0,
+            over: Duration::from_secs(40),
+        };
+        assert!((ramp.rate_at(Duration::from_secs(20)) - 30.0).abs() < f64::EPSILON);
+        assert!((ramp.rate_at(Duration::from_secs(90)) - 50.0).abs() < f64::EPSILON);
+
+        let step = RateProfile::Step {
+            start: 5.0,
+            step: 5.0,
+            every: Duration::from_secs(60),
+        };
+        assert!((step.rate_at(Duration::from_secs(59)) - 5.0).abs() < f64::EPSILON);
+        assert!((step.rate_at(Duration::from_secs(125)) - 15.0).abs() < f64::EPSILON);
+
+        let spike = RateProfile::Spike {
+            base: 2.0,
+            peak: 100.0,
+            at: Duration::from_secs(30),
+            duration: Duration::from_secs(5),
+        };
+        assert!((spike.rate_at(Duration::from_secs(29)) - 2.0).abs() < f64::EPSILON);
+        assert!((spike.rate_at(Duration::from_secs(32)) - 100.0).abs() < f64::EPSILON);
+        assert!((spike.rate_at(Duration::from_secs(35)) - 2.0).abs() < f64::EPSILON);
+    }
+}
@@ -1,5 +1,20 @@
+mod adversarial;
+mod chained;
 mod expectation;
+mod latency;
+mod load;
+mod tracker;
 mod workload;
 
+pub use adversarial::{
+    AdversarialWorkload, AttackKind, AttackLog, AttackRecord, SubmissionOutcome,
+    TxRejectionExpectation,
+};
+pub use chained::ChainedSpends;
 pub use expectation::TxInclusionExpectation;
+pub use latency::{LatencyThresholds, TxLatencyExpectation};
+pub use load::{
+    AchievedRateExpectation, LoadGenerator, LoadSample, LoadStats, LoadSummary, RateProfile,
+};
+pub use tracker::{SubmissionRecord, SubmissionTracker};
 pub use workload::Workload;
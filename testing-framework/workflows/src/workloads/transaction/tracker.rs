@@ -0,0 +1,63 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use lb_core::mantle::TxHash;
+
+/// When and where a transaction was handed to the cluster.
+#[derive(Clone, Debug)]
+pub struct SubmissionRecord {
+    pub submitted_at: Instant,
+    /// Base URL of the node that accepted the transaction.
+    pub target: String,
+}
+
+/// Shared log of every transaction a workload submitted, keyed by hash.
+///
+/// Cloning is cheap; clones share the same log so a workload and its
+/// expectations can hold one each.
+#[derive(Clone, Debug, Default)]
+pub struct SubmissionTracker {
+    inner: Arc<Mutex<HashMap<TxHash, SubmissionRecord>>>,
+}
+
+impl SubmissionTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, tx_hash: TxHash, target: impl Into<String>) {
+        let record = SubmissionRecord {
+            submitted_at: Instant::now(),
+            target: target.into(),
+        };
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(tx_hash, record);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn snapshot(&self) -> HashMap<TxHash, SubmissionRecord> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
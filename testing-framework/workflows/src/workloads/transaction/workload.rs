@@ -26,8 +26,16 @@ pub(super) struct SubmissionPlan {
 }
 use tokio::time::sleep;
 
-use super::expectation::TxInclusionExpectation;
-use crate::workloads::util::submit_transaction_via_cluster;
+use super::{
+    chained::{ChainedSpends, ChainedSubmission},
+    expectation::TxInclusionExpectation,
+    latency::{LatencyThresholds, TxLatencyExpectation},
+    tracker::SubmissionTracker,
+};
+use crate::{
+    expectations::{MempoolExpectation, MempoolThresholds},
+    workloads::util::submit_transaction_to_any_node,
+};
 
 const MAX_SUBMISSION_INTERVAL: Duration = Duration::from_secs(1);
 
@@ -35,6 +43,10 @@ const MAX_SUBMISSION_INTERVAL: Duration = Duration::from_secs(1);
 pub struct Workload {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
+    chained: Option<ChainedSpends>,
+    latency: Option<LatencyThresholds>,
+    mempool: Option<MempoolThresholds>,
+    submissions: SubmissionTracker,
     accounts: Vec<WalletInput>,
 }
 
@@ -51,10 +63,24 @@ impl ScenarioWorkload for Workload {
     }
 
     fn expectations(&self) -> Vec<Box<dyn Expectation>> {
-        vec![Box::new(TxInclusionExpectation::new(
-            self.txs_per_block,
-            self.user_limit,
-        ))]
+        let expectation = TxInclusionExpectation::new(self.txs_per_block, self.user_limit);
+        let mut expectations: Vec<Box<dyn Expectation>> = if self.chained.is_some() {
+            vec![Box::new(expectation.chained())]
+        } else {
+            vec![Box::new(expectation)]
+        };
+        if let Some(thresholds) = self.latency {
+            expectations.push(Box::new(TxLatencyExpectation::new(
+                self.submissions.clone(),
+                thresholds,
+            )));
+        }
+        if let Some(thresholds) = self.mempool {
+            expectations.push(Box::new(
+                MempoolExpectation::new(thresholds).with_submissions(self.submissions.clone()),
+            ));
+        }
+        expectations
     }
 
     fn init(
@@ -116,8 +142,26 @@ impl ScenarioWorkload for Workload {
         tracing::info!(
             txs_per_block = self.txs_per_block.get(),
             users = self.user_limit.map(|u| u.get()),
+            chained = ?self.chained,
             "starting transaction workload submission"
         );
+        if let Some(spends) = self.chained {
+            let plan = chained_submission_plan(self.txs_per_block, ctx)?;
+            let genesis = self
+                .accounts
+                .iter()
+                .map(|input| (input.account.clone(), input.utxo))
+                .collect();
+            return ChainedSubmission::new(
+                ctx,
+                spends,
+                genesis,
+                plan.submission_interval,
+                self.submissions.clone(),
+            )?
+            .execute()
+            .await;
+        }
         Submission::new(self, ctx)?.execute().await
     }
 }
@@ -130,6 +174,10 @@ impl Workload {
         Self {
             txs_per_block,
             user_limit: None,
+            chained: None,
+            latency: None,
+            mempool: None,
+            submissions: SubmissionTracker::new(),
             accounts: Vec::new(),
         }
     }
@@ -152,6 +200,36 @@ impl Workload {
         self.user_limit = user_limit;
         self
     }
+
+    /// Keeps spending the workload's own included outputs for the whole run
+    /// instead of submitting one transaction per genesis note.
+    #[must_use]
+    pub const fn with_chained_spends(mut self, spends: ChainedSpends) -> Self {
+        self.chained = Some(spends);
+        self
+    }
+
+    /// Attaches a [`TxLatencyExpectation`] with the given thresholds.
+    #[must_use]
+    pub const fn with_latency_thresholds(mut self, thresholds: LatencyThresholds) -> Self {
+        self.latency = Some(thresholds);
+        self
+    }
+
+    /// Attaches a [`MempoolExpectation`] that also checks propagation of this
+    /// workload's transactions.
+    #[must_use]
+    pub const fn with_mempool_thresholds(mut self, thresholds: MempoolThresholds) -> Self {
+        self.mempool = Some(thresholds);
+        self
+    }
+
+    /// Every transaction submitted by this workload, with submission time and
+    /// target node.
+    #[must_use]
+    pub const fn submissions(&self) -> &SubmissionTracker {
+        &self.submissions
+    }
 }
 
 impl Default for Workload {
@@ -164,6 +242,7 @@ struct Submission<'a> {
     plan: VecDeque<WalletInput>,
     ctx: &'a RunContext,
     interval: Duration,
+    tracker: SubmissionTracker,
 }
 
 impl<'a> Submission<'a> {
@@ -193,6 +272,7 @@ impl<'a> Submission<'a> {
             plan,
             ctx,
             interval: submission_plan.submission_interval,
+            tracker: workload.submissions.clone(),
         })
     }
 
@@ -205,7 +285,7 @@ impl<'a> Submission<'a> {
         );
 
         while let Some(input) = self.plan.pop_front() {
-            submit_wallet_transaction(self.ctx, &input).await?;
+            submit_wallet_transaction(self.ctx, &input, &self.tracker).await?;
 
             if !self.interval.is_zero() {
                 sleep(self.interval).await;
@@ -218,14 +298,21 @@ impl<'a> Submission<'a> {
     }
 }
 
-async fn submit_wallet_transaction(ctx: &RunContext, input: &WalletInput) -> Result<(), DynError> {
+async fn submit_wallet_transaction(
+    ctx: &RunContext,
+    input: &WalletInput,
+    tracker: &SubmissionTracker,
+) -> Result<(), DynError> {
     let signed_tx = Arc::new(build_wallet_transaction(input)?);
+    let tx_hash = signed_tx.hash();
     tracing::debug!(
-        tx_hash = ?signed_tx.hash(),
+        ?tx_hash,
         user = ?input.account.public_key(),
         "submitting wallet transaction"
     );
-    submit_transaction_via_cluster(ctx, signed_tx).await
+    let target = submit_transaction_to_any_node(ctx, signed_tx).await?;
+    tracker.record(tx_hash, target);
+    Ok(())
 }
 
 fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynError> {
@@ -251,7 +338,7 @@ fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynEr
     })
 }
 
-fn wallet_utxo_map(node: &GeneratedNodeConfig) -> HashMap<ZkPublicKey, Utxo> {
+pub(super) fn wallet_utxo_map(node: &GeneratedNodeConfig) -> HashMap<ZkPublicKey, Utxo> {
     let genesis_tx = node.general.consensus_config.genesis_tx.clone();
     let ledger_tx = genesis_tx.mantle_tx().ledger_tx.clone();
 
@@ -265,7 +352,7 @@ fn wallet_utxo_map(node: &GeneratedNodeConfig) -> HashMap<ZkPublicKey, Utxo> {
         .collect()
 }
 
-fn apply_user_limit<T>(items: &mut Vec<T>, user_limit: Option<NonZeroUsize>) {
+pub(super) fn apply_user_limit<T>(items: &mut Vec<T>, user_limit: Option<NonZeroUsize>) {
     if let Some(limit) = user_limit {
         let allowed = limit.get().min(items.len());
 
@@ -286,6 +373,24 @@ pub(super) fn submission_plan(
         return Err("Transaction workload planning failed: no accounts available for transaction scheduling".into());
     }
 
+    let actual_transactions_to_submit =
+        target_transaction_count(txs_per_block, ctx).min(available_accounts as u64) as usize;
+
+    plan_for_count(ctx, actual_transactions_to_submit)
+}
+
+/// Plan for chained spends: the target rate is not capped by the number of
+/// accounts because outputs are re-spent as soon as they are included.
+pub(super) fn chained_submission_plan(
+    txs_per_block: NonZeroU64,
+    ctx: &RunContext,
+) -> Result<SubmissionPlan, DynError> {
+    let target =
+        usize::try_from(target_transaction_count(txs_per_block, ctx)).unwrap_or(usize::MAX);
+    plan_for_count(ctx, target)
+}
+
+fn target_transaction_count(txs_per_block: NonZeroU64, ctx: &RunContext) -> u64 {
     let run_secs = ctx.run_duration().as_secs_f64();
     let block_secs = ctx
         .run_metrics()
@@ -294,24 +399,23 @@ pub(super) fn submission_plan(
         .as_secs_f64();
 
     let estimated_blocks_in_run = run_secs / block_secs;
-    let target_transaction_count = (estimated_blocks_in_run * txs_per_block.get() as f64)
+    (estimated_blocks_in_run * txs_per_block.get() as f64)
         .floor()
-        .clamp(0.0, u64::MAX as f64) as u64;
-
-    let actual_transactions_to_submit =
-        target_transaction_count.min(available_accounts as u64) as usize;
+        .clamp(0.0, u64::MAX as f64) as u64
+}
 
-    if actual_transactions_to_submit == 0 {
+fn plan_for_count(ctx: &RunContext, transaction_count: usize) -> Result<SubmissionPlan, DynError> {
+    if transaction_count == 0 {
         return Err("Transaction workload planning failed: calculated zero transactions to submit based on run duration and target rate".into());
     }
 
-    let mut submission_interval =
-        Duration::from_secs_f64(run_secs / actual_transactions_to_submit as f64);
+    let run_secs = ctx.run_duration().as_secs_f64();
+    let mut submission_interval = Duration::from_secs_f64(run_secs / transaction_count as f64);
     if submission_interval > MAX_SUBMISSION_INTERVAL {
         submission_interval = MAX_SUBMISSION_INTERVAL;
     }
     Ok(SubmissionPlan {
-        transaction_count: actual_transactions_to_submit,
+        transaction_count,
         submission_interval,
     })
 }
@@ -7,7 +7,7 @@ use lb_core::{
         ops::{Op, channel::MsgId},
     },
 };
-use rand::{seq::SliceRandom as _, thread_rng};
+use reqwest::Url;
 use testing_framework_core::scenario::{DynError, RunContext};
 use tracing::debug;
 
@@ -41,16 +41,23 @@ pub async fn submit_transaction_via_cluster(
     ctx: &RunContext,
     tx: Arc<SignedMantleTx>,
 ) -> Result<(), DynError> {
+    submit_transaction_to_any_node(ctx, tx).await.map(|_| ())
+}
+
+/// Like [`submit_transaction_via_cluster`], but returns the base URL of the
+/// node that accepted the transaction.
+pub async fn submit_transaction_to_any_node(
+    ctx: &RunContext,
+    tx: Arc<SignedMantleTx>,
+) -> Result<Url, DynError> {
     let tx_hash = tx.hash();
     debug!(?tx_hash, "submitting transaction via cluster (nodes first)");
 
     let node_clients = ctx.node_clients();
-    let mut clients = node_clients.node_clients();
-    clients.shuffle(&mut thread_rng());
 
     let mut last_err = None;
     for attempt in 0..SUBMIT_RETRIES {
-        clients.shuffle(&mut thread_rng());
+        let clients = node_clients.shuffled_clients();
 
         for client in &clients {
             let url = client.base_url().clone();
@@ -60,7 +67,7 @@ pub async fn submit_transaction_via_cluster(
                 .await
                 .map_err(|err| -> DynError { err.into() })
             {
-                Ok(()) => return Ok(()),
+                Ok(()) => return Ok(url),
                 Err(err) => {
                     debug!(?tx_hash, %url, attempt, "transaction submission failed");
                     last_err = Some(err);